@@ -6,6 +6,7 @@ mod newick_node;
 mod newick_graph;
 mod validation;
 mod builder;
+mod subgraph;
 
 pub use newick_name::*;
 pub use newick_weight::*;
@@ -13,3 +14,4 @@ pub use newick_reticulation::*;
 pub use newick_node::*;
 pub use newick_graph::*;
 pub use builder::*;
+pub use subgraph::*;
@@ -63,6 +63,57 @@ impl NewickWeight {
 
     #[inline(always)]
     pub fn fractional_part(&self) -> u32 { self.fractional_part as u32 }
+
+    /// Adds two weights together, treating `fractional_part` as decimal
+    /// digits after the dot (i.e. `1.25 + 0.5 == 1.75`). Trailing zeros
+    /// of the resulting fractional part are removed.
+    /// 
+    /// Returns `None` if the result exceeds [`NewickWeight::max_value()`]
+    /// or if its fractional part would start with zero (e.g. `0.25 + 0.8`),
+    /// since such value cannot be represented by [`NewickWeight`].
+    pub fn checked_add(self, other: NewickWeight) -> Option<NewickWeight> {
+        let left_digits = decimal_digits(self.fractional_part());
+        let right_digits = decimal_digits(other.fractional_part());
+        let digits = core::cmp::max(left_digits, right_digits);
+        let modulus = 10u64.pow(digits);
+
+        let mut fractional
+            = u64::from(self.fractional_part()) * 10u64.pow(digits - left_digits)
+            + u64::from(other.fractional_part()) * 10u64.pow(digits - right_digits);
+        let mut integral
+            = u64::from(self.integral_part())
+            + u64::from(other.integral_part());
+
+        if fractional >= modulus {
+            fractional -= modulus;
+            integral += 1;
+        }
+
+        if fractional > 0 && fractional < modulus / 10 {
+            return None;
+        }
+
+        while fractional > 0 && fractional % 10 == 0 {
+            fractional /= 10;
+        }
+
+        let max = u64::from(NewickWeight::max_value());
+        if integral > max || fractional > max {
+            return None;
+        }
+
+        let result = unsafe {
+            Self::new_unchecked(integral as u32, fractional as u32)
+        };
+        Some(result)
+    }
+}
+
+fn decimal_digits(value: u32) -> u32 {
+    match value.checked_ilog10() {
+        Some(log) => log + 1,
+        None => 1,
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -0,0 +1,188 @@
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap)]
+
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash}};
+
+use smallvec::SmallVec;
+
+use super::{
+    InvalidGraphError,
+    NewickGraph,
+    NewickGraphBuilder,
+    NewickNodeId,
+    NewickWeight,
+    OptionalNewickReticulation,
+    OptionalNewickWeight};
+
+
+#[derive(Debug)]
+pub enum ExtractSubgraphError {
+    NoMatchingLeaves,
+    UnrepresentableWeight,
+    GraphError(InvalidGraphError),
+}
+
+impl From<InvalidGraphError> for ExtractSubgraphError {
+    fn from(value: InvalidGraphError) -> Self {
+        Self::GraphError(value)
+    }
+}
+
+impl NewickGraph {
+    /// Builds new [`NewickGraph`] induced by leaves with names in `taxa`.
+    /// 
+    /// All other leaves are removed together with branches that no longer
+    /// lead to any leaf. Resulting unary nodes (single parent, single child)
+    /// are suppressed and their weights are added to the weight of
+    /// their child, see [`NewickWeight::checked_add`]. A unary node is kept
+    /// when its child is a reticulation (i.e. has multiple parents), since
+    /// the child's weight is shared between all of its parents.
+    /// 
+    /// # Errors
+    /// * [`ExtractSubgraphError::NoMatchingLeaves`] if no leaf name is in `taxa`
+    /// * [`ExtractSubgraphError::UnrepresentableWeight`] if summed weights
+    ///   cannot be represented by [`NewickWeight`]
+    /// * [`ExtractSubgraphError::GraphError`] if resulting graph is invalid
+    pub fn extract_subgraph<T, S>(&self, taxa: &HashSet<T, S>)
+        -> Result<NewickGraph, ExtractSubgraphError>
+        where T: Borrow<str> + Hash + Eq,
+              S: BuildHasher
+    {
+        let mut extractor = SubgraphExtractor::new(self);
+        let mut visited = vec![false; self.nodes().len()];
+        if !extractor.mark_kept(self.root_id(), taxa, &mut visited) {
+            return Err(ExtractSubgraphError::NoMatchingLeaves);
+        }
+        extractor.add_node(self.root_id(), None)?;
+        let graph = extractor.builder.build()?;
+        Ok(graph)
+    }
+}
+
+struct SubgraphExtractor<'a> {
+    graph: &'a NewickGraph,
+    kept: Vec<bool>,
+    parents_count: Vec<usize>,
+    new_ids: HashMap<NewickNodeId, NewickNodeId>,
+    builder: NewickGraphBuilder,
+}
+
+impl<'a> SubgraphExtractor<'a> {
+    fn new(graph: &'a NewickGraph) -> Self {
+        let len = graph.nodes().len();
+        let mut parents_count = vec![0; len];
+        for node in graph.nodes() {
+            for child in graph.get_children(node.id()) {
+                parents_count[child.value() as usize] += 1;
+            }
+        }
+
+        Self {
+            graph: graph,
+            kept: vec![false; len],
+            parents_count: parents_count,
+            new_ids: HashMap::new(),
+            builder: NewickGraphBuilder::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn is_kept(&self, id: NewickNodeId) -> bool {
+        self.kept[id.value() as usize]
+    }
+
+    fn mark_kept<T, S>(&mut self, id: NewickNodeId, taxa: &HashSet<T, S>, visited: &mut [bool])
+        -> bool
+        where T: Borrow<str> + Hash + Eq,
+              S: BuildHasher
+    {
+        let idx = id.value() as usize;
+        if visited[idx] {
+            return self.kept[idx];
+        }
+        visited[idx] = true;
+
+        let children = self.graph.get_children(id);
+        let result = if children.is_empty() {
+            let name = self.graph.nodes()[idx].name().as_str();
+            taxa.contains(name)
+        } else {
+            let mut any_kept = false;
+            for child in children {
+                any_kept |= self.mark_kept(*child, taxa, visited);
+            }
+            any_kept
+        };
+
+        self.kept[idx] = result;
+        result
+    }
+
+    fn add_node(&mut self, id: NewickNodeId, incoming: Option<NewickWeight>)
+        -> Result<NewickNodeId, ExtractSubgraphError>
+    {
+        if let Some(new_id) = self.new_ids.get(&id) {
+            return Ok(*new_id);
+        }
+
+        let node = &self.graph.nodes()[id.value() as usize];
+        let weight = add_weights(incoming, node.weight())?;
+
+        let kept_children: SmallVec<[NewickNodeId; 2]> = self.graph
+            .get_children(id)
+            .iter()
+            .copied()
+            .filter(|child| self.is_kept(*child))
+            .collect();
+
+        if kept_children.len() == 1 && self.is_suppressible(id, kept_children[0]) {
+            return self.add_node(kept_children[0], weight);
+        }
+
+        let mut new_children = SmallVec::<[NewickNodeId; 2]>::with_capacity(kept_children.len());
+        for child in kept_children {
+            new_children.push(self.add_node(child, None)?);
+        }
+
+        let optional_weight = match weight {
+            Some(value) => OptionalNewickWeight::some(value),
+            None => OptionalNewickWeight::none(),
+        };
+
+        let new_id = self.builder.add_node(
+            node.name().clone(),
+            optional_weight,
+            node.reticulation().map_or_else(
+                OptionalNewickReticulation::none,
+                |ret| OptionalNewickReticulation::some(ret.clone())),
+            &new_children);
+        self.new_ids.insert(id, new_id);
+        Ok(new_id)
+    }
+
+    #[inline(always)]
+    fn is_suppressible(&self, id: NewickNodeId, child: NewickNodeId) -> bool {
+        self.parents_count[id.value() as usize] <= 1
+            && self.parents_count[child.value() as usize] <= 1
+    }
+}
+
+fn add_weights(left: Option<NewickWeight>, right: Option<NewickWeight>)
+    -> Result<Option<NewickWeight>, ExtractSubgraphError>
+{
+    match (left, right) {
+        (Some(first), Some(second)) => {
+            match first.checked_add(second) {
+                Some(value) => Ok(Some(value)),
+                None => Err(ExtractSubgraphError::UnrepresentableWeight),
+            }
+        },
+        (Some(value), None) | (None, Some(value)) => Ok(Some(value)),
+        (None, None) => Ok(None),
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature="async")]
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::common::{QUOTE, SEMICOLON};
+
+use super::{deserialize, DeserializeError, DeserializeOk};
+
+/// Deserializes instance of [`NewickGraph`][crate::ast::NewickGraph]
+/// from [`AsyncRead`].
+/// 
+/// The input is read byte by byte up to the terminating `';'` (the same
+/// amount of bytes [`deserialize`] would consume) and then passed to the
+/// synchronous deserializer. Wrap `input` in [`tokio::io::BufReader`] when
+/// reading from unbuffered sources.
+/// 
+/// # Errors
+/// * [`DeserializeError::FormatError`] if not a valid Newick format
+/// * [`DeserializeError::GraphError`] if error on graph building
+/// * [`DeserializeError::InputError`] if invalid input
+/// * [`DeserializeError::Utf8`] if input is not a valid UTF-8 string
+pub async fn deserialize_async<TRead: AsyncRead + Unpin>(input: &mut TRead)
+    -> Result<DeserializeOk, DeserializeError>
+{
+    let buffer = read_graph_bytes(input).await?;
+    let mut stream = buffer.as_slice();
+    deserialize(&mut stream)
+}
+
+async fn read_graph_bytes<TRead: AsyncRead + Unpin>(input: &mut TRead)
+    -> Result<Vec<u8>, DeserializeError>
+{
+    // Both QUOTE and SEMICOLON are ascii, so it is enough to scan raw bytes.
+    // Escaped quotes ("") toggle the state twice, which keeps it consistent.
+    const QUOTE_BYTE: u8 = QUOTE as u8;
+    const SEMICOLON_BYTE: u8 = SEMICOLON as u8;
+
+    let mut result = Vec::with_capacity(64);
+    let mut buffer = [0u8; 1];
+    let mut in_quotes = false;
+    loop {
+        if input.read(&mut buffer).await? == 0 {
+            // Let the synchronous deserializer report unexpected end of input.
+            return Ok(result);
+        }
+
+        let byte = buffer[0];
+        result.push(byte);
+        match byte {
+            QUOTE_BYTE => { in_quotes = !in_quotes; },
+            SEMICOLON_BYTE if !in_quotes => { return Ok(result); },
+            _ => { }
+        }
+    }
+}
@@ -2,11 +2,14 @@
 mod ok;
 mod error;
 mod models;
+mod impl_async;
 
 use std::io::Read;
 
 pub use ok::*;
 pub use error::*;
+#[cfg(feature="async")]
+pub use impl_async::*;
 
 
 /// Deserializes instance of [`NewickGraph`][crate::ast::NewickGraph]
@@ -0,0 +1,24 @@
+#![cfg(feature="async")]
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::ast::NewickGraph;
+
+use super::{serialize, SerializeError, SerializeOk};
+
+/// Serializes instance of [`NewickGraph`] into [`AsyncWrite`].
+/// 
+/// The graph is serialized into memory with [`serialize`] first, and
+/// then written to `output` at once.
+/// 
+/// # Errors
+/// * [`SerializeError::InvalidInput`] if graph is inconsistent
+/// * [`SerializeError::OutputError`] if couldn't write to underlying stream
+pub async fn serialize_async<TWrite: AsyncWrite + Unpin>(output: &mut TWrite, graph: &NewickGraph)
+    -> Result<SerializeOk, SerializeError>
+{
+    let mut buffer = Vec::new();
+    let result = serialize(&mut buffer, graph)?;
+    output.write_all(&buffer).await?;
+    Ok(result)
+}
@@ -2,11 +2,14 @@
 mod ok;
 mod error;
 mod models;
+mod impl_async;
 
 use std::io::Write;
 
 pub use ok::*;
 pub use error::*;
+#[cfg(feature="async")]
+pub use impl_async::*;
 
 use crate::ast::NewickGraph;
 
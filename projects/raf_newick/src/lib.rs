@@ -41,6 +41,10 @@
 //!     assert!(graph.get_children(*right_child).is_empty());
 //! }
 //! ```
+//! 
+//! # Features
+//! * `async` enables `deserialize_async` and `serialize_async` functions,
+//!   which work with tokio's `AsyncRead` and `AsyncWrite` traits.
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(
     clippy::needless_return,
@@ -0,0 +1,70 @@
+#![cfg(feature="async")]
+
+use raf_newick::{
+    deserializer::{deserialize_async, deserialize_from_str, DeserializeError},
+    serializer::{serialize_async, serialize_to_string}};
+use rstest::rstest;
+use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+
+
+#[rstest]
+#[case(";")]
+#[case("(,);")]
+#[case("((A,B),C:1.7)ROOT;")]
+#[case("((A#1,B#NN2),C:3.1)ROOT;")]
+#[case("((,)\"te;st\",);")]
+#[tokio::test]
+async fn test_deserialize_async(#[case] repr: &str) {
+    let (mut client, mut server) = duplex(4);
+    let text = repr.to_owned();
+    let writer = tokio::spawn(async move {
+        client.write_all(text.as_bytes()).await.unwrap();
+        client.write_all(b"trailing").await.unwrap();
+    });
+
+    let result = deserialize_async(&mut server).await.unwrap();
+    let expected = deserialize_from_str(repr).unwrap();
+    assert_eq!(result.graph, expected.graph);
+    assert_eq!(result.read_bytes, expected.read_bytes);
+
+    let mut rest = String::new();
+    server.read_to_string(&mut rest).await.unwrap();
+    assert_eq!(rest, "trailing");
+    writer.await.unwrap();
+}
+
+
+#[rstest]
+#[case(";")]
+#[case("(,);")]
+#[case("((A,B),C:1.7)ROOT;")]
+#[case("((A#1,B#NN2),C:3.1)ROOT;")]
+#[case("((,)\"te;st\",);")]
+#[tokio::test]
+async fn test_serialize_async(#[case] repr: &str) {
+    let graph = deserialize_from_str(repr).unwrap().graph;
+    let (mut client, mut server) = duplex(4);
+    let reader = tokio::spawn(async move {
+        let mut output = String::new();
+        server.read_to_string(&mut output).await.unwrap();
+        output
+    });
+
+    let result = serialize_async(&mut client, &graph).await.unwrap();
+    drop(client);
+
+    let output = reader.await.unwrap();
+    assert_eq!(output, serialize_to_string(&graph).unwrap());
+    assert_eq!(result.written_bytes, output.len());
+}
+
+
+#[tokio::test]
+async fn test_deserialize_async_unexpected_end() {
+    let (mut client, mut server) = duplex(16);
+    client.write_all(b"((A,B),C").await.unwrap();
+    drop(client);
+
+    let result = deserialize_async(&mut server).await;
+    assert!(matches!(result, Err(DeserializeError::InputError(_))));
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use raf_newick::{
+    ast::{ExtractSubgraphError, NewickWeight},
+    deserializer::deserialize_from_str,
+    serializer::serialize_to_string};
+use raf_newick_tests::convert_to_graph;
+use rstest::rstest;
+
+
+#[rstest]
+#[case("((A,B),C);", &["A", "B", "C"], "((A,B),C);")]
+#[case("((A,B),C);", &["A", "C"], "(A,C);")]
+#[case("((A,B),C);", &["A", "B"], "(A,B);")]
+#[case("((A,B),C);", &["B"], "B;")]
+#[case("((A,B),(C,(D,E)));", &["A", "D", "E"], "(A,(D,E));")]
+#[case("((A,B),(C,(D,E)));", &["A", "E"], "(A,E);")]
+#[case("((A:1.5,B:2.0):0.5,C:1.25);", &["A", "C"], "(A:2.0,C:1.25);")]
+#[case("((A:1.5,B:2.0):0.5,C:1.25);", &["A"], "A:2.0;")]
+#[case("(((A:0.25,B):0.75,C):1.0,D);", &["A", "D"], "(A:2.0,D);")]
+#[case("((A,B)X,C)ROOT;", &["A", "B", "Z"], "(A,B)X;")]
+fn test_extract_subgraph(
+    #[case] repr: &str,
+    #[case] taxa: &[&str],
+    #[case] expected: &str
+) {
+    let graph = deserialize_from_str(repr).unwrap().graph;
+    let taxa: HashSet<&str> = taxa.iter().copied().collect();
+    let subgraph = graph.extract_subgraph(&taxa).unwrap();
+    assert_eq!(serialize_to_string(&subgraph).unwrap(), expected);
+}
+
+
+#[rstest]
+#[case(&["A", "B", "C"], "((B#5,A),((B#5),C));")]
+#[case(&["A", "B"], "((B#5,A),(B#5));")]
+#[case(&["A", "C"], "(A,C);")]
+#[case(&["B"], "((B#5),(B#5));")]
+fn test_extract_subnetwork(
+    #[case] taxa: &[&str],
+    #[case] expected: &str
+) {
+    let graph = convert_to_graph(
+        &[(0, 1), (0, 2), (1, 4), (1, 5), (2, 3), (2, 6), (3, 4)],
+        &[(5, "A"), (6, "C"), (4, "B")]);
+    let taxa: HashSet<String> = taxa.iter().map(|name| (*name).to_owned()).collect();
+    let subgraph = graph.extract_subgraph(&taxa).unwrap();
+    assert_eq!(serialize_to_string(&subgraph).unwrap(), expected);
+}
+
+
+#[test]
+fn test_extract_subgraph_no_matching_leaves() {
+    let graph = deserialize_from_str("((A,B),C);").unwrap().graph;
+    let taxa = HashSet::from(["X"]);
+    let result = graph.extract_subgraph(&taxa);
+    assert!(matches!(result, Err(ExtractSubgraphError::NoMatchingLeaves)));
+}
+
+
+#[rstest]
+#[case((0, 0), (0, 0), (0, 0))]
+#[case((1, 5), (2, 0), (3, 5))]
+#[case((1, 25), (0, 5), (1, 75))]
+#[case((0, 5), (0, 5), (1, 0))]
+#[case((0, 95), (0, 5), (1, 45))]
+#[case((0, 125), (0, 5), (0, 625))]
+fn test_weight_checked_add(
+    #[case] left: (u32, u32),
+    #[case] right: (u32, u32),
+    #[case] expected: (u32, u32)
+) {
+    let left = NewickWeight::new(left.0, left.1).unwrap();
+    let right = NewickWeight::new(right.0, right.1).unwrap();
+    let result = left.checked_add(right).unwrap();
+    assert_eq!((result.integral_part(), result.fractional_part()), expected);
+}
+
+
+#[test]
+fn test_weight_checked_add_overflow() {
+    let max = NewickWeight::new(NewickWeight::max_value(), 0).unwrap();
+    let one = NewickWeight::new(1, 0).unwrap();
+    assert!(max.checked_add(one).is_none());
+}
+
+
+#[test]
+fn test_weight_checked_add_unrepresentable() {
+    let left = NewickWeight::new(0, 25).unwrap();
+    let right = NewickWeight::new(0, 8).unwrap();
+    assert!(left.checked_add(right).is_none());
+}
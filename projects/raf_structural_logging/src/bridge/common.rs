@@ -0,0 +1,99 @@
+#![cfg(any(feature="log", feature="tracing"))]
+use std::{collections::HashMap, sync::{Arc, LazyLock, RwLock}};
+
+use raf_array::immutable_string::ImmutableString;
+
+use crate::{
+    core::{CoreLogger, CoreLoggerFactory},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{LogLevel, StructuralLog, StructuralLoggerFactory}};
+
+static MESSAGE: LazyLock<ImmutableString>
+    = LazyLock::new(|| ImmutableString::new("message").unwrap());
+
+/// Log converted from a record of another logging ecosystem.
+pub(super) struct BridgeLog<'a> {
+    pub(super) log_level: LogLevel,
+    pub(super) message: String,
+    pub(super) fields: Vec<(&'a str, SLObject)>,
+}
+
+/// Converts `::` separated target into dotted logger name.
+pub(super) fn logger_name(target: &str) -> String {
+    target.replace("::", ".")
+}
+
+/// Loggers created by a bridge, one per target. Targets are usually
+/// module paths, so their number stays bounded.
+pub(super) struct LoggerCache {
+    factory: Arc<CoreLoggerFactory>,
+    loggers: RwLock<HashMap<String, Arc<CoreLogger>>>,
+}
+
+impl LoggerCache {
+    pub(super) fn new(factory: Arc<CoreLoggerFactory>) -> Self {
+        Self {
+            factory: factory,
+            loggers: RwLock::default(),
+        }
+    }
+
+    #[cfg(feature="log")]
+    #[inline(always)]
+    pub(super) fn factory(&self) -> &CoreLoggerFactory { &self.factory }
+
+    /// Returns logger for `target`, creating it on first use.
+    pub(super) fn logger(&self, target: &str) -> Arc<CoreLogger> {
+        if let Some(logger) = self.loggers
+            .read()
+            .expect("LoggerCache - loggers.read() fail")
+            .get(target)
+        {
+            return logger.clone();
+        }
+
+        self.loggers
+            .write()
+            .expect("LoggerCache - loggers.write() fail")
+            .entry(target.to_owned())
+            .or_insert_with(|| Arc::new(self.factory.create_from_str(&logger_name(target))))
+            .clone()
+    }
+}
+
+#[inline(always)]
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(['@', '$'])
+        && !key.chars().any(|chr| chr.is_whitespace() || matches!(chr, '{' | '}' | ',' | ':'))
+}
+
+impl StructuralLog for BridgeLog<'_> {
+    fn log_data(&self) -> LogDataHolder {
+        let mut template = String::from("{message}");
+        let mut template_params = HashMap::with_capacity(self.fields.len() + 1);
+        template_params.insert(MESSAGE.clone(), SLObject::from(self.message.as_str()));
+
+        let mut fields: Vec<&(&str, SLObject)> = self.fields
+            .iter()
+            .filter(|(key, _)| is_valid_key(key) && *key != MESSAGE.as_str())
+            .collect();
+        fields.sort_by_key(|(key, _)| *key);
+        for (key, value) in fields {
+            let Ok(imm) = ImmutableString::new(key) else { continue };
+            if template_params.insert(imm, value.clone()).is_none() {
+                template.push(' ');
+                template.push_str(key);
+                template.push_str("={");
+                template.push_str(key);
+                template.push('}');
+            }
+        }
+
+        LogDataHolder::new(
+            self.log_level,
+            TemplateBuilder::global().create_from_str(&template),
+            template_params)
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature="log")]
+use std::sync::Arc;
+
+use log::kv::{Key, Value, VisitSource};
+
+use crate::{
+    core::CoreLoggerFactory,
+    models::SLObject,
+    traits::{LogLevel, StructuralLogger}};
+
+use super::common::{BridgeLog, LoggerCache};
+
+/// Implementation of `log::Log` that routes records through
+/// [`CoreLoggerFactory`]. Structured key-values become template parameters.
+/// Loggers are created once per target and reused.
+pub struct LogBridge {
+    loggers: LoggerCache,
+}
+
+impl LogBridge {
+    pub fn new(factory: Arc<CoreLoggerFactory>) -> Self {
+        Self {
+            loggers: LoggerCache::new(factory),
+        }
+    }
+
+    /// Installs the bridge as the global `log` logger, and lets all
+    /// records through `log`'s max level, leaving filtering to the factory.
+    /// 
+    /// # Errors
+    /// * [`log::SetLoggerError`] if a global logger is already installed
+    pub fn install(self) -> Result<(), log::SetLoggerError> {
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(log::LevelFilter::Trace);
+        Ok(())
+    }
+}
+
+/// Maps `log::Level` into [`LogLevel`], `Trace` becomes [`LogLevel::Debug`].
+pub fn from_log_level(level: log::Level) -> LogLevel {
+    match level {
+        log::Level::Trace | log::Level::Debug => LogLevel::Debug,
+        log::Level::Info => LogLevel::Info,
+        log::Level::Warn => LogLevel::Warning,
+        log::Level::Error => LogLevel::Error,
+    }
+}
+
+struct KeyValues<'a> {
+    fields: Vec<(&'a str, SLObject)>,
+}
+
+impl<'kvs> VisitSource<'kvs> for KeyValues<'kvs> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        let Some(key) = key.to_borrowed_str() else {
+            return Ok(());
+        };
+        let value = if let Some(number) = value.to_i64() {
+            SLObject::from(number)
+        } else if let Some(flag) = value.to_bool() {
+            SLObject::from(flag)
+        } else {
+            SLObject::from(value.to_string())
+        };
+        self.fields.push((key, value));
+        Ok(())
+    }
+}
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.loggers
+            .logger(metadata.target())
+            .is_enabled(from_log_level(metadata.level()))
+    }
+
+    fn log(&self, record: &log::Record) {
+        let log_level = from_log_level(record.level());
+        let logger = self.loggers.logger(record.target());
+        if !logger.is_enabled(log_level) {
+            return;
+        }
+
+        let mut key_values = KeyValues { fields: Vec::new() };
+        let _ = record.key_values().visit(&mut key_values);
+        logger.log(BridgeLog {
+            log_level: log_level,
+            message: record.args().to_string(),
+            fields: key_values.fields,
+        });
+    }
+
+    fn flush(&self) {
+        self.loggers.factory().flush();
+    }
+}
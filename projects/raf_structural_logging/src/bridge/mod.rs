@@ -0,0 +1,21 @@
+//! Bridges that route logs of other logging ecosystems through
+//! [`CoreLoggerFactory`][crate::core::CoreLoggerFactory]:
+//! * `log` feature enables [`LogBridge`], an implementation of `log::Log`
+//! * `tracing` feature enables [`TracingBridge`], an implementation
+//!   of `tracing_subscriber::Layer`
+//! 
+//! Both bridges convert records into logs with `message` template parameter
+//! holding the formatted message and one template parameter per structured
+//! key-value or field. The synthesized template depends on keys only, e.g.
+//! `{message} user={user}`, so that the number of distinct templates stays
+//! bounded. Logger name is the record's target with `::` replaced by `.`,
+//! which makes it compatible with level rules of
+//! [`LevelFilter`][crate::core::LevelFilter].
+mod common;
+mod log_bridge;
+mod tracing_bridge;
+
+#[cfg(feature="log")]
+pub use log_bridge::*;
+#[cfg(feature="tracing")]
+pub use tracing_bridge::*;
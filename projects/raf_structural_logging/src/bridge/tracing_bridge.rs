@@ -0,0 +1,103 @@
+#![cfg(feature="tracing")]
+use std::{fmt::Debug, sync::Arc};
+
+use tracing_core::{field::{Field, Visit}, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::{
+    core::CoreLoggerFactory,
+    models::SLObject,
+    traits::{LogLevel, StructuralLogger}};
+
+use super::common::{BridgeLog, LoggerCache};
+
+/// Implementation of `tracing_subscriber::Layer` that routes events through
+/// [`CoreLoggerFactory`]. Event fields become template parameters, with
+/// the `message` field being the formatted message. Spans are ignored.
+/// Loggers are created once per target and reused. Events below the level
+/// of their logger are skipped in [`Layer::on_event`] rather than disabled,
+/// so that other layers of the subscriber still receive them.
+pub struct TracingBridge {
+    loggers: LoggerCache,
+}
+
+impl TracingBridge {
+    pub fn new(factory: Arc<CoreLoggerFactory>) -> Self {
+        Self {
+            loggers: LoggerCache::new(factory),
+        }
+    }
+}
+
+/// Maps `tracing` level into [`LogLevel`], `TRACE` becomes
+/// [`LogLevel::Debug`].
+pub fn from_tracing_level(level: Level) -> LogLevel {
+    if level == Level::ERROR {
+        LogLevel::Error
+    } else if level == Level::WARN {
+        LogLevel::Warning
+    } else if level == Level::INFO {
+        LogLevel::Info
+    } else {
+        LogLevel::Debug
+    }
+}
+
+struct Fields {
+    message: String,
+    fields: Vec<(&'static str, SLObject)>,
+}
+
+impl Visit for Fields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.push((field.name(), SLObject::from(value)));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        let value = match i64::try_from(value) {
+            Ok(number) => SLObject::from(number),
+            Err(_) => SLObject::from(value.to_string()),
+        };
+        self.fields.push((field.name(), value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.push((field.name(), SLObject::from(value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            value.clone_into(&mut self.message);
+        } else {
+            self.fields.push((field.name(), SLObject::from(value)));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        let text = format!("{value:?}");
+        if field.name() == "message" {
+            self.message = text;
+        } else {
+            self.fields.push((field.name(), SLObject::from(text)));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TracingBridge {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let log_level = from_tracing_level(*metadata.level());
+        let logger = self.loggers.logger(metadata.target());
+        if !logger.is_enabled(log_level) {
+            return;
+        }
+
+        let mut fields = Fields { message: String::new(), fields: Vec::new() };
+        event.record(&mut fields);
+        logger.log(BridgeLog {
+            log_level: log_level,
+            message: fields.message,
+            fields: fields.fields,
+        });
+    }
+}
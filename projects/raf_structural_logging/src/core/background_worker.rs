@@ -1,85 +1,188 @@
 use std::{
-    sync::{mpsc::{channel, Receiver, Sender, TryRecvError}, Arc},
-    thread::{self, JoinHandle}};
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration};
 
-use crate::{models::LogDataHolder, traits::StructuralLogHandler};
+use raf_array::immutable_string::ImmutableString;
 
-enum Event {
-    Exit,
-    LogData(LogDataHolder),
+use crate::{
+    models::LogDataHolder,
+    template::{Template, TemplateBuilder},
+    traits::LogLevel};
+
+use super::{
+    dispatcher::{
+        flush_handler,
+        flush_handlers,
+        handle_batch,
+        handle_log,
+        handle_log_by,
+        handle_unique_log},
+    log_queue::{LogQueue, QueueItem},
+    sampling::RepeatCheck,
+    shared_config::SharedConfig,
+    HandlerId,
+    OverflowPolicy};
+
+static DROPPED_TEMPLATE: LazyLock<Template>
+    = LazyLock::new(|| TemplateBuilder::global().create_from_str("{dropped_count} logs dropped"));
+
+static DROPPED_COUNT: LazyLock<ImmutableString>
+    = LazyLock::new(|| ImmutableString::new("dropped_count").unwrap());
+
+static WORKER_NAME: LazyLock<ImmutableString>
+    = LazyLock::new(|| ImmutableString::new("raf_structural_logging").unwrap());
+
+/// Decides which handlers receive logs from [`BackgroundWorker`].
+#[derive(Clone, Copy)]
+pub(super) enum WorkerTarget {
+    /// Each log is passed to all handlers.
+    AllHandlers,
+
+    /// Up to given number of queued logs are passed at once to all
+    /// handlers, see [`StructuralLogHandler::handle_batch`].
+    /// 
+    /// [`StructuralLogHandler::handle_batch`]: crate::traits::StructuralLogHandler::handle_batch
+    AllHandlersBatched(usize),
+
+    /// Logs are passed only to the handler with given id. Deduplication
+    /// is the responsibility of the sender.
+    Handler(HandlerId),
 }
 
+/// Thread that passes queued logs to handlers.
 pub(super) struct BackgroundWorker {
-    join_handle: Option<JoinHandle<()>>,
-    tx: Sender<Event>,
+    join_handle: Mutex<Option<JoinHandle<()>>>,
+    queue: Arc<LogQueue>,
+    config: Arc<SharedConfig>,
+    target: WorkerTarget,
 }
 
-fn handle_log(
-    is_running: &mut bool,
-    ev: Event,
-    handlers: &mut Vec<Arc<dyn StructuralLogHandler>>)
+/// Builds synthetic log that informs how many logs were dropped due
+/// to queue overflow.
+fn dropped_log(dropped: u64, config: &SharedConfig) -> LogDataHolder {
+    let mut template_params = HashMap::with_capacity(1);
+    template_params.insert(
+        DROPPED_COUNT.clone(),
+        i64::try_from(dropped).unwrap_or(i64::MAX).into());
+    let mut log_data = LogDataHolder::new(
+        LogLevel::Warning,
+        DROPPED_TEMPLATE.clone(),
+        template_params);
+    log_data.set_logger_name(config.logger_name_key().clone(), WORKER_NAME.clone());
+    log_data
+}
+
+fn run_in_background(queue: &LogQueue, config: &SharedConfig, target: WorkerTarget)
 {
-    match ev {
-        Event::LogData(log_data) => {
-            if !log_data.is_empty() {
-                for handler in handlers {
-                    handler.handle(&log_data);
+    match target {
+        WorkerTarget::AllHandlers => {
+            while let Some(item) = queue.pop() {
+                match item {
+                    QueueItem::LogData(log_data) => handle_unique_log(&log_data, config),
+                    QueueItem::Dropped(dropped) => handle_log(&dropped_log(dropped, config), config),
                 }
             }
         },
-        Event::Exit => {
-            *is_running = false;
+        WorkerTarget::AllHandlersBatched(max_len) => {
+            while let Some(items) = queue.pop_batch(max_len) {
+                handle_batch(&to_batch(items, config), config);
+            }
+        },
+        WorkerTarget::Handler(id) => {
+            while let Some(item) = queue.pop() {
+                match item {
+                    QueueItem::LogData(log_data) => handle_log_by(&log_data, config, id),
+                    QueueItem::Dropped(dropped) => handle_log_by(&dropped_log(dropped, config), config, id),
+                }
+            }
         },
     }
 }
 
-fn run_in_background(
-    rx: &Receiver<Event>,
-    mut handlers: Vec<Arc<dyn StructuralLogHandler>>)
-{
-    let mut is_running = true;
-
-    while is_running {
-        let ev = rx.recv().expect("BackgroundWorker - rx.recv() fail");
-        handle_log(&mut is_running, ev, &mut handlers);
-
-        loop {
-            match rx.try_recv() {
-                Ok(ev) => handle_log(&mut is_running, ev, &mut handlers),
-                Err(err) => {
-                    if err == TryRecvError::Empty {
-                        break;
-                    }
-                    panic!("Couldn't handle {err} error on rx.try_recv().");
+/// Converts queued items into logs, skipping repeated logs and adding
+/// synthetic logs, in order.
+fn to_batch(items: Vec<QueueItem>, config: &SharedConfig) -> Vec<LogDataHolder> {
+    let mut batch = Vec::with_capacity(items.len());
+    for item in items {
+        match item {
+            QueueItem::LogData(log_data) => {
+                if let RepeatCheck::New(summary) = config.sampler().check_repeat(&log_data) {
+                    batch.extend(summary);
+                    batch.push(log_data);
                 }
-            }
+            },
+            QueueItem::Dropped(dropped) => batch.push(dropped_log(dropped, config)),
         }
     }
+    batch
 }
 
 impl BackgroundWorker {
-    pub(super) fn new(handlers: Vec<Arc<dyn StructuralLogHandler>>) -> Self {
-        let (tx, rx) = channel::<Event>();
+    pub(super) fn new(
+        config: Arc<SharedConfig>,
+        capacity: Option<usize>,
+        overflow_policy: OverflowPolicy,
+        target: WorkerTarget) -> Self
+    {
+        let queue = Arc::new(LogQueue::new(capacity, overflow_policy));
+        let worker_queue = queue.clone();
+        let worker_config = config.clone();
         let handle = thread::spawn(move || {
-            run_in_background(&rx, handlers);
+            run_in_background(&worker_queue, &worker_config, target);
         });
 
         Self {
-            tx: tx,
-            join_handle: Some(handle)
+            queue: queue,
+            config: config,
+            target: target,
+            join_handle: Mutex::new(Some(handle)),
         }
     }
 
+    #[inline(always)]
     pub(super) fn send_log(&self, log_data: LogDataHolder) {
-        self.tx.send(Event::LogData(log_data)).expect("BackgroundWorker - tx.send() fail");
+        self.queue.push(log_data);
+    }
+
+    #[inline(always)]
+    pub(super) fn dropped_logs(&self) -> u64 {
+        self.queue.dropped()
+    }
+
+    pub(super) fn flush(&self, timeout: Option<Duration>) -> bool {
+        if !self.queue.wait_drained(timeout) {
+            return false;
+        }
+        self.flush_target();
+        true
+    }
+
+    fn flush_target(&self) {
+        match self.target {
+            WorkerTarget::AllHandlers | WorkerTarget::AllHandlersBatched(_) => {
+                flush_handlers(&self.config);
+            },
+            WorkerTarget::Handler(id) => flush_handler(&self.config, id),
+        }
+    }
+
+    pub(super) fn shutdown(&self) {
+        let handle = self.join_handle
+            .lock()
+            .expect("BackgroundWorker shutdown - lock() fail")
+            .take();
+        if let Some(handle) = handle {
+            self.queue.close();
+            handle.join().expect("BackgroundWorker shutdown - handle.join() fail");
+            self.flush_target();
+        }
     }
 }
 
 impl Drop for BackgroundWorker {
     fn drop(&mut self) {
-        if let Some(handle) = self.join_handle.take() {
-            self.tx.send(Event::Exit).expect("BackgroundWorker drop - tx.send() fail");
-            handle.join().expect("BackgroundWorker drop - handle.join() fail");
-        }
+        self.shutdown();
     }
 }
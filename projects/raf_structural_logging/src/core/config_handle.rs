@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::traits::{LogLevel, StructuralLogHandler};
+
+use super::{
+    level_filter::parse_directives,
+    shared_config::SharedConfig,
+    HandlerId,
+    LevelFilter,
+    ParseLevelFilterError};
+
+/// Allows changing configuration of [`super::CoreLoggerFactory`] at
+/// runtime. Changes are visible to all loggers, including those created
+/// before the change, and to the background worker. Logs already queued
+/// are dispatched to handlers registered at the moment of dispatch.
+#[derive(Clone)]
+pub struct CoreLoggerConfigHandle {
+    config: Arc<SharedConfig>,
+}
+
+impl CoreLoggerConfigHandle {
+    pub(super) fn new(config: Arc<SharedConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Returns copy of current [`LevelFilter`].
+    pub fn level_filter(&self) -> LevelFilter {
+        self.config.level_filter()
+    }
+
+    /// Atomically replaces current [`LevelFilter`].
+    pub fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.config.update_level_filter(|current| *current = level_filter);
+    }
+
+    /// Sets default level, see [`LevelFilter::set_default_level`].
+    pub fn set_log_level(&self, log_level: LogLevel) {
+        self.config.update_level_filter(|current| current.set_default_level(log_level));
+    }
+
+    /// Adds level rule, see [`LevelFilter::add_rule`].
+    pub fn add_level_rule(&self, prefix: &str, log_level: LogLevel) {
+        self.config.update_level_filter(|current| current.add_rule(prefix, log_level));
+    }
+
+    /// Applies level directives, see [`LevelFilter::apply_directives`].
+    /// Directives are parsed first and then applied atomically, so nothing
+    /// changes if they are invalid.
+    /// 
+    /// # Errors
+    /// Same as [`LevelFilter::apply_directives`]
+    pub fn apply_level_directives(&self, directives: &str)
+        -> Result<(), ParseLevelFilterError>
+    {
+        let rules = parse_directives(directives)?;
+        self.config.update_level_filter(|current| current.add_rules(&rules));
+        Ok(())
+    }
+
+    /// Returns ids of registered handlers, in order of registration.
+    pub fn handler_ids(&self) -> Vec<HandlerId> {
+        self.config.handlers().iter().map(|entry| entry.id).collect()
+    }
+
+    /// Adds `handler` that receives all logs.
+    pub fn add_handler(&self, handler: Arc<dyn StructuralLogHandler>) -> HandlerId {
+        self.config.add_handler(handler, LogLevel::Debug)
+    }
+
+    /// Adds `handler` that receives only logs with at least `log_level`.
+    pub fn add_handler_with_level(&self, handler: Arc<dyn StructuralLogHandler>, log_level: LogLevel)
+        -> HandlerId
+    {
+        self.config.add_handler(handler, log_level)
+    }
+
+    /// Removes handler. Returns `false` if there was no such handler.
+    pub fn remove_handler(&self, id: HandlerId) -> bool {
+        self.config.remove_handler(id)
+    }
+
+    /// Changes minimal level of logs received by handler. Returns `false`
+    /// if there was no such handler.
+    pub fn set_handler_level(&self, id: HandlerId, log_level: LogLevel) -> bool {
+        self.config.set_handler_level(id, log_level)
+    }
+}
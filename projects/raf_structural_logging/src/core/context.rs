@@ -0,0 +1,99 @@
+use std::{cell::RefCell, marker::PhantomData};
+
+use raf_array::immutable_string::ImmutableString;
+
+use crate::models::{LogDataHolder, SLObject};
+
+/// Key/value pairs attached to logs.
+pub(super) type ContextData = Vec<(ImmutableString, SLObject)>;
+
+thread_local! {
+    static CONTEXT_STACK: RefCell<ContextData> = const { RefCell::new(Vec::new()) };
+}
+
+/// Computes data attached to every log, see
+/// [`super::CoreLoggerFactoryBuilder::add_enricher`].
+pub(super) enum Enricher {
+    Static(ImmutableString, SLObject),
+    Dynamic(ImmutableString, Box<dyn Fn() -> SLObject + Send + Sync>),
+}
+
+/// Keeps data pushed by [`push_context`] on the current thread's context
+/// stack. The data is removed when the guard is dropped.
+pub struct ContextGuard {
+    length: usize,
+    // Context is bound to the thread it was pushed on.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT_STACK.with_borrow_mut(|stack| stack.truncate(self.length));
+    }
+}
+
+/// Pushes key/value pairs on the current thread's context stack. Until
+/// the returned [`ContextGuard`] is dropped, the pairs are attached to
+/// `additional_data` of every log sent from this thread by
+/// [`super::CoreLogger`]. Guards are expected to be dropped in reverse
+/// order of creation, dropping an outer guard removes inner data as well.
+/// 
+/// # Panics
+/// When key cannot be converted into [`ImmutableString`].
+pub fn push_context<'a, I, V>(context: I) -> ContextGuard
+    where I: IntoIterator<Item = (&'a str, V)>,
+          V: Into<SLObject>
+{
+    let context = to_context_data(context);
+    let length = CONTEXT_STACK.with_borrow_mut(|stack| {
+        let length = stack.len();
+        stack.extend(context);
+        length
+    });
+    ContextGuard { length: length, _not_send: PhantomData }
+}
+
+pub(super) fn to_context_data<'a, I, V>(context: I) -> ContextData
+    where I: IntoIterator<Item = (&'a str, V)>,
+          V: Into<SLObject>
+{
+    context
+        .into_iter()
+        .map(|(key, value)| {
+            let key = ImmutableString::new(key)
+                .expect("to_context_data() fail on new ImmutableString");
+            (key, value.into())
+        })
+        .collect()
+}
+
+#[inline(always)]
+fn insert_missing<F>(log_data: &mut LogDataHolder, key: &ImmutableString, value: F)
+    where F: FnOnce() -> SLObject
+{
+    if !log_data.additional_data().contains_key(key) {
+        log_data.update_data(key.clone(), value());
+    }
+}
+
+/// Attaches context to `log_data`. Data already present in the log
+/// wins over logger's context, which wins over thread's context stack
+/// (inner over outer), which wins over enrichers.
+pub(super) fn enrich(log_data: &mut LogDataHolder, logger_context: &ContextData, enrichers: &[Enricher]) {
+    for (key, value) in logger_context {
+        insert_missing(log_data, key, || value.clone());
+    }
+
+    CONTEXT_STACK.with_borrow(|stack| {
+        for (key, value) in stack.iter().rev() {
+            insert_missing(log_data, key, || value.clone());
+        }
+    });
+
+    for enricher in enrichers {
+        match enricher {
+            Enricher::Static(key, value) => insert_missing(log_data, key, || value.clone()),
+            Enricher::Dynamic(key, func) => insert_missing(log_data, key, func),
+        }
+    }
+}
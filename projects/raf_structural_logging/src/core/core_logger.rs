@@ -1,26 +1,128 @@
-use std::sync::Arc;
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
 
 use raf_array::immutable_string::ImmutableString;
 
-use crate::{template::get_logger_name_key, traits::{LogLevel, StructuralLog, StructuralLogger}};
+use crate::{
+    models::SLObject,
+    traits::{LogLevel, StructuralLog, StructuralLogger}};
 
-use super::background_worker::BackgroundWorker;
+use super::{
+    dispatcher::Dispatcher,
+    context::{enrich, to_context_data, ContextData},
+    shared_config::SharedConfig};
 
-/// Logger that sends every log into a background worker through a thread-safe
-/// queue.
+const LEVEL_BITS: u32 = 2;
+const LEVEL_MASK: u64 = (1 << LEVEL_BITS) - 1;
+
+/// Logger that sends every log to handlers through a dispatcher shared
+/// by all loggers of a factory, see [`super::DispatchMode`].
 pub struct CoreLogger {
-    log_level: LogLevel,
     name: ImmutableString,
-    worker: Arc<BackgroundWorker>,
+    config: Arc<SharedConfig>,
+    dispatcher: Arc<Dispatcher>,
+    context: Arc<ContextData>,
+    // Packs config generation and resolved LogLevel into a single value,
+    // so that both are always read consistently.
+    cached_level: AtomicU64,
 }
 
 impl CoreLogger {
     pub(super) fn new(
-        log_level: LogLevel,
         name: ImmutableString,
-        worker: Arc<BackgroundWorker>) -> Self
+        config: Arc<SharedConfig>,
+        dispatcher: Arc<Dispatcher>) -> Self
+    {
+        let cached_level = resolve_level(&name, &config);
+        Self {
+            name: name,
+            config: config,
+            dispatcher: dispatcher,
+            context: Arc::default(),
+            cached_level: AtomicU64::new(cached_level),
+        }
+    }
+
+    /// Creates logger with the same name, that additionally attaches
+    /// `context` to `additional_data` of every log. Context of the child
+    /// logger wins over context inherited from this logger.
+    /// 
+    /// # Panics
+    /// When key cannot be converted into [`ImmutableString`].
+    #[must_use]
+    pub fn with_context<'a, I, V>(&self, context: I) -> CoreLogger
+        where I: IntoIterator<Item = (&'a str, V)>,
+              V: Into<SLObject>
     {
-        Self { log_level, name, worker }
+        let mut new_context = to_context_data(context);
+        new_context.extend(self.context.iter().cloned());
+        Self {
+            name: self.name.clone(),
+            config: self.config.clone(),
+            dispatcher: self.dispatcher.clone(),
+            context: Arc::new(new_context),
+            cached_level: AtomicU64::new(self.cached_level.load(Ordering::Acquire)),
+        }
+    }
+
+    /// Creates logger named `{parent}.{name}`, e.g. child `pool` of logger
+    /// `db` is named `db.pool`, so that level rules for `db` apply to it
+    /// as well. The child inherits context of this logger. Empty `name`
+    /// keeps the name of this logger.
+    /// 
+    /// # Panics
+    /// When the new name cannot be converted into [`ImmutableString`].
+    #[must_use]
+    pub fn child(&self, name: &str) -> CoreLogger {
+        let child_name = if name.is_empty() {
+            self.name.clone()
+        } else if self.name.as_str().is_empty() {
+            new_name(name)
+        } else {
+            new_name(&format!("{}.{name}", self.name.as_str()))
+        };
+        let cached_level = resolve_level(&child_name, &self.config);
+        Self {
+            name: child_name,
+            config: self.config.clone(),
+            dispatcher: self.dispatcher.clone(),
+            context: self.context.clone(),
+            cached_level: AtomicU64::new(cached_level),
+        }
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &ImmutableString { &self.name }
+
+    /// Returns current minimal [`LogLevel`] of this logger.
+    pub fn log_level(&self) -> LogLevel {
+        let mut cached_level = self.cached_level.load(Ordering::Acquire);
+        if cached_level >> LEVEL_BITS != self.config.generation() {
+            cached_level = resolve_level(&self.name, &self.config);
+            self.cached_level.store(cached_level, Ordering::Release);
+        }
+        level_from_bits(cached_level & LEVEL_MASK)
+    }
+}
+
+fn new_name(name: &str) -> ImmutableString {
+    ImmutableString::new(name)
+        .expect("CoreLogger - new ImmutableString fail")
+}
+
+fn resolve_level(name: &ImmutableString, config: &SharedConfig) -> u64 {
+    // Generation has to be read before the level. Otherwise concurrent
+    // update could be missed until the next one.
+    let generation = config.generation();
+    let log_level = config.level_for(name.as_str());
+    (generation << LEVEL_BITS) | (log_level as u64)
+}
+
+fn level_from_bits(bits: u64) -> LogLevel {
+    match bits {
+        0 => LogLevel::Debug,
+        1 => LogLevel::Info,
+        2 => LogLevel::Warning,
+        _ => LogLevel::Error,
     }
 }
 
@@ -28,12 +130,18 @@ impl CoreLogger {
 impl StructuralLogger for CoreLogger {
     fn log<T>(&self, log: T) where T : StructuralLog {
         let mut log_data = log.log_data();
-        let log_level = log_data.log_level() as i32;
-        let self_log_level = self.log_level as i32;
-        if log_level < self_log_level {
+        if !self.is_enabled(log_data.log_level())
+            || !self.config.sampler().should_log(&log_data)
+        {
             return;
         }
-        log_data.update_data(get_logger_name_key().clone(), self.name.clone());
-        self.worker.send_log(log_data);
+        enrich(&mut log_data, &self.context, self.config.enrichers());
+        log_data.set_logger_name(self.config.logger_name_key().clone(), self.name.clone());
+        self.dispatcher.send_log(log_data);
+    }
+
+    #[inline(always)]
+    fn is_enabled(&self, log_level: LogLevel) -> bool {
+        (log_level as i32) >= (self.log_level() as i32)
     }
 }
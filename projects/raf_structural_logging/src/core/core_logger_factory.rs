@@ -1,23 +1,79 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use raf_array::immutable_string::ImmutableString;
 
-use crate::traits::{LogLevel, StructuralLoggerFactory};
+use crate::traits::StructuralLoggerFactory;
 
-use super::{background_worker::BackgroundWorker, CoreLogger};
+use super::{
+    dispatcher::Dispatcher,
+    shared_config::SharedConfig,
+    CoreLogger,
+    CoreLoggerConfigHandle};
 
-/// Factory that creates loggers over a shared thread-safe background worker.
+/// Factory that creates loggers over a shared thread-safe dispatcher.
 pub struct CoreLoggerFactory {
-    log_level: LogLevel,
-    worker: Arc<BackgroundWorker>,
+    config: Arc<SharedConfig>,
+    dispatcher: Arc<Dispatcher>,
 }
 
 impl CoreLoggerFactory {
     pub(super) fn new(
-        log_level: LogLevel,
-        worker: Arc<BackgroundWorker>) -> Self
+        config: Arc<SharedConfig>,
+        dispatcher: Arc<Dispatcher>) -> Self
     {
-        Self { log_level, worker }
+        Self { config, dispatcher }
+    }
+
+    /// Returns key under which loggers store their names in
+    /// `additional_data`, see
+    /// [`super::CoreLoggerFactoryBuilder::set_logger_name_key`].
+    #[inline(always)]
+    pub fn logger_name_key(&self) -> &ImmutableString {
+        self.config.logger_name_key()
+    }
+
+    /// Returns handle that allows changing levels and handlers at runtime.
+    pub fn config_handle(&self) -> CoreLoggerConfigHandle {
+        CoreLoggerConfigHandle::new(self.config.clone())
+    }
+
+    /// Returns the total number of logs dropped due to queue overflow,
+    /// see [`super::OverflowPolicy`]. Once the queue is drained, handlers
+    /// receive a synthetic `Warning` log with `dropped_count` parameter
+    /// that holds the number of logs dropped since the previous such log.
+    pub fn dropped_logs(&self) -> u64 {
+        self.dispatcher.dropped_logs()
+    }
+
+    /// Returns the total number of logs rejected by sampling or rate
+    /// limiting, see [`super::CoreLoggerFactoryBuilder::set_sampling_rate`]
+    /// and [`super::CoreLoggerFactoryBuilder::set_rate_limit`].
+    pub fn sampled_out_logs(&self) -> u64 {
+        self.config.sampler().sampled_out()
+    }
+
+    /// Waits until all queued logs are passed to handlers, and then calls
+    /// [`StructuralLogHandler::flush`][crate::traits::StructuralLogHandler::flush]
+    /// on each handler. Must not be called from within a handler, since
+    /// it would wait for itself.
+    pub fn flush(&self) {
+        self.dispatcher.flush(None);
+    }
+
+    /// Same as [`CoreLoggerFactory::flush`] but waits at most `timeout`
+    /// for queued logs. Returns `false` if the timeout elapsed, in which
+    /// case handlers are not flushed.
+    pub fn flush_timeout(&self, timeout: Duration) -> bool {
+        self.dispatcher.flush(Some(timeout))
+    }
+
+    /// Passes all queued logs to handlers, stops the background worker and
+    /// calls [`StructuralLogHandler::flush`][crate::traits::StructuralLogHandler::flush]
+    /// on each handler. Logs sent afterwards by loggers of this factory
+    /// are ignored. Calling it multiple times is safe. The same happens
+    /// when the factory and all of its loggers are dropped.
+    pub fn shutdown(&self) {
+        self.dispatcher.shutdown();
     }
 }
 
@@ -26,8 +82,8 @@ impl StructuralLoggerFactory for CoreLoggerFactory {
 
     fn create(&self, name: &ImmutableString) -> Self::Logger {
         CoreLogger::new(
-            self.log_level,
             name.clone(),
-            self.worker.clone())
+            self.config.clone(),
+            self.dispatcher.clone())
     }
 }
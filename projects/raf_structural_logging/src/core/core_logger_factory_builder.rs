@@ -1,14 +1,157 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use crate::traits::{LogLevel, StructuralLogHandler, StructuralLoggerFactoryBuilder};
+use raf_array::immutable_string::ImmutableString;
 
-use super::{background_worker::BackgroundWorker, CoreLoggerFactory};
+use crate::{
+    models::SLObject,
+    template::get_logger_name_key,
+    traits::{LogLevel, StructuralLogHandler, StructuralLoggerFactoryBuilder}};
+
+use super::{
+    context::Enricher,
+    dispatcher::Dispatcher,
+    sampling::SamplingConfig,
+    shared_config::SharedConfig,
+    CoreLoggerFactory,
+    DispatchMode,
+    LevelFilter,
+    OverflowPolicy,
+    ParseLevelFilterError};
 
 /// Simple core logger factory builder.
 #[derive(Default)]
 pub struct CoreLoggerFactoryBuilder {
-    log_level: LogLevel,
-    handlers: Vec<Arc<dyn StructuralLogHandler>>,
+    level_filter: LevelFilter,
+    handlers: Vec<(Arc<dyn StructuralLogHandler>, LogLevel)>,
+    queue_capacity: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    enrichers: Vec<Enricher>,
+    sampling: SamplingConfig,
+    dispatch_mode: DispatchMode,
+    logger_name_key: Option<ImmutableString>,
+}
+
+impl CoreLoggerFactoryBuilder {
+    /// Adds `handler` that receives only logs with at least `log_level`.
+    pub fn add_handler_with_level(
+        &mut self,
+        handler: Arc<dyn StructuralLogHandler>,
+        log_level: LogLevel)
+    {
+        self.handlers.push((handler, log_level));
+    }
+
+    /// Bounds the queue of logs waiting for handlers to `capacity` logs.
+    /// When the queue is full, the [`OverflowPolicy`] decides what happens.
+    /// By default the queue is unbounded.
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        self.queue_capacity = Some(capacity);
+    }
+
+    /// Sets policy applied when bounded queue is full, see
+    /// [`CoreLoggerFactoryBuilder::set_queue_capacity`]. Defaults
+    /// to [`OverflowPolicy::Block`].
+    pub fn set_overflow_policy(&mut self, overflow_policy: OverflowPolicy) {
+        self.overflow_policy = overflow_policy;
+    }
+
+    /// Sets how logs are passed to handlers. Defaults to
+    /// [`DispatchMode::Background`].
+    pub fn set_dispatch_mode(&mut self, dispatch_mode: DispatchMode) {
+        self.dispatch_mode = dispatch_mode;
+    }
+
+    /// Passes only `rate` fraction of logs with `log_level`, chosen at
+    /// random. Rate is clamped to `0.0..=1.0`, NaN is treated as `0.0`.
+    /// By default all logs are passed.
+    pub fn set_sampling_rate(&mut self, log_level: LogLevel, rate: f64) {
+        let rate = if rate.is_nan() { 0.0 } else { rate.clamp(0.0, 1.0) };
+        self.sampling.rates[log_level as usize] = rate;
+    }
+
+    /// Passes only the first `max_logs` logs of each [`Template`] within
+    /// every `interval`. Templates are compared by their text.
+    /// 
+    /// [`Template`]: crate::template::Template
+    pub fn set_rate_limit(&mut self, max_logs: u64, interval: Duration) {
+        self.sampling.rate_limit = Some((max_logs, interval));
+    }
+
+    /// When enabled, logs identical to the previous one are not passed
+    /// to handlers. Instead, handlers receive a log with `repeat_count`
+    /// parameter once a different log arrives or on flush. Logs are
+    /// identical when they have the same level, template, template
+    /// parameters and logger name. Disabled by default.
+    pub fn set_deduplication(&mut self, enabled: bool) {
+        self.sampling.deduplicate = enabled;
+    }
+
+    /// Attaches `key` with constant `value` to `additional_data` of every
+    /// log, unless the log already has such key.
+    /// 
+    /// # Panics
+    /// When `key` cannot be converted into [`ImmutableString`].
+    pub fn add_static_data<T>(&mut self, key: &str, value: T)
+        where T: Into<SLObject>
+    {
+        self.enrichers.push(Enricher::Static(new_key(key), value.into()));
+    }
+
+    /// Attaches `key` to `additional_data` of every log, unless the log
+    /// already has such key. The value is computed by `enricher` on the
+    /// thread that sends the log, e.g. to attach its name.
+    /// 
+    /// # Panics
+    /// When `key` cannot be converted into [`ImmutableString`].
+    pub fn add_enricher<F>(&mut self, key: &str, enricher: F)
+        where F: Fn() -> SLObject + Send + Sync + 'static
+    {
+        self.enrichers.push(Enricher::Dynamic(new_key(key), Box::new(enricher)));
+    }
+
+    /// Sets `key` under which loggers store their names in
+    /// `additional_data`. Defaults to
+    /// [`get_logger_name_key`][crate::template::get_logger_name_key],
+    /// i.e. `logger_name`.
+    /// 
+    /// # Panics
+    /// When `key` cannot be converted into [`ImmutableString`].
+    pub fn set_logger_name_key(&mut self, key: &str) {
+        self.logger_name_key = Some(new_key(key));
+    }
+
+    /// Sets `log_level` for loggers named `prefix` and their descendants,
+    /// see [`LevelFilter::add_rule`].
+    pub fn add_level_rule(&mut self, prefix: &str, log_level: LogLevel) {
+        self.level_filter.add_rule(prefix, log_level);
+    }
+
+    /// Replaces the whole [`LevelFilter`], including the level set by
+    /// [`StructuralLoggerFactoryBuilder::set_log_level`].
+    pub fn set_level_filter(&mut self, level_filter: LevelFilter) {
+        self.level_filter = level_filter;
+    }
+
+    /// Applies level directives, see [`LevelFilter::apply_directives`].
+    /// 
+    /// # Errors
+    /// Same as [`LevelFilter::apply_directives`]
+    pub fn apply_level_directives(&mut self, directives: &str)
+        -> Result<(), ParseLevelFilterError>
+    {
+        self.level_filter.apply_directives(directives)
+    }
+
+    /// Applies level directives stored in `var_name` environment
+    /// variable, see [`LevelFilter::apply_env`].
+    /// 
+    /// # Errors
+    /// Same as [`LevelFilter::apply_env`]
+    pub fn apply_level_env(&mut self, var_name: &str)
+        -> Result<(), ParseLevelFilterError>
+    {
+        self.level_filter.apply_env(var_name)
+    }
 }
 
 impl StructuralLoggerFactoryBuilder for CoreLoggerFactoryBuilder {
@@ -16,15 +159,34 @@ impl StructuralLoggerFactoryBuilder for CoreLoggerFactoryBuilder {
 
     
     fn add_handler(&mut self, handler: Arc<dyn StructuralLogHandler>) {
-        self.handlers.push(handler);
+        self.add_handler_with_level(handler, LogLevel::Debug);
     }
 
     fn build(self) -> Self::Factory {
-        let worker = BackgroundWorker::new(self.handlers);
-        CoreLoggerFactory::new(self.log_level, Arc::new(worker))
+        let logger_name_key = self.logger_name_key
+            .unwrap_or_else(|| get_logger_name_key().clone());
+        let config = Arc::new(SharedConfig::new(
+            self.level_filter,
+            self.enrichers,
+            self.sampling,
+            logger_name_key));
+        for (handler, log_level) in self.handlers {
+            config.add_handler(handler, log_level);
+        }
+        let dispatcher = Dispatcher::new(
+            config.clone(),
+            self.dispatch_mode,
+            self.queue_capacity,
+            self.overflow_policy);
+        CoreLoggerFactory::new(config, Arc::new(dispatcher))
     }
     
     fn set_log_level(&mut self, log_level: LogLevel) {
-        self.log_level = log_level;
+        self.level_filter.set_default_level(log_level);
     }
 }
+
+fn new_key(key: &str) -> ImmutableString {
+    ImmutableString::new(key)
+        .expect("CoreLoggerFactoryBuilder - new ImmutableString fail")
+}
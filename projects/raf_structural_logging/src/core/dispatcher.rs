@@ -0,0 +1,402 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering}, Arc, RwLock},
+    time::{Duration, Instant}};
+
+use crate::{models::LogDataHolder, traits::LogLevel};
+
+use super::{
+    background_worker::{BackgroundWorker, WorkerTarget},
+    sampling::RepeatCheck,
+    shared_config::SharedConfig,
+    HandlerId,
+    OverflowPolicy};
+
+/// Decides on which thread handlers receive logs.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum DispatchMode {
+    /// Logs are queued and passed to handlers by a single background
+    /// thread.
+    #[default]
+    Background,
+
+    /// Logs are passed to handlers on the thread that sends them, before
+    /// [`StructuralLogger::log`][crate::traits::StructuralLogger::log]
+    /// returns. Queue capacity and overflow policy are ignored. Useful
+    /// for CLIs and tests.
+    Synchronous,
+
+    /// Each handler has its own queue and background thread, so that
+    /// a slow handler doesn't delay other handlers. Queue capacity and
+    /// overflow policy apply to each queue separately. Each handler
+    /// receives its own copy of the log.
+    PerHandler,
+
+    /// Logs are queued and passed by a single background thread to
+    /// [`StructuralLogHandler::handle_batch`] in batches of up to given
+    /// number of logs, at least one. Batches hold logs queued by the time
+    /// the thread picks them, the thread doesn't wait for more logs.
+    /// 
+    /// [`StructuralLogHandler::handle_batch`]: crate::traits::StructuralLogHandler::handle_batch
+    Batched(usize),
+}
+
+#[inline(always)]
+fn accepts(log_level: LogLevel, log_data: &LogDataHolder) -> bool {
+    (log_data.log_level() as i32) >= (log_level as i32)
+}
+
+/// Panics of handlers are caught, so that a single faulty handler
+/// doesn't stop other handlers and the dispatcher itself.
+pub(super) fn handle_log(log_data: &LogDataHolder, config: &SharedConfig)
+{
+    if log_data.is_empty() {
+        return;
+    }
+
+    for entry in config.handlers().iter() {
+        if accepts(entry.log_level, log_data) {
+            let _ = catch_unwind(AssertUnwindSafe(|| entry.handler.handle(log_data)));
+        }
+    }
+}
+
+/// Same as [`handle_log`] but passes log only to the handler with `id`,
+/// if it is still registered.
+pub(super) fn handle_log_by(log_data: &LogDataHolder, config: &SharedConfig, id: HandlerId) {
+    if log_data.is_empty() {
+        return;
+    }
+
+    let handlers = config.handlers();
+    if let Some(entry) = handlers.iter().find(|entry| entry.id == id) {
+        if accepts(entry.log_level, log_data) {
+            let _ = catch_unwind(AssertUnwindSafe(|| entry.handler.handle(log_data)));
+        }
+    }
+}
+
+/// Passes `logs` to each handler as slices of consecutive logs accepted
+/// by the handler.
+pub(super) fn handle_batch(logs: &[LogDataHolder], config: &SharedConfig) {
+    for entry in config.handlers().iter() {
+        let accepted = |log_data: &LogDataHolder| !log_data.is_empty() && accepts(entry.log_level, log_data);
+        let mut rest = logs;
+        while !rest.is_empty() {
+            let start = rest.iter().position(accepted).unwrap_or(rest.len());
+            rest = &rest[start..];
+            let len = rest.iter().position(|log_data| !accepted(log_data)).unwrap_or(rest.len());
+            if len > 0 {
+                let _ = catch_unwind(AssertUnwindSafe(|| entry.handler.handle_batch(&rest[..len])));
+            }
+            rest = &rest[len..];
+        }
+    }
+}
+
+/// Flushes the handler with `id`, if it is still registered.
+pub(super) fn flush_handler(config: &SharedConfig, id: HandlerId) {
+    let handlers = config.handlers();
+    if let Some(entry) = handlers.iter().find(|entry| entry.id == id) {
+        let _ = catch_unwind(AssertUnwindSafe(|| entry.handler.flush()));
+    }
+}
+
+/// Handles log unless it repeats the previous one. Summary of
+/// repeats is handled before the next different log.
+pub(super) fn handle_unique_log(log_data: &LogDataHolder, config: &SharedConfig) {
+    if let RepeatCheck::New(summary) = config.sampler().check_repeat(log_data) {
+        if let Some(summary) = summary {
+            handle_log(&summary, config);
+        }
+        handle_log(log_data, config);
+    }
+}
+
+/// Passes pending summary of repeated logs to handlers and flushes them.
+pub(super) fn flush_handlers(config: &SharedConfig) {
+    if let Some(summary) = config.sampler().take_repeat_summary() {
+        handle_log(&summary, config);
+    }
+    for entry in config.handlers().iter() {
+        let _ = catch_unwind(AssertUnwindSafe(|| entry.handler.flush()));
+    }
+}
+
+/// Handles logs on the caller thread.
+pub(super) struct SynchronousDispatcher {
+    config: Arc<SharedConfig>,
+    is_shut_down: AtomicBool,
+}
+
+impl SynchronousDispatcher {
+    fn new(config: Arc<SharedConfig>) -> Self {
+        Self {
+            config: config,
+            is_shut_down: AtomicBool::new(false),
+        }
+    }
+
+    fn send_log(&self, log_data: &LogDataHolder) {
+        if !self.is_shut_down.load(Ordering::Acquire) {
+            handle_unique_log(log_data, &self.config);
+        }
+    }
+
+    fn shutdown(&self) {
+        if !self.is_shut_down.swap(true, Ordering::AcqRel) {
+            flush_handlers(&self.config);
+        }
+    }
+}
+
+impl Drop for SynchronousDispatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Worker of a single handler, together with minimal [`LogLevel`] of logs
+/// the handler receives.
+struct HandlerWorker {
+    id: HandlerId,
+    log_level: LogLevel,
+    worker: Arc<BackgroundWorker>,
+}
+
+/// Workers of handlers registered in given generation of [`SharedConfig`].
+#[derive(Default)]
+struct WorkerSnapshot {
+    generation: Option<u64>,
+    workers: Arc<[HandlerWorker]>,
+}
+
+/// Passes logs to handlers through one [`BackgroundWorker`] per handler.
+/// Workers are started and stopped lazily, as handlers are registered
+/// and removed. The list of workers is rebuilt only when configuration
+/// changes, otherwise logs are passed to a shared snapshot of it.
+pub(super) struct PerHandlerDispatcher {
+    config: Arc<SharedConfig>,
+    capacity: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    workers: RwLock<WorkerSnapshot>,
+    removed_dropped: AtomicU64,
+    is_shut_down: AtomicBool,
+}
+
+impl PerHandlerDispatcher {
+    fn new(config: Arc<SharedConfig>, capacity: Option<usize>, overflow_policy: OverflowPolicy) -> Self {
+        Self {
+            config: config,
+            capacity: capacity,
+            overflow_policy: overflow_policy,
+            workers: RwLock::default(),
+            removed_dropped: AtomicU64::new(0),
+            is_shut_down: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns workers of currently registered handlers.
+    fn workers(&self) -> Arc<[HandlerWorker]> {
+        let generation = self.config.generation();
+        let snapshot = self.workers
+            .read()
+            .expect("PerHandlerDispatcher - workers.read() fail");
+        if snapshot.generation == Some(generation) {
+            return snapshot.workers.clone();
+        }
+        drop(snapshot);
+        self.rebuild_workers()
+    }
+
+    /// Starts workers of new handlers and stops workers of removed
+    /// handlers.
+    fn rebuild_workers(&self) -> Arc<[HandlerWorker]> {
+        let mut removed = Vec::new();
+        let result = {
+            let mut snapshot = self.workers
+                .write()
+                .expect("PerHandlerDispatcher - workers.write() fail");
+            if self.is_shut_down.load(Ordering::Acquire) {
+                return Arc::new([]);
+            }
+            // Generation has to be read before handlers. Otherwise
+            // concurrent change could be missed until the next one.
+            let generation = self.config.generation();
+            if snapshot.generation == Some(generation) {
+                return snapshot.workers.clone();
+            }
+            let handlers = self.config.handlers();
+            let workers: Arc<[HandlerWorker]> = handlers
+                .iter()
+                .map(|entry| {
+                    let worker = match snapshot.workers.iter().find(|current| current.id == entry.id) {
+                        Some(current) => current.worker.clone(),
+                        None => Arc::new(BackgroundWorker::new(
+                            self.config.clone(),
+                            self.capacity,
+                            self.overflow_policy,
+                            WorkerTarget::Handler(entry.id))),
+                    };
+                    HandlerWorker { id: entry.id, log_level: entry.log_level, worker: worker }
+                })
+                .collect();
+            removed.extend(snapshot.workers
+                .iter()
+                .filter(|current| workers.iter().all(|worker| worker.id != current.id))
+                .map(|current| current.worker.clone()));
+            snapshot.generation = Some(generation);
+            snapshot.workers = workers.clone();
+            workers
+        };
+        for worker in removed {
+            self.removed_dropped.fetch_add(worker.dropped_logs(), Ordering::Relaxed);
+            worker.shutdown();
+        }
+        result
+    }
+
+    fn fan_out(&self, log_data: LogDataHolder) {
+        let workers = self.workers();
+        let mut accepted = workers
+            .iter()
+            .filter(|worker| accepts(worker.log_level, &log_data))
+            .peekable();
+        while let Some(current) = accepted.next() {
+            if accepted.peek().is_none() {
+                current.worker.send_log(log_data);
+                return;
+            }
+            current.worker.send_log(log_data.clone());
+        }
+    }
+
+    fn send_log(&self, log_data: LogDataHolder) {
+        if self.is_shut_down.load(Ordering::Acquire) {
+            return;
+        }
+        if let RepeatCheck::New(summary) = self.config.sampler().check_repeat(&log_data) {
+            if let Some(summary) = summary {
+                self.fan_out(summary);
+            }
+            self.fan_out(log_data);
+        }
+    }
+
+    fn dropped_logs(&self) -> u64 {
+        let snapshot = self.workers
+            .read()
+            .expect("PerHandlerDispatcher - workers.read() fail");
+        let dropped: u64 = snapshot.workers.iter().map(|current| current.worker.dropped_logs()).sum();
+        dropped + self.removed_dropped.load(Ordering::Relaxed)
+    }
+
+    fn flush(&self, timeout: Option<Duration>) -> bool {
+        if let Some(summary) = self.config.sampler().take_repeat_summary() {
+            self.fan_out(summary);
+        }
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        for current in self.workers().iter() {
+            let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if !current.worker.flush(remaining) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn shutdown(&self) {
+        if let Some(summary) = self.config.sampler().take_repeat_summary() {
+            self.fan_out(summary);
+        }
+        if self.is_shut_down.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let snapshot = std::mem::take(&mut *self.workers
+            .write()
+            .expect("PerHandlerDispatcher - workers.write() fail"));
+        for current in snapshot.workers.iter() {
+            current.worker.shutdown();
+        }
+    }
+}
+
+impl Drop for PerHandlerDispatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Passes logs sent by loggers to handlers, according to [`DispatchMode`].
+pub(super) enum Dispatcher {
+    Background(BackgroundWorker),
+    Synchronous(SynchronousDispatcher),
+    PerHandler(PerHandlerDispatcher),
+}
+
+impl Dispatcher {
+    pub(super) fn new(
+        config: Arc<SharedConfig>,
+        dispatch_mode: DispatchMode,
+        capacity: Option<usize>,
+        overflow_policy: OverflowPolicy) -> Self
+    {
+        match dispatch_mode {
+            DispatchMode::Background => {
+                Self::Background(BackgroundWorker::new(
+                    config,
+                    capacity,
+                    overflow_policy,
+                    WorkerTarget::AllHandlers))
+            },
+            DispatchMode::Batched(max_len) => {
+                Self::Background(BackgroundWorker::new(
+                    config,
+                    capacity,
+                    overflow_policy,
+                    WorkerTarget::AllHandlersBatched(max_len.max(1))))
+            },
+            DispatchMode::Synchronous => Self::Synchronous(SynchronousDispatcher::new(config)),
+            DispatchMode::PerHandler => {
+                Self::PerHandler(PerHandlerDispatcher::new(config, capacity, overflow_policy))
+            },
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn send_log(&self, log_data: LogDataHolder) {
+        match self {
+            Self::Background(worker) => worker.send_log(log_data),
+            Self::Synchronous(dispatcher) => dispatcher.send_log(&log_data),
+            Self::PerHandler(dispatcher) => dispatcher.send_log(log_data),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn dropped_logs(&self) -> u64 {
+        match self {
+            Self::Background(worker) => worker.dropped_logs(),
+            Self::Synchronous(_) => 0,
+            Self::PerHandler(dispatcher) => dispatcher.dropped_logs(),
+        }
+    }
+
+    pub(super) fn flush(&self, timeout: Option<Duration>) -> bool {
+        match self {
+            Self::Background(worker) => worker.flush(timeout),
+            Self::Synchronous(dispatcher) => {
+                flush_handlers(&dispatcher.config);
+                true
+            },
+            Self::PerHandler(dispatcher) => dispatcher.flush(timeout),
+        }
+    }
+
+    pub(super) fn shutdown(&self) {
+        match self {
+            Self::Background(worker) => worker.shutdown(),
+            Self::Synchronous(dispatcher) => dispatcher.shutdown(),
+            Self::PerHandler(dispatcher) => dispatcher.shutdown(),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::env;
+
+use crate::traits::LogLevel;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseLevelFilterError {
+    InvalidLogLevel(String),
+    InvalidDirective(String),
+    InvalidEnvVar(String),
+}
+
+/// Resolves minimal [`LogLevel`] for loggers by their names. Names form
+/// dotted hierarchies: a rule for `db` applies to loggers `db`, `db.pool`
+/// and `db.pool.conn`, but not to `dbx`. When multiple rules match, the
+/// one with the longest prefix wins. Loggers without any matching rule use
+/// the default level.
+#[derive(Clone, Default, Debug)]
+pub struct LevelFilter {
+    default_level: LogLevel,
+    rules: Vec<(String, LogLevel)>,
+}
+
+impl LevelFilter {
+    pub fn new(default_level: LogLevel) -> Self {
+        Self {
+            default_level: default_level,
+            rules: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn default_level(&self) -> LogLevel { self.default_level }
+
+    #[inline(always)]
+    pub fn set_default_level(&mut self, log_level: LogLevel) {
+        self.default_level = log_level;
+    }
+
+    /// Sets `log_level` for loggers named `prefix` and their descendants.
+    /// Overrides previous rule for the same `prefix`.
+    pub fn add_rule(&mut self, prefix: &str, log_level: LogLevel) {
+        if prefix.is_empty() {
+            self.default_level = log_level;
+            return;
+        }
+
+        if let Some(rule) = self.rules.iter_mut().find(|rule| rule.0 == prefix) {
+            rule.1 = log_level;
+        } else {
+            self.rules.push((prefix.to_owned(), log_level));
+        }
+    }
+
+    /// Returns minimal [`LogLevel`] of logger with given `logger_name`.
+    pub fn level_for(&self, logger_name: &str) -> LogLevel {
+        let mut best: Option<&(String, LogLevel)> = None;
+        for rule in &self.rules {
+            if !is_in_hierarchy(logger_name, &rule.0) {
+                continue;
+            }
+            if best.map_or(true, |current| current.0.len() < rule.0.len()) {
+                best = Some(rule);
+            }
+        }
+        best.map_or(self.default_level, |rule| rule.1)
+    }
+
+    /// Applies comma separated directives in the spirit of `RUST_LOG`,
+    /// e.g. `info,db.pool=debug,http=warning`. A bare level sets the
+    /// default level, while `prefix=level` adds a rule, see
+    /// [`LevelFilter::add_rule`]. Levels are case insensitive and `warn`
+    /// is accepted as an alias of `warning`. Empty directives are ignored.
+    /// 
+    /// # Errors
+    /// * [`ParseLevelFilterError::InvalidLogLevel`] if level is not recognized
+    /// * [`ParseLevelFilterError::InvalidDirective`] if directive has empty
+    ///   prefix or multiple `=` signs
+    pub fn apply_directives(&mut self, directives: &str) -> Result<(), ParseLevelFilterError> {
+        let rules = parse_directives(directives)?;
+        self.add_rules(&rules);
+        Ok(())
+    }
+
+    /// Adds rules returned by [`parse_directives`], in order.
+    pub(super) fn add_rules(&mut self, rules: &[(String, LogLevel)]) {
+        for (prefix, log_level) in rules {
+            self.add_rule(prefix, *log_level);
+        }
+    }
+
+    /// Applies directives stored in `var_name` environment variable,
+    /// see [`LevelFilter::apply_directives`]. Does nothing if the variable
+    /// is not set.
+    /// 
+    /// # Errors
+    /// * [`ParseLevelFilterError::InvalidEnvVar`] if the variable is not
+    ///   a valid unicode
+    /// * Errors of [`LevelFilter::apply_directives`]
+    pub fn apply_env(&mut self, var_name: &str) -> Result<(), ParseLevelFilterError> {
+        match env::var(var_name) {
+            Ok(directives) => self.apply_directives(&directives),
+            Err(env::VarError::NotPresent) => Ok(()),
+            Err(env::VarError::NotUnicode(_)) => {
+                Err(ParseLevelFilterError::InvalidEnvVar(var_name.to_owned()))
+            },
+        }
+    }
+}
+
+fn is_in_hierarchy(logger_name: &str, prefix: &str) -> bool {
+    match logger_name.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+/// Parses comma separated directives into `(prefix, level)` rules, see
+/// [`LevelFilter::apply_directives`]. An empty prefix stands for
+/// the default level.
+pub(super) fn parse_directives(directives: &str)
+    -> Result<Vec<(String, LogLevel)>, ParseLevelFilterError>
+{
+    let mut parsed = Vec::new();
+    for directive in directives.split(',') {
+        let directive = directive.trim();
+        if directive.is_empty() {
+            continue;
+        }
+
+        let mut parts = directive.split('=');
+        let first = parts.next().unwrap_or_default().trim();
+        let rule = match (parts.next(), parts.next()) {
+            (None, _) => (String::new(), parse_log_level(first)?),
+            (Some(level), None) if !first.is_empty() => {
+                (first.to_owned(), parse_log_level(level.trim())?)
+            },
+            _ => {
+                return Err(ParseLevelFilterError::InvalidDirective(directive.to_owned()));
+            },
+        };
+        parsed.push(rule);
+    }
+    Ok(parsed)
+}
+
+fn parse_log_level(text: &str) -> Result<LogLevel, ParseLevelFilterError> {
+    let level = match text.to_ascii_lowercase().as_str() {
+        "debug" => LogLevel::Debug,
+        "info" => LogLevel::Info,
+        "warn" | "warning" => LogLevel::Warning,
+        "error" => LogLevel::Error,
+        _ => return Err(ParseLevelFilterError::InvalidLogLevel(text.to_owned())),
+    };
+    Ok(level)
+}
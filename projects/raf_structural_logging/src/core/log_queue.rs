@@ -0,0 +1,212 @@
+use std::{
+    collections::VecDeque,
+    sync::{atomic::{AtomicU64, Ordering}, Condvar, Mutex, MutexGuard},
+    time::Duration};
+
+use crate::{models::LogDataHolder, traits::LogLevel};
+
+/// Decides what happens with a log sent to a full queue.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum OverflowPolicy {
+    /// Sender waits until there is space in the queue.
+    #[default]
+    Block,
+
+    /// The new log is dropped.
+    DropNewest,
+
+    /// The oldest queued log is dropped to make space for the new one.
+    DropOldest,
+
+    /// The new log is dropped if its level is below given level,
+    /// otherwise sender waits until there is space in the queue.
+    DropBelowLevel(LogLevel),
+}
+
+pub(super) enum QueueItem {
+    LogData(LogDataHolder),
+    Dropped(u64),
+}
+
+struct QueueState {
+    logs: VecDeque<LogDataHolder>,
+    pending_dropped: u64,
+    is_busy: bool,
+    is_closed: bool,
+}
+
+impl QueueState {
+    #[inline(always)]
+    fn is_drained(&self) -> bool {
+        self.logs.is_empty() && self.pending_dropped == 0 && !self.is_busy
+    }
+}
+
+/// Multi-producer, single-consumer queue of logs with optional capacity.
+pub(super) struct LogQueue {
+    state: Mutex<QueueState>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    drained: Condvar,
+    capacity: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    dropped: AtomicU64,
+}
+
+impl LogQueue {
+    pub(super) fn new(capacity: Option<usize>, overflow_policy: OverflowPolicy) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                logs: VecDeque::new(),
+                pending_dropped: 0,
+                is_busy: false,
+                is_closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            drained: Condvar::new(),
+            capacity: capacity.map(|value| value.max(1)),
+            overflow_policy: overflow_policy,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the total number of logs dropped due to overflow.
+    #[inline(always)]
+    pub(super) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Pushes log to the queue. Logs pushed after [`LogQueue::close`]
+    /// are ignored.
+    pub(super) fn push(&self, log_data: LogDataHolder) {
+        let mut guard = self.lock();
+        if let Some(capacity) = self.capacity {
+            while guard.logs.len() >= capacity && !guard.is_closed {
+                match self.overflow_policy {
+                    OverflowPolicy::Block => { },
+                    OverflowPolicy::DropNewest => {
+                        self.mark_dropped(&mut guard);
+                        return;
+                    },
+                    OverflowPolicy::DropOldest => {
+                        guard.logs.pop_front();
+                        self.mark_dropped(&mut guard);
+                        break;
+                    },
+                    OverflowPolicy::DropBelowLevel(log_level) => {
+                        if (log_data.log_level() as i32) < (log_level as i32) {
+                            self.mark_dropped(&mut guard);
+                            return;
+                        }
+                    },
+                }
+                guard = self.not_full
+                    .wait(guard)
+                    .expect("LogQueue - not_full.wait() fail");
+            }
+        }
+        if guard.is_closed {
+            return;
+        }
+        guard.logs.push_back(log_data);
+        self.not_empty.notify_one();
+    }
+
+    /// Waits for the next item. Returns number of dropped logs once
+    /// all queued logs are consumed. Returns `None` when the queue is
+    /// closed and there is nothing more to consume. Calling `pop` marks
+    /// the previously returned item as processed.
+    pub(super) fn pop(&self) -> Option<QueueItem> {
+        let mut guard = self.lock();
+        guard.is_busy = false;
+        loop {
+            if let Some(log_data) = guard.logs.pop_front() {
+                guard.is_busy = true;
+                self.not_full.notify_one();
+                return Some(QueueItem::LogData(log_data));
+            }
+
+            if guard.pending_dropped > 0 {
+                let dropped = guard.pending_dropped;
+                guard.pending_dropped = 0;
+                guard.is_busy = true;
+                return Some(QueueItem::Dropped(dropped));
+            }
+
+            self.drained.notify_all();
+            if guard.is_closed {
+                return None;
+            }
+
+            guard = self.not_empty
+                .wait(guard)
+                .expect("LogQueue - not_empty.wait() fail");
+        }
+    }
+
+    /// Same as [`LogQueue::pop`] but returns up to `max_len` items at once.
+    /// The returned batch is never empty.
+    pub(super) fn pop_batch(&self, max_len: usize) -> Option<Vec<QueueItem>> {
+        let mut guard = self.lock();
+        guard.is_busy = false;
+        loop {
+            if !guard.logs.is_empty() || guard.pending_dropped > 0 {
+                let len = guard.logs.len().min(max_len.max(1));
+                let mut items: Vec<QueueItem> = guard.logs
+                    .drain(..len)
+                    .map(QueueItem::LogData)
+                    .collect();
+                if guard.pending_dropped > 0 && items.len() < max_len.max(1) {
+                    items.push(QueueItem::Dropped(guard.pending_dropped));
+                    guard.pending_dropped = 0;
+                }
+                guard.is_busy = true;
+                self.not_full.notify_all();
+                return Some(items);
+            }
+
+            self.drained.notify_all();
+            if guard.is_closed {
+                return None;
+            }
+
+            guard = self.not_empty
+                .wait(guard)
+                .expect("LogQueue - not_empty.wait() fail");
+        }
+    }
+
+    /// Waits until all queued logs are processed. Returns `false` if
+    /// `timeout` elapsed first.
+    pub(super) fn wait_drained(&self, timeout: Option<Duration>) -> bool {
+        let guard = self.lock();
+        if let Some(timeout) = timeout {
+            let (guard, _) = self.drained
+                .wait_timeout_while(guard, timeout, |state| !state.is_drained())
+                .expect("LogQueue - drained.wait_timeout_while() fail");
+            return guard.is_drained();
+        }
+
+        let _guard = self.drained
+            .wait_while(guard, |state| !state.is_drained())
+            .expect("LogQueue - drained.wait_while() fail");
+        true
+    }
+
+    pub(super) fn close(&self) {
+        let mut guard = self.lock();
+        guard.is_closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    fn mark_dropped(&self, state: &mut QueueState) {
+        state.pending_dropped += 1;
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().expect("LogQueue - lock() fail")
+    }
+}
@@ -1,9 +1,22 @@
 //! Holds core logging implementation.
 mod background_worker;
+mod dispatcher;
 mod core_logger_factory_builder;
 mod core_logger_factory;
 mod core_logger;
+mod level_filter;
+mod shared_config;
+mod config_handle;
+mod log_queue;
+mod context;
+mod sampling;
 
 pub use core_logger::*;
+pub use dispatcher::DispatchMode;
 pub use core_logger_factory::*;
 pub use core_logger_factory_builder::*;
+pub use level_filter::*;
+pub use shared_config::HandlerId;
+pub use config_handle::*;
+pub use log_queue::OverflowPolicy;
+pub use context::{push_context, ContextGuard};
@@ -0,0 +1,231 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    sync::{atomic::{AtomicU64, Ordering}, LazyLock, Mutex, MutexGuard},
+    time::{Duration, Instant}};
+
+use raf_array::immutable_string::ImmutableString;
+
+use crate::{
+    models::{LogDataHolder, SLObject},
+    template::{Template, TemplateBuilder},
+    traits::LogLevel};
+
+static REPEATED_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    TemplateBuilder::global().create_from_str("Previous message repeated {repeat_count} times")
+});
+
+static REPEAT_COUNT: LazyLock<ImmutableString>
+    = LazyLock::new(|| ImmutableString::new("repeat_count").unwrap());
+
+/// Rate limiter forgets templates with expired windows once it tracks
+/// that many templates.
+const MAX_TRACKED_TEMPLATES: usize = 1024;
+
+const LEVELS_COUNT: usize = 4;
+
+/// Sampling settings collected by [`super::CoreLoggerFactoryBuilder`].
+#[derive(Clone, Debug)]
+pub(super) struct SamplingConfig {
+    pub(super) rates: [f64; LEVELS_COUNT],
+    pub(super) rate_limit: Option<(u64, Duration)>,
+    pub(super) deduplicate: bool,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            rates: [1.0; LEVELS_COUNT],
+            rate_limit: None,
+            deduplicate: false,
+        }
+    }
+}
+
+struct RateWindow {
+    started_at: Instant,
+    count: u64,
+}
+
+/// Identifies log for deduplication together with number of its
+/// suppressed repeats.
+struct RepeatedLog {
+    log_level: LogLevel,
+    template: Template,
+    template_params: HashMap<ImmutableString, SLObject>,
+    logger_name: Option<(ImmutableString, ImmutableString)>,
+    count: u64,
+}
+
+impl RepeatedLog {
+    fn new(log_data: &LogDataHolder) -> Self {
+        Self {
+            log_level: log_data.log_level(),
+            template: log_data.template().clone(),
+            template_params: log_data.template_params().clone(),
+            logger_name: log_data.logger_name_key()
+                .zip(log_data.logger_name())
+                .map(|(key, name)| (key.clone(), name.clone())),
+            count: 0,
+        }
+    }
+
+    fn is_same(&self, log_data: &LogDataHolder) -> bool {
+        self.log_level == log_data.log_level()
+            && self.template == *log_data.template()
+            && self.template_params == *log_data.template_params()
+            && self.logger_name.as_ref().map(|(_, name)| name) == log_data.logger_name()
+    }
+
+    /// Builds synthetic log that informs how many times the log was
+    /// repeated. Returns `None` if it wasn't repeated.
+    fn summary(&self) -> Option<LogDataHolder> {
+        if self.count == 0 {
+            return None;
+        }
+        let mut template_params = HashMap::with_capacity(1);
+        template_params.insert(
+            REPEAT_COUNT.clone(),
+            i64::try_from(self.count).unwrap_or(i64::MAX).into());
+        let mut log_data = LogDataHolder::new(
+            self.log_level,
+            REPEATED_TEMPLATE.clone(),
+            template_params);
+        if let Some((key, name)) = &self.logger_name {
+            log_data.set_logger_name(key.clone(), name.clone());
+        }
+        Some(log_data)
+    }
+}
+
+/// Result of [`Sampler::check_repeat`].
+#[allow(clippy::large_enum_variant)]
+pub(super) enum RepeatCheck {
+    /// Log is identical to the previous one and should be skipped.
+    Repeated,
+
+    /// Log differs from the previous one. Holds summary of repeats of
+    /// the previous log, which should be handled first.
+    New(Option<LogDataHolder>),
+}
+
+/// Decides which logs are passed to handlers. Probabilistic sampling and
+/// rate limiting are applied by loggers, before logs are queued.
+/// Deduplication is applied by the background worker, so that it sees
+/// logs in the order they are handled.
+pub(super) struct Sampler {
+    config: SamplingConfig,
+    windows: Mutex<HashMap<Template, RateWindow>>,
+    last_log: Mutex<Option<RepeatedLog>>,
+    sampled_out: AtomicU64,
+}
+
+impl Sampler {
+    pub(super) fn new(config: SamplingConfig) -> Self {
+        Self {
+            config: config,
+            windows: Mutex::default(),
+            last_log: Mutex::default(),
+            sampled_out: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the total number of logs rejected by [`Sampler::should_log`].
+    #[inline(always)]
+    pub(super) fn sampled_out(&self) -> u64 {
+        self.sampled_out.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if log passes both probabilistic sampling and
+    /// rate limiting.
+    pub(super) fn should_log(&self, log_data: &LogDataHolder) -> bool {
+        let result = self.is_sampled(log_data.log_level())
+            && self.is_within_rate_limit(log_data.template());
+        if !result {
+            self.sampled_out.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn is_sampled(&self, log_level: LogLevel) -> bool {
+        let rate = self.config.rates[log_level as usize];
+        if rate >= 1.0 {
+            return true;
+        }
+        // Takes 53 random bits, which is the precision of f64.
+        let random = (next_random() >> 11) as f64 / (1u64 << 53) as f64;
+        random < rate
+    }
+
+    fn is_within_rate_limit(&self, template: &Template) -> bool {
+        let Some((max_logs, interval)) = self.config.rate_limit else {
+            return true;
+        };
+        let now = Instant::now();
+        let mut guard = self.windows
+            .lock()
+            .expect("Sampler - windows.lock() fail");
+        if guard.len() >= MAX_TRACKED_TEMPLATES && !guard.contains_key(template) {
+            guard.retain(|_, window| now.duration_since(window.started_at) < interval);
+        }
+        let window = guard
+            .entry(template.clone())
+            .or_insert(RateWindow { started_at: now, count: 0 });
+        if now.duration_since(window.started_at) >= interval {
+            window.started_at = now;
+            window.count = 0;
+        }
+        window.count += 1;
+        window.count <= max_logs
+    }
+
+    /// Compares log with the previous one, when deduplication is enabled.
+    pub(super) fn check_repeat(&self, log_data: &LogDataHolder) -> RepeatCheck {
+        if !self.config.deduplicate {
+            return RepeatCheck::New(None);
+        }
+        let mut guard = self.lock_last_log();
+        if let Some(last_log) = guard.as_mut() {
+            if last_log.is_same(log_data) {
+                last_log.count += 1;
+                return RepeatCheck::Repeated;
+            }
+        }
+        let previous = guard.replace(RepeatedLog::new(log_data));
+        RepeatCheck::New(previous.and_then(|previous| previous.summary()))
+    }
+
+    /// Returns summary of repeats of the last log, if any. Following
+    /// identical logs are counted from zero.
+    pub(super) fn take_repeat_summary(&self) -> Option<LogDataHolder> {
+        let mut guard = self.lock_last_log();
+        let last_log = guard.as_mut()?;
+        let summary = last_log.summary();
+        last_log.count = 0;
+        summary
+    }
+
+    fn lock_last_log(&self) -> MutexGuard<'_, Option<RepeatedLog>> {
+        self.last_log
+            .lock()
+            .expect("Sampler - last_log.lock() fail")
+    }
+}
+
+thread_local! {
+    static RANDOM: Cell<u64> = Cell::new(RandomState::new().hash_one(std::thread::current().id()));
+}
+
+/// `SplitMix64` generator with per thread state.
+fn next_random() -> u64 {
+    RANDOM.with(|state| {
+        let value = state.get().wrapping_add(0x9E3779B97F4A7C15);
+        state.set(value);
+        let mut result = value;
+        result = (result ^ (result >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        result = (result ^ (result >> 27)).wrapping_mul(0x94D049BB133111EB);
+        result ^ (result >> 31)
+    })
+}
@@ -0,0 +1,164 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+    RwLock};
+
+use raf_array::immutable_string::ImmutableString;
+
+use crate::traits::{LogLevel, StructuralLogHandler};
+
+use super::{
+    context::Enricher,
+    sampling::{Sampler, SamplingConfig},
+    LevelFilter};
+
+/// Identifies handler registered in [`super::CoreLoggerFactory`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct HandlerId {
+    value: u64,
+}
+
+impl HandlerId {
+    #[inline(always)]
+    pub fn value(&self) -> u64 { self.value }
+}
+
+/// Handler together with minimal [`LogLevel`] of logs it receives.
+#[derive(Clone)]
+pub(super) struct HandlerEntry {
+    pub(super) id: HandlerId,
+    pub(super) log_level: LogLevel,
+    pub(super) handler: Arc<dyn StructuralLogHandler>,
+}
+
+/// Configuration shared between factory, loggers and background worker.
+/// Each change of [`LevelFilter`] or handlers bumps `generation`, which
+/// lets loggers and dispatchers cheaply detect that their cached state is
+/// outdated. Handlers are kept as an immutable snapshot, replaced on each
+/// change, so that no lock is held while they handle logs.
+pub(super) struct SharedConfig {
+    level_filter: RwLock<LevelFilter>,
+    generation: AtomicU64,
+    handlers: RwLock<Arc<[HandlerEntry]>>,
+    next_handler_id: AtomicU64,
+    enrichers: Vec<Enricher>,
+    sampler: Sampler,
+    logger_name_key: ImmutableString,
+}
+
+impl SharedConfig {
+    pub(super) fn new(
+        level_filter: LevelFilter,
+        enrichers: Vec<Enricher>,
+        sampling: SamplingConfig,
+        logger_name_key: ImmutableString) -> Self
+    {
+        Self {
+            level_filter: RwLock::new(level_filter),
+            generation: AtomicU64::new(0),
+            handlers: RwLock::new(Arc::new([])),
+            next_handler_id: AtomicU64::new(0),
+            enrichers: enrichers,
+            sampler: Sampler::new(sampling),
+            logger_name_key: logger_name_key,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn enrichers(&self) -> &[Enricher] {
+        &self.enrichers
+    }
+
+    #[inline(always)]
+    pub(super) fn sampler(&self) -> &Sampler {
+        &self.sampler
+    }
+
+    /// Returns key under which loggers store their names in
+    /// `additional_data`.
+    #[inline(always)]
+    pub(super) fn logger_name_key(&self) -> &ImmutableString {
+        &self.logger_name_key
+    }
+
+    #[inline(always)]
+    pub(super) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub(super) fn level_for(&self, logger_name: &str) -> LogLevel {
+        self.level_filter
+            .read()
+            .expect("SharedConfig - level_filter.read() fail")
+            .level_for(logger_name)
+    }
+
+    pub(super) fn level_filter(&self) -> LevelFilter {
+        self.level_filter
+            .read()
+            .expect("SharedConfig - level_filter.read() fail")
+            .clone()
+    }
+
+    pub(super) fn update_level_filter<F>(&self, update: F)
+        where F: FnOnce(&mut LevelFilter)
+    {
+        let mut guard = self.level_filter
+            .write()
+            .expect("SharedConfig - level_filter.write() fail");
+        update(&mut guard);
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Returns snapshot of currently registered handlers.
+    pub(super) fn handlers(&self) -> Arc<[HandlerEntry]> {
+        self.handlers
+            .read()
+            .expect("SharedConfig - handlers.read() fail")
+            .clone()
+    }
+
+    /// Replaces handlers with the result of `update` called on a copy
+    /// of the current ones.
+    fn update_handlers<F, R>(&self, update: F) -> R
+        where F: FnOnce(&mut Vec<HandlerEntry>) -> R
+    {
+        let mut guard = self.handlers
+            .write()
+            .expect("SharedConfig - handlers.write() fail");
+        let mut handlers = guard.to_vec();
+        let result = update(&mut handlers);
+        *guard = handlers.into();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        result
+    }
+
+    pub(super) fn add_handler(&self, handler: Arc<dyn StructuralLogHandler>, log_level: LogLevel)
+        -> HandlerId
+    {
+        let id = HandlerId { value: self.next_handler_id.fetch_add(1, Ordering::Relaxed) };
+        let entry = HandlerEntry { id: id, log_level: log_level, handler: handler };
+        self.update_handlers(|handlers| handlers.push(entry));
+        id
+    }
+
+    pub(super) fn remove_handler(&self, id: HandlerId) -> bool {
+        self.update_handlers(|handlers| {
+            let length = handlers.len();
+            handlers.retain(|entry| entry.id != id);
+            handlers.len() != length
+        })
+    }
+
+    pub(super) fn set_handler_level(&self, id: HandlerId, log_level: LogLevel) -> bool {
+        self.update_handlers(|handlers| {
+            match handlers.iter_mut().find(|entry| entry.id == id) {
+                Some(entry) => {
+                    entry.log_level = log_level;
+                    true
+                },
+                None => false,
+            }
+        })
+    }
+}
@@ -1,4 +1,14 @@
 //! Helpers for structural logging.
+//! 
+//! # Features
+//! * `log` enables `bridge::LogBridge`, which routes records of the `log`
+//!   crate through `core::CoreLoggerFactory`.
+//! * `tracing` enables `bridge::TracingBridge`, a `tracing_subscriber`
+//!   layer which routes `tracing` events through `core::CoreLoggerFactory`.
+//! * `uuid` enables conversion of `uuid::Uuid` into `models::SLObject`.
+//! * `serde` enables `models::SLObject::from_serialize`, which converts any
+//!   `serde::Serialize` value into `models::SLObject`, and implements
+//!   `Serialize` and `Deserialize` for `models::SLObject`.
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(
     clippy::needless_return,
@@ -12,3 +22,8 @@ pub mod traits;
 pub mod models;
 pub mod core;
 pub mod template;
+pub mod bridge;
+pub mod testing;
+
+#[doc(hidden)]
+pub mod macro_helpers;
@@ -0,0 +1,60 @@
+use std::{cell::Cell, collections::HashMap};
+
+use crate::{
+    models::{LogDataHolder, SLObject},
+    template::{Template, TemplateBuilder, LOGGER_NAME_KEY},
+    traits::{LogLevel, StructuralLog}};
+
+#[doc(hidden)]
+pub use raf_array::immutable_string::ImmutableString;
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn template_builder() -> &'static TemplateBuilder { TemplateBuilder::global() }
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn new_key(key: &str) -> ImmutableString {
+    ImmutableString::new(key).expect("Couldn't create template key.")
+}
+
+/// Log built by logging macros at the call site. Template parameters are
+/// moved out on the first [`StructuralLog::log_data`] call.
+#[doc(hidden)]
+pub struct MacroLog {
+    log_level: LogLevel,
+    template: Template,
+    template_params: Cell<HashMap<ImmutableString, SLObject>>,
+}
+
+impl MacroLog {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn new(
+        log_level: LogLevel,
+        template: Template,
+        template_params: HashMap<ImmutableString, SLObject>) -> Self
+    {
+        Self {
+            log_level: log_level,
+            template: template,
+            template_params: Cell::new(template_params),
+        }
+    }
+}
+
+impl StructuralLog for MacroLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(
+            self.log_level,
+            self.template.clone(),
+            self.template_params.take())
+    }
+}
+
+/// Keys that are always available through [`LogDataHolder::get`] once
+/// the log passes through a logger, and thus can be referenced in templates
+/// without being passed explicitly. Logger name is available under the
+/// default key even if loggers store it under a custom one.
+#[doc(hidden)]
+pub const IMPLICIT_KEYS: [&str; 3] = ["created_at", "log_level", LOGGER_NAME_KEY];
@@ -0,0 +1,198 @@
+#![cfg(feature="serde")]
+use std::collections::HashMap;
+
+use raf_array::immutable_string::ImmutableString;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq, SerializeStruct},
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer};
+
+use crate::traits::LogLevel;
+
+use super::{SLError, SLObject};
+
+/// Log levels are serialized as unit variants. Time and duration use
+/// [`std::time::SystemTime`] and [`std::time::Duration`] representations,
+/// while UUIDs and IP addresses are serialized as strings.
+impl Serialize for SLObject {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match self {
+            SLObject::Empty => serializer.serialize_unit(),
+            SLObject::LogLevel(inner) => {
+                let log_level = *inner.value();
+                let name = match log_level {
+                    LogLevel::Debug => "Debug",
+                    LogLevel::Info => "Info",
+                    LogLevel::Warning => "Warning",
+                    LogLevel::Error => "Error",
+                };
+                serializer.serialize_unit_variant("LogLevel", log_level as u32, name)
+            },
+            SLObject::SystemTime(inner) => inner.value().serialize(serializer),
+            SLObject::Duration(inner) => inner.value().serialize(serializer),
+            SLObject::String(inner) => serializer.serialize_str(inner.value().as_str()),
+            SLObject::Number(inner) => serializer.serialize_i64(inner.value()),
+            SLObject::Float(inner) => serializer.serialize_f64(inner.value()),
+            SLObject::UInt64(inner) => serializer.serialize_u64(inner.value()),
+            SLObject::UInt128(inner) => serializer.serialize_u128(*inner.value()),
+            SLObject::Bool(inner) => serializer.serialize_bool(inner.value()),
+            SLObject::Bytes(inner) => serializer.serialize_bytes(inner.value()),
+            SLObject::Uuid(inner) => serializer.collect_str(inner),
+            SLObject::IpAddr(inner) => serializer.collect_str(inner.value()),
+            SLObject::Error(inner) => inner.serialize(serializer),
+            SLObject::Array(inner) => {
+                let mut seq = serializer.serialize_seq(Some(inner.value().len()))?;
+                for item in inner.value() {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            },
+            SLObject::Dict(inner) => {
+                let mut map = serializer.serialize_map(Some(inner.value().len()))?;
+                for (key, value) in inner.value() {
+                    map.serialize_entry(key.as_str(), value)?;
+                }
+                map.end()
+            },
+        }
+    }
+}
+
+impl Serialize for SLError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let sources: Vec<&str> = self.sources.iter().map(ImmutableString::as_str).collect();
+        let mut state = serializer.serialize_struct("SLError", 3)?;
+        state.serialize_field("message", self.message.as_str())?;
+        state.serialize_field("sources", &sources)?;
+        state.serialize_field("backtrace", &self.backtrace.as_ref().map(ImmutableString::as_str))?;
+        state.end()
+    }
+}
+
+fn new_string<E: serde::de::Error>(text: &str) -> Result<ImmutableString, E> {
+    ImmutableString::new(text).map_err(|_| E::custom("Couldn't create ImmutableString."))
+}
+
+struct SLObjectVisitor;
+
+impl<'de> Visitor<'de> for SLObjectVisitor {
+    type Value = SLObject;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("SLObject")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<SLObject, E> {
+        Ok(SLObject::from(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<SLObject, E> {
+        Ok(SLObject::from(v))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<SLObject, E>
+        where E: serde::de::Error
+    {
+        if let Ok(value) = i64::try_from(v) {
+            return Ok(SLObject::from(value));
+        }
+        match u128::try_from(v) {
+            Ok(value) => self.visit_u128(value),
+            Err(_) => Err(E::custom(format!("Integer [{v}] is out of range."))),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<SLObject, E> {
+        match i64::try_from(v) {
+            Ok(value) => Ok(SLObject::from(value)),
+            Err(_) => Ok(SLObject::from(v)),
+        }
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<SLObject, E>
+        where E: serde::de::Error
+    {
+        match u64::try_from(v) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Ok(SLObject::from(v)),
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<SLObject, E> {
+        Ok(SLObject::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<SLObject, E>
+        where E: serde::de::Error
+    {
+        Ok(SLObject::from(new_string::<E>(v)?))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<SLObject, E> {
+        Ok(SLObject::from(v))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<SLObject, E> {
+        Ok(SLObject::from(v))
+    }
+
+    fn visit_none<E>(self) -> Result<SLObject, E> {
+        Ok(SLObject::Empty)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<SLObject, D::Error>
+        where D: Deserializer<'de>
+    {
+        SLObject::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<SLObject, E> {
+        Ok(SLObject::Empty)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<SLObject, D::Error>
+        where D: Deserializer<'de>
+    {
+        SLObject::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<SLObject, A::Error>
+        where A: SeqAccess<'de>
+    {
+        let mut items: Vec<SLObject> = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(SLObject::from(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<SLObject, A::Error>
+        where A: MapAccess<'de>
+    {
+        let mut items = HashMap::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some((key, value)) = map.next_entry::<String, SLObject>()? {
+            items.insert(new_string(&key)?, value);
+        }
+        Ok(SLObject::from(items))
+    }
+}
+
+/// Self-describing formats only. Since most formats don't distinguish
+/// between them, strings are never restored as UUIDs or IP addresses, and
+/// unsigned integers are restored as [`SLObject::Number`] whenever they fit.
+/// Similarly log levels, time, durations and errors are restored in their
+/// serialized forms, as strings and dicts.
+impl<'de> Deserialize<'de> for SLObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(SLObjectVisitor)
+    }
+}
@@ -0,0 +1,554 @@
+//! Holds basic structural logging models.
+mod serializer;
+mod impl_serde;
+
+#[cfg(feature="serde")]
+pub use serializer::*;
+
+use core::hash::{Hash, Hasher};
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::LazyLock,
+    time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_readonly::readonly;
+
+use crate::{
+    template::{get_logger_name_key, Template},
+    traits::LogLevel};
+
+/// Represents different entities interpretable by loggers.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum SLObject {
+    Empty,
+    LogLevel(SLLogLevel),
+    SystemTime(SLSystemTime),
+    Duration(SLDuration),
+    String(SLString),
+    Number(SLNumber),
+    Float(SLFloat),
+    UInt64(SLUInt64),
+    UInt128(SLUInt128),
+    Bool(SLBool),
+    Bytes(SLBytes),
+    Uuid(SLUuid),
+    IpAddr(SLIpAddr),
+    Error(Box<SLError>),
+    Array(Box<SLArray>),
+    Dict(Box<SLDict>),
+}
+
+impl From<LogLevel> for SLObject {
+    fn from(value: LogLevel) -> Self { Self::LogLevel(SLLogLevel::new(value)) }
+}
+
+impl From<SystemTime> for SLObject {
+    fn from(value: SystemTime) -> Self { Self::SystemTime(SLSystemTime::new(value)) }
+}
+
+impl From<Duration> for SLObject {
+    fn from(value: Duration) -> Self { Self::Duration(SLDuration::new(value)) }
+}
+
+impl From<ImmutableString> for SLObject {
+    fn from(value: ImmutableString) -> Self { Self::String(SLString::new(value)) }
+}
+
+impl From<&str> for SLObject {
+    fn from(value: &str) -> Self { Self::String(SLString::from(value)) }
+}
+
+impl From<String> for SLObject {
+    fn from(value: String) -> Self { Self::String(SLString::from(value.as_str())) }
+}
+
+impl From<i64> for SLObject {
+    fn from(value: i64) -> Self { Self::Number(SLNumber::new(value)) }
+}
+
+impl From<i32> for SLObject {
+    fn from(value: i32) -> Self { Self::Number(SLNumber::new(i64::from(value))) }
+}
+
+impl From<u32> for SLObject {
+    fn from(value: u32) -> Self { Self::UInt64(SLUInt64::new(u64::from(value))) }
+}
+
+impl From<f64> for SLObject {
+    fn from(value: f64) -> Self { Self::Float(SLFloat::new(value)) }
+}
+
+impl From<f32> for SLObject {
+    fn from(value: f32) -> Self { Self::Float(SLFloat::new(f64::from(value))) }
+}
+
+impl From<u64> for SLObject {
+    fn from(value: u64) -> Self { Self::UInt64(SLUInt64::new(value)) }
+}
+
+impl From<u128> for SLObject {
+    fn from(value: u128) -> Self { Self::UInt128(SLUInt128::new(value)) }
+}
+
+impl From<bool> for SLObject {
+    fn from(value: bool) -> Self { Self::Bool(SLBool::new(value)) }
+}
+
+impl From<Vec<u8>> for SLObject {
+    fn from(value: Vec<u8>) -> Self { Self::Bytes(SLBytes::new(value)) }
+}
+
+impl From<&[u8]> for SLObject {
+    fn from(value: &[u8]) -> Self { Self::Bytes(SLBytes::new(value.to_vec())) }
+}
+
+impl From<SLUuid> for SLObject {
+    fn from(value: SLUuid) -> Self { Self::Uuid(value) }
+}
+
+#[cfg(feature="uuid")]
+impl From<uuid::Uuid> for SLObject {
+    fn from(value: uuid::Uuid) -> Self { Self::Uuid(SLUuid::new(value.into_bytes())) }
+}
+
+impl From<IpAddr> for SLObject {
+    fn from(value: IpAddr) -> Self { Self::IpAddr(SLIpAddr::new(value)) }
+}
+
+impl From<Ipv4Addr> for SLObject {
+    fn from(value: Ipv4Addr) -> Self { Self::IpAddr(SLIpAddr::new(IpAddr::V4(value))) }
+}
+
+impl From<Ipv6Addr> for SLObject {
+    fn from(value: Ipv6Addr) -> Self { Self::IpAddr(SLIpAddr::new(IpAddr::V6(value))) }
+}
+
+impl From<SLError> for SLObject {
+    fn from(value: SLError) -> Self { Self::Error(Box::new(value)) }
+}
+
+impl From<&(dyn Error + 'static)> for SLObject {
+    fn from(value: &(dyn Error + 'static)) -> Self { Self::from(SLError::from_error(value)) }
+}
+
+impl From<Vec<SLObject>> for SLObject {
+    fn from(value: Vec<SLObject>) -> Self {
+        let arr = SLArray::new(value);
+        let boxed = Box::new(arr);
+        Self::Array(boxed)
+    }
+}
+
+impl From<HashMap<ImmutableString, SLObject>> for SLObject {
+    fn from(value: HashMap<ImmutableString, SLObject>) -> Self {
+        let arr = SLDict::new(value);
+        let boxed = Box::new(arr);
+        Self::Dict(boxed)
+    }
+}
+
+/// Holds [`LogLevel`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLLogLevel {
+    pub value: LogLevel,
+}
+
+
+/// Holds [`SystemTime`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLSystemTime {
+    pub value: SystemTime,
+}
+
+/// Holds [`Duration`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLDuration {
+    pub value: Duration,
+}
+
+/// Holds [`ImmutableString`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLString {
+    pub value: ImmutableString,
+}
+
+impl From<&str> for SLString {
+    fn from(value: &str) -> Self {
+        Self::new(ImmutableString::new(value).unwrap())
+    }
+}
+
+/// Holds [`i64`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLNumber {
+    pub value: i64,
+}
+
+/// Holds [`f64`] internally. Unlike [`f64`] itself it is [`Eq`] and
+/// [`Hash`]: all NaNs are equal to each other, and so are `0.0` and `-0.0`.
+#[readonly]
+#[derive(Clone, Copy, Debug)]
+pub struct SLFloat {
+    pub value: f64,
+}
+
+impl SLFloat {
+    fn canonical_bits(self) -> u64 {
+        if self.value.is_nan() {
+            f64::NAN.to_bits()
+        } else if self.value == 0.0 {
+            0
+        } else {
+            self.value.to_bits()
+        }
+    }
+}
+
+impl PartialEq for SLFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_bits() == other.canonical_bits()
+    }
+}
+
+impl Eq for SLFloat { }
+
+impl Hash for SLFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.canonical_bits());
+    }
+}
+
+/// Holds [`u64`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLUInt64 {
+    pub value: u64,
+}
+
+/// Holds [`u128`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLUInt128 {
+    pub value: u128,
+}
+
+/// Holds [`bool`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLBool {
+    pub value: bool
+}
+
+/// Holds raw bytes internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLBytes {
+    pub value: Vec<u8>,
+}
+
+/// Holds 16 bytes of an UUID internally. With `uuid` feature enabled
+/// it can be created directly out of `uuid::Uuid`.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct SLUuid {
+    pub value: [u8; 16],
+}
+
+impl Display for SLUuid {
+    /// Writes UUID in the lowercase, hyphenated form.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, byte) in self.value.iter().enumerate() {
+            if matches!(idx, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Holds [`IpAddr`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLIpAddr {
+    pub value: IpAddr,
+}
+
+/// Holds description of an [`Error`]: its message, messages of the whole
+/// [`Error::source`] chain (closest source first) and optional backtrace.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLError {
+    pub message: ImmutableString,
+    pub sources: Vec<ImmutableString>,
+    pub backtrace: Option<ImmutableString>,
+}
+
+impl SLError {
+    /// Captures `error` message and its [`Error::source`] chain, without
+    /// backtrace.
+    pub fn from_error(error: &(dyn Error + 'static)) -> Self {
+        let mut sources = Vec::new();
+        let mut current = error.source();
+        while let Some(source) = current {
+            sources.push(to_immutable_string(&source.to_string()));
+            current = source.source();
+        }
+        Self::new(to_immutable_string(&error.to_string()), sources, None)
+    }
+
+    /// Same as [`SLError::from_error`], but additionally stores `backtrace`
+    /// if it was actually captured.
+    pub fn with_backtrace(error: &(dyn Error + 'static), backtrace: &Backtrace) -> Self {
+        let mut result = Self::from_error(error);
+        if backtrace.status() == BacktraceStatus::Captured {
+            result.backtrace = Some(to_immutable_string(&backtrace.to_string()));
+        }
+        result
+    }
+
+    /// Same as [`SLError::with_backtrace`] with [`Backtrace::capture`]. Note
+    /// that backtrace is captured only when enabled through `RUST_BACKTRACE`
+    /// or `RUST_LIB_BACKTRACE` environment variables.
+    pub fn capture(error: &(dyn Error + 'static)) -> Self {
+        Self::with_backtrace(error, &Backtrace::capture())
+    }
+}
+
+fn to_immutable_string(text: &str) -> ImmutableString {
+    ImmutableString::new(text).expect("ImmutableString::new() fail")
+}
+
+/// Holds array of other [`SLObject`] internally.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SLArray {
+    pub value: Vec<SLObject>,
+}
+
+/// Holds a map from [`String`] to [`SLObject`] internally.
+#[readonly]
+pub struct SLDict {
+    pub value: HashMap<ImmutableString, SLObject>,
+}
+
+impl PartialEq for SLDict {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for SLDict { }
+
+impl Hash for SLDict {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut total_hash = self.value.len() as u64;
+        for (key, value) in &self.value {
+            let mut fnv1 = raf_fnv1a_hasher::FNV1a32Hasher::new();
+            key.hash(&mut fnv1);
+            value.hash(&mut fnv1);
+            total_hash ^= fnv1.finish();
+        }
+        state.write_u64(total_hash);
+    }
+}
+
+impl Clone for SLDict {
+    fn clone(&self) -> Self {
+        Self { value: self.value.clone() }
+    }
+}
+
+impl std::fmt::Debug for SLDict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SLDict").field("value", &self.value).finish()
+    }
+}
+
+/// Represents real data that loggers can work with.
+/// 
+/// Creation time and level are kept as dedicated fields, everything else
+/// attached to the log lives in `additional_data`. All of them, together
+/// with template parameters, are available through [`LogDataHolder::get`].
+/// The holder is filled before it is dispatched and handlers only receive
+/// shared references, so it is safe to read from multiple threads.
+#[derive(Clone)]
+pub struct LogDataHolder {
+    empty: bool,
+    template: Template,
+    log_level: LogLevel,
+    created_at: SystemTime,
+    created_at_object: SLObject,
+    template_params: HashMap<ImmutableString, SLObject>,
+    additional_data: HashMap<ImmutableString, SLObject>,
+    logger_name_key: Option<ImmutableString>,
+}
+
+impl Default for LogDataHolder {
+    fn default() -> Self {
+        Self {
+            empty: true,
+            log_level: LogLevel::default(),
+            created_at: UNIX_EPOCH,
+            created_at_object: UNIX_EPOCH.into(),
+            template: Template::default(),
+            template_params: HashMap::default(),
+            additional_data: HashMap::default(),
+            logger_name_key: None,
+        }
+    }
+}
+
+static CREATED_AT: LazyLock<ImmutableString> = LazyLock::new(|| {
+    ImmutableString::new("created_at").unwrap()
+});
+
+static LOG_LEVEL: LazyLock<ImmutableString> = LazyLock::new(|| {
+    ImmutableString::new("log_level").unwrap()
+});
+
+/// Objects of all levels, indexed by level, so that holders don't need
+/// to keep their own.
+static LOG_LEVEL_OBJECTS: LazyLock<[SLObject; 4]> = LazyLock::new(|| {
+    [LogLevel::Debug, LogLevel::Info, LogLevel::Warning, LogLevel::Error].map(SLObject::from)
+});
+
+/// Key under which [`LogDataHolder::created_at`] is available through
+/// [`LogDataHolder::get`].
+#[inline(always)]
+pub fn created_at_key() -> &'static ImmutableString { &CREATED_AT }
+
+/// Key under which [`LogDataHolder::log_level`] is available through
+/// [`LogDataHolder::get`].
+#[inline(always)]
+pub fn log_level_key() -> &'static ImmutableString { &LOG_LEVEL }
+
+impl LogDataHolder {
+    pub fn new(
+        log_level: LogLevel,
+        template: Template,
+        template_params: HashMap<ImmutableString, SLObject>) -> Self
+    {
+        Self::new_with_created_at(
+            log_level,
+            template,
+            template_params,
+            SystemTime::now())
+    }
+
+    /// Same as [`LogDataHolder::new`] but with explicit creation time
+    /// instead of [`SystemTime::now()`]. Useful when restoring logs.
+    pub fn new_with_created_at(
+        log_level: LogLevel,
+        template: Template,
+        template_params: HashMap<ImmutableString, SLObject>,
+        created_at: SystemTime) -> Self
+    {
+        Self {
+            empty: false,
+            template: template,
+            created_at: created_at,
+            created_at_object: created_at.into(),
+            log_level: log_level,
+            template_params: template_params,
+            additional_data: HashMap::new(),
+            logger_name_key: None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn template(&self) -> &Template { &self.template }
+
+    #[inline(always)]
+    pub fn created_at(&self) -> SystemTime { self.created_at }
+
+    #[inline(always)]
+    pub fn log_level(&self) -> LogLevel { self.log_level }
+
+    #[inline(always)]
+    pub fn template_params(&self) -> &HashMap<ImmutableString, SLObject> { &self.template_params }
+
+    /// Returns names of template parameters that are missing from
+    /// [`LogDataHolder::template_params`], see [`Template::missing_parameters`].
+    #[inline(always)]
+    pub fn missing_template_params(&self) -> Vec<&ImmutableString> {
+        self.template.missing_parameters(&self.template_params)
+    }
+
+    /// Returns data attached to the log by loggers and enrichers. Never
+    /// contains [`created_at_key`] nor [`log_level_key`].
+    #[inline(always)]
+    pub fn additional_data(&self) -> &HashMap<ImmutableString, SLObject> { &self.additional_data }
+
+    /// Returns key under which [`LogDataHolder::logger_name`] is stored
+    /// in `additional_data`, if the name was set.
+    #[inline(always)]
+    pub fn logger_name_key(&self) -> Option<&ImmutableString> { self.logger_name_key.as_ref() }
+
+    /// Returns name of the logger that created the log, regardless of
+    /// the key it is stored under, see [`LogDataHolder::set_logger_name`].
+    pub fn logger_name(&self) -> Option<&ImmutableString> {
+        match self.additional_data.get(self.logger_name_key.as_ref()?) {
+            Some(SLObject::String(name)) => Some(&name.value),
+            _ => None,
+        }
+    }
+
+    /// Returns value stored under `key`, looked up in `template_params`
+    /// first, then among creation time and level (under [`created_at_key`]
+    /// and [`log_level_key`]), then in `additional_data`. Logger name is
+    /// also available under [`get_logger_name_key`], whatever key it is
+    /// stored under.
+    pub fn get(&self, key: &ImmutableString) -> Option<&SLObject> {
+        if let Some(value) = self.template_params.get(key) {
+            return Some(value);
+        }
+        if key == created_at_key() {
+            return Some(&self.created_at_object);
+        }
+        if key == log_level_key() {
+            return Some(&LOG_LEVEL_OBJECTS[self.log_level as usize]);
+        }
+        if let Some(value) = self.additional_data.get(key) {
+            return Some(value);
+        }
+        if key == get_logger_name_key() {
+            return self.additional_data.get(self.logger_name_key.as_ref()?);
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.empty }
+
+    /// Attaches `value` under `key` to `additional_data`. Values under
+    /// [`created_at_key`] and [`log_level_key`] are ignored, since these
+    /// are fixed when the log is created.
+    pub fn update_data<T>(&mut self, key: ImmutableString, value: T)
+        where T: Into<SLObject>
+    {
+        if key != *created_at_key() && key != *log_level_key() {
+            self.additional_data.insert(key, value.into());
+        }
+    }
+
+    /// Attaches `name` of the logger under `key` to `additional_data` and
+    /// remembers the key, so that consumers can find the name through
+    /// [`LogDataHolder::logger_name`] whichever key is configured.
+    pub fn set_logger_name(&mut self, key: ImmutableString, name: ImmutableString) {
+        if key != *created_at_key() && key != *log_level_key() {
+            self.additional_data.insert(key.clone(), name.into());
+            self.logger_name_key = Some(key);
+        }
+    }
+}
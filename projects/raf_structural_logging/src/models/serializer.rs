@@ -0,0 +1,610 @@
+#![cfg(feature="serde")]
+use std::{collections::HashMap, fmt::{Display, Formatter}};
+
+use raf_array::immutable_string::ImmutableString;
+use serde::{
+    ser::{
+        Impossible,
+        SerializeMap,
+        SerializeSeq,
+        SerializeStruct,
+        SerializeStructVariant,
+        SerializeTuple,
+        SerializeTupleStruct,
+        SerializeTupleVariant},
+    Serialize,
+    Serializer};
+
+use super::SLObject;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SLSerializeError {
+    /// Error reported by the serialized value itself.
+    Custom(String),
+
+    /// Map key is not a string, char, bool, integer or unit variant.
+    InvalidMapKey,
+
+    /// String couldn't be converted into [`ImmutableString`].
+    InvalidString,
+
+    /// Negative [`i128`] that doesn't fit into [`i64`].
+    IntegerOutOfRange(i128),
+}
+
+impl Display for SLSerializeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Custom(message) => f.write_str(message),
+            Self::InvalidMapKey => f.write_str("Map key must be a string or a primitive."),
+            Self::InvalidString => f.write_str("Couldn't create ImmutableString."),
+            Self::IntegerOutOfRange(value) => write!(f, "Integer [{value}] is out of range."),
+        }
+    }
+}
+
+impl std::error::Error for SLSerializeError { }
+
+impl serde::ser::Error for SLSerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl SLObject {
+    /// Converts any [`Serialize`] value into [`SLObject`], see
+    /// [`SLObjectSerializer`] for details of the mapping.
+    ///
+    /// # Errors
+    /// * Errors of [`SLObjectSerializer`]
+    pub fn from_serialize<T>(value: &T) -> Result<Self, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(SLObjectSerializer)
+    }
+}
+
+fn new_string(text: &str) -> Result<ImmutableString, SLSerializeError> {
+    ImmutableString::new(text).map_err(|_| SLSerializeError::InvalidString)
+}
+
+fn wrap_variant(variant: &'static str, value: SLObject) -> Result<SLObject, SLSerializeError> {
+    let mut map = HashMap::with_capacity(1);
+    map.insert(new_string(variant)?, value);
+    Ok(SLObject::from(map))
+}
+
+/// [`Serializer`] producing [`SLObject`]s. Structs and maps become
+/// [`SLObject::Dict`], sequences and tuples become [`SLObject::Array`],
+/// `None` and units become [`SLObject::Empty`]. Enum variants follow
+/// the externally tagged representation: unit variants become strings,
+/// the remaining ones single entry dicts keyed by variant name. Signed
+/// integers and unsigned integers up to 32 bits become
+/// [`SLObject::Number`], while [`u64`] and [`u128`] keep their own variants.
+///
+/// # Errors
+/// * [`SLSerializeError::InvalidMapKey`] if map key is not a string,
+///   char, bool, integer or unit variant
+/// * [`SLSerializeError::IntegerOutOfRange`] if [`i128`] is below [`i64::MIN`]
+/// * [`SLSerializeError::InvalidString`] if string is too long
+/// * [`SLSerializeError::Custom`] if serialized value reports an error
+pub struct SLObjectSerializer;
+
+impl Serializer for SLObjectSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = DictSerializer;
+    type SerializeStruct = DictSerializer;
+    type SerializeStructVariant = DictSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<SLObject, SLSerializeError> {
+        if let Ok(value) = i64::try_from(v) {
+            return Ok(SLObject::from(value));
+        }
+        match u128::try_from(v) {
+            Ok(value) => Ok(SLObject::from(value)),
+            Err(_) => Err(SLSerializeError::IntegerOutOfRange(v)),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(i64::from(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<SLObject, SLSerializeError> {
+        let mut buffer = [0u8; 4];
+        self.serialize_str(v.encode_utf8(&mut buffer))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(new_string(v)?))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::from(v))
+    }
+
+    fn serialize_none(self) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::Empty)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<SLObject, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::Empty)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<SLObject, SLSerializeError> {
+        Ok(SLObject::Empty)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str) -> Result<SLObject, SLSerializeError>
+    {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T)
+        -> Result<SLObject, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T) -> Result<SLObject, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        wrap_variant(variant, value.serialize(self)?)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SLSerializeError> {
+        Ok(SeqSerializer::new(len.unwrap_or_default(), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SLSerializeError> {
+        Ok(SeqSerializer::new(len, None))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize)
+        -> Result<SeqSerializer, SLSerializeError>
+    {
+        Ok(SeqSerializer::new(len, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize) -> Result<SeqSerializer, SLSerializeError>
+    {
+        Ok(SeqSerializer::new(len, Some(variant)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<DictSerializer, SLSerializeError> {
+        Ok(DictSerializer::new(len.unwrap_or_default(), None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize)
+        -> Result<DictSerializer, SLSerializeError>
+    {
+        Ok(DictSerializer::new(len, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize) -> Result<DictSerializer, SLSerializeError>
+    {
+        Ok(DictSerializer::new(len, Some(variant)))
+    }
+}
+
+/// Collects sequences, tuples and tuple variants into [`SLObject::Array`].
+pub struct SeqSerializer {
+    items: Vec<SLObject>,
+    variant: Option<&'static str>,
+}
+
+impl SeqSerializer {
+    fn new(capacity: usize, variant: Option<&'static str>) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            variant: variant,
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.items.push(value.serialize(SLObjectSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<SLObject, SLSerializeError> {
+        let result = SLObject::from(self.items);
+        match self.variant {
+            Some(variant) => wrap_variant(variant, result),
+            None => Ok(result),
+        }
+    }
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+impl SerializeTupleVariant for SeqSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+/// Collects maps, structs and struct variants into [`SLObject::Dict`].
+pub struct DictSerializer {
+    items: HashMap<ImmutableString, SLObject>,
+    next_key: Option<ImmutableString>,
+    variant: Option<&'static str>,
+}
+
+impl DictSerializer {
+    fn new(capacity: usize, variant: Option<&'static str>) -> Self {
+        Self {
+            items: HashMap::with_capacity(capacity),
+            next_key: None,
+            variant: variant,
+        }
+    }
+
+    fn insert<T>(&mut self, key: &'static str, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.items.insert(new_string(key)?, value.serialize(SLObjectSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<SLObject, SLSerializeError> {
+        let result = SLObject::from(self.items);
+        match self.variant {
+            Some(variant) => wrap_variant(variant, result),
+            None => Ok(result),
+        }
+    }
+}
+
+impl SerializeMap for DictSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        let Some(key) = self.next_key.take() else {
+            return Err(SLSerializeError::Custom("Value serialized before key.".to_owned()));
+        };
+        self.items.insert(key, value.serialize(SLObjectSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+impl SerializeStruct for DictSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+impl SerializeStructVariant for DictSerializer {
+    type Ok = SLObject;
+    type Error = SLSerializeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<SLObject, SLSerializeError> { self.finish() }
+}
+
+/// Converts map keys into [`ImmutableString`]. Primitive keys are
+/// converted into their textual representation.
+struct MapKeySerializer;
+
+impl MapKeySerializer {
+    fn to_key(value: &impl Display) -> Result<ImmutableString, SLSerializeError> {
+        new_string(&value.to_string())
+    }
+}
+
+impl Serializer for MapKeySerializer {
+    type Ok = ImmutableString;
+    type Error = SLSerializeError;
+    type SerializeSeq = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeTuple = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeTupleStruct = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeTupleVariant = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeMap = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeStruct = Impossible<ImmutableString, SLSerializeError>;
+    type SerializeStructVariant = Impossible<ImmutableString, SLSerializeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<ImmutableString, SLSerializeError> {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<ImmutableString, SLSerializeError> {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_char(self, v: char) -> Result<ImmutableString, SLSerializeError> {
+        Self::to_key(&v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<ImmutableString, SLSerializeError> {
+        new_string(v)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<ImmutableString, SLSerializeError> {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_none(self) -> Result<ImmutableString, SLSerializeError> {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<ImmutableString, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_unit(self) -> Result<ImmutableString, SLSerializeError> {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str)
+        -> Result<ImmutableString, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str) -> Result<ImmutableString, SLSerializeError>
+    {
+        new_string(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T)
+        -> Result<ImmutableString, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T) -> Result<ImmutableString, SLSerializeError>
+        where T: Serialize + ?Sized
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>)
+        -> Result<Self::SerializeSeq, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_tuple(self, _len: usize)
+        -> Result<Self::SerializeTuple, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeTupleStruct, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize) -> Result<Self::SerializeTupleVariant, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_map(self, _len: Option<usize>)
+        -> Result<Self::SerializeMap, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize)
+        -> Result<Self::SerializeStruct, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize) -> Result<Self::SerializeStructVariant, SLSerializeError>
+    {
+        Err(SLSerializeError::InvalidMapKey)
+    }
+}
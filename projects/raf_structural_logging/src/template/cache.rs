@@ -0,0 +1,154 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, RandomState},
+    sync::{atomic::{AtomicU64, Ordering}, Mutex}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_readonly::readonly;
+
+use super::Template;
+
+const MAX_SHARDS: usize = 16;
+const MIN_SHARD_CAPACITY: usize = 64;
+
+/// Snapshot of [`TemplateBuilder`][super::TemplateBuilder] cache statistics.
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub struct TemplateCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub len: usize,
+}
+
+struct CacheEntry {
+    template: Template,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Shard {
+    entries: HashMap<ImmutableString, CacheEntry>,
+    usage: BTreeMap<u64, ImmutableString>,
+    tick: u64,
+}
+
+impl Shard {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &ImmutableString) -> Option<Template> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        let _ = self.usage.remove(&entry.last_used);
+        entry.last_used = tick;
+        let _ = self.usage.insert(tick, key.clone());
+        Some(entry.template.clone())
+    }
+
+    /// Inserts `template` unless already present and returns the cached
+    /// template together with number of evicted entries.
+    fn insert(&mut self, key: &ImmutableString, template: Template, capacity: usize) -> (Template, u64) {
+        if let Some(existing) = self.get(key) {
+            return (existing, 0);
+        }
+
+        let mut evicted = 0;
+        while self.entries.len() >= capacity {
+            let Some((_, oldest)) = self.usage.pop_first() else { break };
+            let _ = self.entries.remove(&oldest);
+            evicted += 1;
+        }
+
+        let tick = self.next_tick();
+        let _ = self.usage.insert(tick, key.clone());
+        let _ = self.entries.insert(key.clone(), CacheEntry {
+            template: template.clone(),
+            last_used: tick,
+        });
+        (template, evicted)
+    }
+}
+
+/// Bounded cache of parsed templates. Keys are distributed over
+/// independently locked shards, each evicting its least recently used
+/// entry when full. Thus eviction order is only approximately LRU
+/// across the whole cache. Small caches use a single shard.
+pub(super) struct TemplateCache {
+    shards: Box<[Mutex<Shard>]>,
+    shard_capacity: usize,
+    capacity: usize,
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl TemplateCache {
+    pub(super) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let shards_count = (capacity / MIN_SHARD_CAPACITY).clamp(1, MAX_SHARDS);
+        let shards: Vec<Mutex<Shard>> = (0..shards_count)
+            .map(|_| Mutex::default())
+            .collect();
+        Self {
+            shards: shards.into_boxed_slice(),
+            shard_capacity: capacity.div_ceil(shards_count),
+            capacity: capacity,
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn capacity(&self) -> usize { self.capacity }
+
+    fn shard(&self, key: &ImmutableString) -> &Mutex<Shard> {
+        let hash = self.hasher.hash_one(key);
+        #[allow(clippy::cast_possible_truncation)]
+        let idx = (hash % self.shards.len() as u64) as usize;
+        &self.shards[idx]
+    }
+
+    pub(super) fn get(&self, key: &ImmutableString) -> Option<Template> {
+        let result = self.shard(key).lock().unwrap().get(key);
+        let counter = if result.is_some() { &self.hits } else { &self.misses };
+        let _ = counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    /// Inserts `template` under `key`. If other thread inserted the key
+    /// in the meantime then the already cached template is returned.
+    pub(super) fn insert(&self, key: &ImmutableString, template: Template) -> Template {
+        let (result, evicted) = self.shard(key)
+            .lock()
+            .unwrap()
+            .insert(key, template, self.shard_capacity);
+        if evicted > 0 {
+            let _ = self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+        result
+    }
+
+    pub(super) fn clear(&self) {
+        for shard in &self.shards {
+            *shard.lock().unwrap() = Shard::default();
+        }
+    }
+
+    pub(super) fn stats(&self) -> TemplateCacheStats {
+        let len = self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().entries.len())
+            .sum();
+        TemplateCacheStats::new(
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+            self.evictions.load(Ordering::Relaxed),
+            len)
+    }
+}
@@ -2,7 +2,12 @@
 //! should be filled with other parameters to generate a proper log string.
 #![allow(clippy::missing_panics_doc)]
 
+mod cache;
 mod models;
 mod parser;
+mod rendering;
 
+pub use cache::TemplateCacheStats;
 pub use models::*;
+pub use parser::*;
+pub use rendering::*;
@@ -1,21 +1,68 @@
-use std::{
-    collections::HashMap,
-    sync::{LazyLock, Mutex}};
+use std::{collections::HashMap, sync::LazyLock};
 
 use raf_array::{atomic_array::{StrongArray, StrongArrayBuilder}, immutable_string::ImmutableString};
+use raf_readonly::readonly;
 
-use super::parser::parse_template_to_pieces;
+use crate::models::SLObject;
+
+use super::{
+    cache::{TemplateCache, TemplateCacheStats},
+    parser::{parse_template, parse_template_lenient, TemplateParseError}};
+
+/// Serilog-style hint put in front of parameter name.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum ParameterHint {
+    #[default]
+    Default,
+
+    /// `{@name}`, value is rendered with its full structure: dict keys
+    /// are sorted and strings are quoted.
+    Destructure,
+
+    /// `{$name}`, value is rendered as a quoted string.
+    Stringify,
+}
+
+/// Alignment of rendered parameter, padded with spaces up to the width.
+/// Written as `{name,10}` for right and `{name,-10}` for left alignment.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Alignment {
+    Left(usize),
+    Right(usize),
+}
+
+/// Parameter of a template written as `{[@|$]name[,alignment][:format]}`.
+/// The format is interpreted by handlers depending on value type, see
+/// [`render_parameter`][super::render_parameter].
+#[readonly]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct TemplateParameter {
+    pub name: ImmutableString,
+    pub hint: ParameterHint,
+    pub alignment: Option<Alignment>,
+    pub format: Option<ImmutableString>,
+}
+
+impl TemplateParameter {
+    /// Returns `true` if parameter has no hint, alignment nor format,
+    /// i.e. it is of the `{name}` form.
+    #[inline(always)]
+    pub fn is_plain(&self) -> bool {
+        self.hint == ParameterHint::Default && self.alignment.is_none() && self.format.is_none()
+    }
+}
 
 /// Represents two different pieces of template: raw string and parameter.
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum TemplatePiece {
     RawString(ImmutableString),
-    Parameter(ImmutableString),
+    Parameter(TemplateParameter),
 }
 
 /// Represents textual template. Template should be of the form
 /// `"This is {val} value"` which the engine splits into three pieces:
 /// `RawString("This is")` -> `Parameter("val")` -> `RawString(" value")`.
+/// See [`parse_template`] for the full syntax of parameters.
 #[derive(Debug, Default, Clone)]
 pub struct Template {
     raw: ImmutableString,
@@ -47,39 +94,131 @@ impl Template {
 
     #[inline(always)]
     pub fn pieces(&self) -> &[TemplatePiece] { self.pieces.as_slice() }
+
+    /// Returns parameters of the template in order of appearance.
+    pub fn parameters(&self) -> impl Iterator<Item=&TemplateParameter> {
+        self.pieces().iter().filter_map(|piece| match piece {
+            TemplatePiece::Parameter(parameter) => Some(parameter),
+            TemplatePiece::RawString(_) => None,
+        })
+    }
+
+    /// Returns names of parameters referenced in the template but missing
+    /// from `params`. Each name is reported once, in order of appearance.
+    pub fn missing_parameters<'a>(&'a self, params: &HashMap<ImmutableString, SLObject>) -> Vec<&'a ImmutableString> {
+        let mut result: Vec<&ImmutableString> = Vec::new();
+        for parameter in self.parameters() {
+            let name = parameter.name();
+            if !params.contains_key(name) && !result.contains(&name) {
+                result.push(name);
+            }
+        }
+        result
+    }
 }
 
+/// Default number of templates cached by [`TemplateBuilder`].
+pub const DEFAULT_TEMPLATE_CACHE_CAPACITY: usize = 4096;
+
 /// Represents builder for [`Template`] out of string. Will cache already
-/// constructed templates.
-#[derive(Default)]
+/// constructed templates, up to configured capacity. When full, least
+/// recently used templates are evicted.
+/// 
+/// By default the builder is strict and rejects malformed templates. A
+/// builder created with [`TemplateBuilder::lenient`] accepts any text and
+/// keeps malformed parameters as raw text, see [`parse_template_lenient`].
+/// 
+/// Components should prefer [`TemplateBuilder::global`] so that parsed
+/// templates are shared.
 pub struct TemplateBuilder {
-    cache: Mutex<HashMap<ImmutableString, Template>>,
+    cache: TemplateCache,
+    lenient: bool,
 }
 
+impl Default for TemplateBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEMPLATE_CACHE_CAPACITY, false)
+    }
+}
+
+static GLOBAL_TEMPLATE_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
 impl TemplateBuilder {
-    pub fn create(&self, imm: &ImmutableString) -> Template {
-        {
-            let guard = self.cache.lock().unwrap();
-            if let Some(tmpl) = guard.get(imm) {
-                return tmpl.clone();
-            }
+    /// Creates builder caching at most `capacity` templates, at least one.
+    /// If `lenient` is `true` then the builder never fails and malformed
+    /// parameters are rendered literally.
+    pub fn new(capacity: usize, lenient: bool) -> Self {
+        Self {
+            cache: TemplateCache::new(capacity),
+            lenient: lenient,
+        }
+    }
+
+    /// Creates lenient builder with default capacity.
+    pub fn lenient() -> Self {
+        Self::new(DEFAULT_TEMPLATE_CACHE_CAPACITY, true)
+    }
+
+    /// Returns strict builder with default capacity shared by the whole
+    /// process. Used by logging macros and bridges.
+    #[inline(always)]
+    pub fn global() -> &'static TemplateBuilder { &GLOBAL_TEMPLATE_BUILDER }
+
+    #[inline(always)]
+    pub fn is_lenient(&self) -> bool { self.lenient }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize { self.cache.capacity() }
+
+    /// Returns current cache statistics.
+    pub fn stats(&self) -> TemplateCacheStats { self.cache.stats() }
+
+    /// Removes all cached templates. Statistics are kept.
+    pub fn clear(&self) { self.cache.clear(); }
+
+    /// Creates [`Template`] out of `imm`, or returns a cached one.
+    /// 
+    /// # Errors
+    /// * [`TemplateParseError`] with position and reason if `imm` is not
+    ///   a valid template. Never happens for lenient builder.
+    pub fn try_create(&self, imm: &ImmutableString) -> Result<Template, TemplateParseError> {
+        if let Some(tmpl) = self.cache.get(imm) {
+            return Ok(tmpl);
         }
 
-        let pieces = parse_template_to_pieces(imm);
+        let pieces = if self.lenient {
+            parse_template_lenient(imm.as_str())
+        } else {
+            parse_template(imm.as_str())?
+        };
         let strong_pieces = StrongArrayBuilder::default().build_from_clonable(&pieces).unwrap();
         let new_tmpl = Template::new(
             imm.clone(), 
             strong_pieces);
 
-        {
-            let mut guard = self.cache.lock().unwrap();
-            if let Some(tmpl) = guard.get(imm) {
-                return tmpl.clone();
-            }
-            guard.insert(imm.clone(), new_tmpl.clone());
-        }
+        Ok(self.cache.insert(imm, new_tmpl))
+    }
 
-        new_tmpl
+    /// Same as [`TemplateBuilder::try_create`] but with `&str` input.
+    /// 
+    /// # Errors
+    /// * [`TemplateParseError`] if `txt` is not a valid template
+    pub fn try_create_from_str(&self, txt: &str) -> Result<Template, TemplateParseError> {
+        let imm = ImmutableString::new(txt).unwrap();
+        self.try_create(&imm)
+    }
+
+    /// Creates [`Template`] out of `imm`, or returns a cached one.
+    /// 
+    /// # Panics
+    /// * If `imm` is not a valid template and the builder is not lenient,
+    ///   use [`TemplateBuilder::try_create`] to handle the error instead
+    pub fn create(&self, imm: &ImmutableString) -> Template {
+        match self.try_create(imm) {
+            Ok(tmpl) => tmpl,
+            Err(err) => panic!("{err}"),
+        }
     }
 
     pub fn create_from_str(&self, txt: &str) -> Template {
@@ -88,11 +227,15 @@ impl TemplateBuilder {
     }
 }
 
+/// Default key under which loggers store their names.
+pub(crate) const LOGGER_NAME_KEY: &str = "logger_name";
 
 static LOGGER_NAME: LazyLock<ImmutableString>
     = LazyLock::new(|| {
-        ImmutableString::new("logger_name").unwrap()
+        ImmutableString::new(LOGGER_NAME_KEY).unwrap()
     });
 
+/// Returns default key under which loggers store their names
+/// in `additional_data`.
 #[inline(always)]
-pub(crate) fn get_logger_name_key() -> &'static ImmutableString { &LOGGER_NAME }
+pub fn get_logger_name_key() -> &'static ImmutableString { &LOGGER_NAME }
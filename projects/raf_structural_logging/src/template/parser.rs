@@ -1,126 +1,212 @@
+use std::{iter::Peekable, str::CharIndices};
+
 use raf_array::immutable_string::ImmutableString;
 
-use super::TemplatePiece;
+use super::{Alignment, ParameterHint, TemplateParameter, TemplatePiece};
 
-fn read_text(input: &str) -> (usize, ImmutableString) {
-    let mut chars = input.chars();
-    let mut content = String::new();
-    let mut offset = 0;
+/// Reason of [`TemplateParseError`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum TemplateParseErrorReason {
+    /// Parameter is opened with `{` but never closed with `}`.
+    UnterminatedParameter,
 
-    loop {
-        let Some(chr) = chars.next() else { break };
+    /// Parameter has no name, e.g. `{}` or `{@}`.
+    EmptyParameterName,
 
-        if chr != '{' {
-            offset += chr.len_utf8();
-            content.push(chr);
-            continue;
-        }
+    /// Unexpected character inside parameter, e.g. `{a b}`.
+    UnexpectedCharacter(char),
 
-        let Some(peek_next) = chars.next() else {
-            offset += chr.len_utf8();
-            content.push(chr);
-            break;
-        };
+    /// Alignment after `,` is not a, possibly negative, integer.
+    InvalidAlignment,
+}
 
-        if peek_next == '{' {
-            offset += chr.len_utf8();
-            content.push(chr);
-            offset += peek_next.len_utf8();
-            continue;
-        }
+/// Describes why template couldn't be parsed. The `position` is a byte
+/// offset into the template text.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct TemplateParseError {
+    pub position: usize,
+    pub reason: TemplateParseErrorReason,
+}
 
-        break;
+impl std::fmt::Display for TemplateParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.reason {
+            TemplateParseErrorReason::UnterminatedParameter => "unterminated parameter".to_owned(),
+            TemplateParseErrorReason::EmptyParameterName => "empty parameter name".to_owned(),
+            TemplateParseErrorReason::UnexpectedCharacter(chr) => format!("unexpected character {chr:?}"),
+            TemplateParseErrorReason::InvalidAlignment => "invalid alignment".to_owned(),
+        };
+        write!(f, "Invalid template at position {}: {reason}.", self.position)
     }
-
-    let imm = ImmutableString::new(&content).unwrap();
-    (offset, imm)
 }
 
-fn read_key(input: &str) -> (usize, ImmutableString) {
-    let mut chars = input.chars().peekable();
-    let _ = chars.next();
-    let mut content = String::new();
-    let mut offset = 1;
-
-    loop {
-        let Some(chr) = chars.peek() else { break };
+impl std::error::Error for TemplateParseError { }
 
-        if !chr.is_whitespace() {
-            break;
-        }
+type Chars<'a> = Peekable<CharIndices<'a>>;
 
-        offset += chr.len_utf8();
-        let _ = chars.next();
+fn error(position: usize, reason: TemplateParseErrorReason) -> TemplateParseError {
+    TemplateParseError {
+        position: position,
+        reason: reason,
     }
+}
 
-    loop {
-        let Some(chr) = chars.peek() else { break };
+fn new_string(text: &str) -> ImmutableString {
+    ImmutableString::new(text).unwrap()
+}
 
-        if chr.is_whitespace() || *chr == '}' {
-            break;
-        }
+fn skip_whitespaces(chars: &mut Chars) {
+    while chars.next_if(|(_, chr)| chr.is_whitespace()).is_some() { }
+}
 
-        offset += chr.len_utf8();
+/// Returns position of the next character or length of `input` if there
+/// are no more characters.
+fn position(chars: &mut Chars, input: &str) -> usize {
+    chars.peek().map_or(input.len(), |(idx, _)| *idx)
+}
 
-        content.push(*chr);
+fn read_text(chars: &mut Chars) -> String {
+    let mut content = String::new();
+    while let Some((_, chr)) = chars.peek().copied() {
+        if chr == '{' {
+            let mut lookahead = chars.clone();
+            let _ = lookahead.next();
+            if lookahead.peek().map(|(_, next)| *next) != Some('{') {
+                break;
+            }
+            let _ = chars.next();
+        }
+        content.push(chr);
         let _ = chars.next();
     }
+    content
+}
 
-    loop {
-        let Some(chr) = chars.next() else { break };
-
-        offset += chr.len_utf8();
+fn is_name_char(chr: char) -> bool {
+    !chr.is_whitespace() && !matches!(chr, '{' | '}' | ',' | ':')
+}
 
-        if chr.is_whitespace() {
-            continue;
-        }
+fn read_alignment(chars: &mut Chars, input: &str) -> Result<Alignment, TemplateParseError> {
+    skip_whitespaces(chars);
+    let start = position(chars, input);
+    let is_left = chars.next_if(|(_, chr)| *chr == '-').is_some();
+    let mut digits = String::new();
+    while let Some((_, chr)) = chars.next_if(|(_, chr)| chr.is_ascii_digit()) {
+        digits.push(chr);
+    }
+    let Ok(width) = digits.parse::<usize>() else {
+        return Err(error(start, TemplateParseErrorReason::InvalidAlignment));
+    };
+    skip_whitespaces(chars);
+    if is_left {
+        Ok(Alignment::Left(width))
+    } else {
+        Ok(Alignment::Right(width))
+    }
+}
 
-        if chr == '}' {
-            break;
-        }
+/// Reads `{[@|$]name[,alignment][:format]}` starting at `{`.
+fn read_parameter(chars: &mut Chars, input: &str) -> Result<TemplateParameter, TemplateParseError> {
+    let (open_position, _) = chars.next().unwrap();
+    let unterminated = || error(open_position, TemplateParseErrorReason::UnterminatedParameter);
+    skip_whitespaces(chars);
+
+    let hint = match chars.next_if(|(_, chr)| matches!(chr, '@' | '$')) {
+        Some((_, '@')) => ParameterHint::Destructure,
+        Some(_) => ParameterHint::Stringify,
+        None => ParameterHint::Default,
+    };
+
+    let name_position = position(chars, input);
+    let mut name = String::new();
+    while let Some((_, chr)) = chars.next_if(|(_, chr)| is_name_char(*chr)) {
+        name.push(chr);
+    }
+    if name.is_empty() {
+        return match chars.peek() {
+            None => Err(unterminated()),
+            Some((_, '{')) => Err(error(name_position, TemplateParseErrorReason::UnexpectedCharacter('{'))),
+            Some(_) => Err(error(name_position, TemplateParseErrorReason::EmptyParameterName)),
+        };
+    }
+    skip_whitespaces(chars);
 
-        panic!("Invalid template key.");
+    let mut alignment = None;
+    if chars.next_if(|(_, chr)| *chr == ',').is_some() {
+        alignment = Some(read_alignment(chars, input)?);
     }
 
-    let imm = ImmutableString::new(&content).unwrap();
-    (offset, imm)
-}
+    let mut format = None;
+    if chars.next_if(|(_, chr)| *chr == ':').is_some() {
+        let mut content = String::new();
+        while let Some((_, chr)) = chars.next_if(|(_, chr)| *chr != '}') {
+            content.push(chr);
+        }
+        format = Some(new_string(&content));
+    }
 
-fn read_piece(txt: &str) -> (usize, TemplatePiece) {
-    if txt.is_empty() {
-        return (0, TemplatePiece::RawString(ImmutableString::default().clone()));
+    match chars.next() {
+        Some((_, '}')) => { },
+        Some((idx, chr)) => {
+            return Err(error(idx, TemplateParseErrorReason::UnexpectedCharacter(chr)));
+        },
+        None => return Err(unterminated()),
     }
 
-    let mut chars = txt.chars();
+    Ok(TemplateParameter::new(new_string(&name), hint, alignment, format))
+}
 
-    if let Some(current) = chars.next() {
-        if current == '{' {
-            if let Some(next) = chars.next() {
-                if next != '{' {
-                    let (size, imm) = read_key(txt);
-                    return (size, TemplatePiece::Parameter(imm));
-                }
+fn parse(template: &str, lenient: bool) -> Result<Vec<TemplatePiece>, TemplateParseError> {
+    let mut chars = template.char_indices().peekable();
+    let mut result = Vec::with_capacity(4);
+    let mut text = String::new();
+    while let Some((_, chr)) = chars.peek().copied() {
+        let mut lookahead = chars.clone();
+        let _ = lookahead.next();
+        let is_escaped = lookahead.peek().map(|(_, next)| *next) == Some('{');
+        if chr == '{' && !is_escaped {
+            let snapshot = chars.clone();
+            match read_parameter(&mut chars, template) {
+                Ok(parameter) => {
+                    if !text.is_empty() {
+                        result.push(TemplatePiece::RawString(new_string(&text)));
+                        text.clear();
+                    }
+                    result.push(TemplatePiece::Parameter(parameter));
+                },
+                Err(_) if lenient => {
+                    chars = snapshot;
+                    let _ = chars.next();
+                    text.push('{');
+                },
+                Err(err) => return Err(err),
             }
+        } else {
+            text.push_str(&read_text(&mut chars));
         }
     }
+    if !text.is_empty() {
+        result.push(TemplatePiece::RawString(new_string(&text)));
+    }
+    Ok(result)
+}
 
-    let (size, imm) = read_text(txt);
-    (size, TemplatePiece::RawString(imm))
+/// Splits template text into pieces. Text is split on parameters of the
+/// form `{[@|$]name[,alignment][:format]}`, while `{{` is an escaped `{`.
+///
+/// # Errors
+/// * [`TemplateParseError`] with position and reason if parameter
+///   is malformed
+pub fn parse_template(template: &str) -> Result<Vec<TemplatePiece>, TemplateParseError> {
+    parse(template, false)
 }
 
-pub(super) fn parse_template_to_pieces(template: &ImmutableString) -> Vec<TemplatePiece> {
-    if template.as_str().is_empty() {
-        return Vec::default();
+/// Same as [`parse_template`] but never fails: malformed parameters are
+/// kept as raw text, e.g. `"a {b c} {d}"` is split into
+/// `RawString("a {b c} ")` -> `Parameter("d")`.
+pub fn parse_template_lenient(template: &str) -> Vec<TemplatePiece> {
+    match parse(template, true) {
+        Ok(pieces) => pieces,
+        Err(_) => unreachable!("Lenient parsing doesn't fail."),
     }
-    
-    let mut txt = template.as_str();
-    let mut result = Vec::with_capacity(4);
-    while !txt.is_empty() {
-        let (read, piece) = read_piece(txt);
-        result.push(piece);
-        let current_len = txt.len();
-        txt = &txt[read..current_len];
-    }
-
-    result
 }
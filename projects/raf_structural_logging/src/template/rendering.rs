@@ -0,0 +1,357 @@
+use std::{
+    fmt::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+
+use crate::{
+    models::{LogDataHolder, SLObject},
+    traits::LogLevel};
+
+use super::{Alignment, ParameterHint, TemplateParameter, TemplatePiece};
+
+/// Renders log's template with its parameters into plain text. Parameters
+/// are looked up with [`LogDataHolder::get`] and rendered with
+/// [`render_parameter`]. Missing parameters are rendered
+/// as empty strings.
+pub fn render_message(log: &LogDataHolder) -> String {
+    let mut result = String::new();
+    for piece in log.template().pieces() {
+        match piece {
+            TemplatePiece::RawString(txt) => {
+                result.push_str(txt.as_str());
+            },
+            TemplatePiece::Parameter(parameter) => {
+                let value = log.get(parameter.name());
+                render_parameter(value.unwrap_or(&SLObject::Empty), parameter, &mut result);
+            },
+        }
+    }
+    result
+}
+
+/// Renders `obj` according to `parameter`'s hint, format and alignment.
+/// Hints take precedence over format. Formats are interpreted depending
+/// on value type, and ignored if not applicable:
+/// * numbers accept `x`/`X` (hexadecimal, integers only), `e`/`E`
+///   (scientific) and zero patterns like `000` or `0.00`, where the number
+///   of zeros before the dot is the minimal number of integer digits and
+///   after the dot is the exact number of decimal digits
+/// * system time accepts `strftime`-like format in UTC with `%Y`, `%m`,
+///   `%d`, `%H`, `%M`, `%S`, `%3f` (milliseconds), `%F` (`%Y-%m-%d`),
+///   `%T` (`%H:%M:%S`) and `%%`, e.g. `{when:%F %T}`
+pub fn render_parameter(obj: &SLObject, parameter: &TemplateParameter, output: &mut String) {
+    if parameter.alignment().is_none() {
+        render_unaligned(obj, parameter, output);
+        return;
+    }
+
+    let mut text = String::new();
+    render_unaligned(obj, parameter, &mut text);
+    let length = text.chars().count();
+    match parameter.alignment() {
+        Some(Alignment::Left(width)) => {
+            output.push_str(&text);
+            output.extend(core::iter::repeat(' ').take(width.saturating_sub(length)));
+        },
+        Some(Alignment::Right(width)) => {
+            output.extend(core::iter::repeat(' ').take(width.saturating_sub(length)));
+            output.push_str(&text);
+        },
+        None => output.push_str(&text),
+    }
+}
+
+fn render_unaligned(obj: &SLObject, parameter: &TemplateParameter, output: &mut String) {
+    match parameter.hint() {
+        ParameterHint::Destructure => render_destructured(obj, output),
+        ParameterHint::Stringify => {
+            let mut text = String::new();
+            render_object(obj, &mut text);
+            push_quoted(&text, output);
+        },
+        ParameterHint::Default => {
+            let formatted = parameter.format()
+                .as_ref()
+                .and_then(|format| format_object(obj, format.as_str()));
+            match formatted {
+                Some(text) => output.push_str(&text),
+                None => render_object(obj, output),
+            }
+        },
+    }
+}
+
+/// Renders `obj` into plain text, without any format.
+pub fn render_object(obj: &SLObject, output: &mut String) {
+    match obj {
+        SLObject::Empty => { },
+        SLObject::LogLevel(inner) => output.push_str(level_to_str(*inner.value())),
+        SLObject::SystemTime(inner) => render_time(*inner.value(), output),
+        SLObject::Duration(inner) => render_duration(*inner.value(), output),
+        SLObject::String(inner) => output.push_str(inner.value().as_str()),
+        SLObject::Number(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::Float(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::UInt64(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::UInt128(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::Bool(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::Bytes(inner) => {
+            output.push_str("0x");
+            for byte in inner.value() {
+                let _ = write!(output, "{byte:02x}");
+            }
+        },
+        SLObject::Uuid(inner) => { let _ = write!(output, "{inner}"); },
+        SLObject::IpAddr(inner) => { let _ = write!(output, "{}", inner.value()); },
+        SLObject::Error(inner) => {
+            output.push_str(inner.message().as_str());
+            for source in inner.sources() {
+                output.push_str(": ");
+                output.push_str(source.as_str());
+            }
+        },
+        SLObject::Array(inner) => render_array(inner.value(), output, render_object),
+        SLObject::Dict(inner) => {
+            let items: Vec<_> = inner.value().iter().collect();
+            render_dict(&items, output, render_object);
+        },
+    }
+}
+
+fn render_destructured(obj: &SLObject, output: &mut String) {
+    match obj {
+        SLObject::String(inner) => push_quoted(inner.value().as_str(), output),
+        SLObject::Array(inner) => render_array(inner.value(), output, render_destructured),
+        SLObject::Dict(inner) => {
+            let mut items: Vec<_> = inner.value().iter().collect();
+            items.sort_by(|left, right| left.0.as_str().cmp(right.0.as_str()));
+            render_dict(&items, output, render_destructured);
+        },
+        _ => render_object(obj, output),
+    }
+}
+
+fn level_to_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Debug => "Debug",
+        LogLevel::Info => "Info",
+        LogLevel::Warning => "Warning",
+        LogLevel::Error => "Error",
+    }
+}
+
+fn render_duration(value: Duration, output: &mut String) {
+    let _ = write!(output, "{}.{:03}s", value.as_secs(), value.subsec_millis());
+}
+
+fn render_array(value: &[SLObject], output: &mut String, render: fn(&SLObject, &mut String)) {
+    output.push('[');
+    for (idx, item) in value.iter().enumerate() {
+        if idx > 0 {
+            output.push_str(", ");
+        }
+        render(item, output);
+    }
+    output.push(']');
+}
+
+fn render_dict(
+    items: &[(&ImmutableString, &SLObject)],
+    output: &mut String,
+    render: fn(&SLObject, &mut String))
+{
+    output.push('{');
+    for (idx, (key, item)) in items.iter().enumerate() {
+        if idx > 0 {
+            output.push_str(", ");
+        }
+        output.push_str(key.as_str());
+        output.push_str(": ");
+        render(item, output);
+    }
+    output.push('}');
+}
+
+fn push_quoted(text: &str, output: &mut String) {
+    output.push('"');
+    for chr in text.chars() {
+        if matches!(chr, '"' | '\\') {
+            output.push('\\');
+        }
+        output.push(chr);
+    }
+    output.push('"');
+}
+
+fn format_object(obj: &SLObject, format: &str) -> Option<String> {
+    match obj {
+        SLObject::Number(inner) => format_integer(i128::from(inner.value()), format),
+        SLObject::UInt64(inner) => format_integer(i128::from(inner.value()), format),
+        SLObject::UInt128(inner) => {
+            match i128::try_from(*inner.value()) {
+                Ok(value) => format_integer(value, format),
+                Err(_) => None,
+            }
+        },
+        SLObject::Float(inner) => format_float(inner.value(), format),
+        SLObject::SystemTime(inner) => Some(format_time(*inner.value(), format)),
+        _ => None,
+    }
+}
+
+/// Parses zero patterns like `00.000` into number of integer digits
+/// and number of decimal digits.
+fn parse_zero_pattern(format: &str) -> Option<(usize, usize)> {
+    let (integer, decimals) = format.split_once('.').unwrap_or((format, ""));
+    let is_zeros = |text: &str| text.bytes().all(|chr| chr == b'0');
+    if integer.is_empty() || !is_zeros(integer) || !is_zeros(decimals) {
+        return None;
+    }
+    if format.contains('.') && decimals.is_empty() {
+        return None;
+    }
+    Some((integer.len(), decimals.len()))
+}
+
+fn pad_integer_digits(text: &str, is_negative: bool, min_digits: usize) -> String {
+    let integer_len = text.find('.').unwrap_or(text.len());
+    let mut result = String::with_capacity(text.len() + min_digits + 1);
+    if is_negative {
+        result.push('-');
+    }
+    result.extend(core::iter::repeat('0').take(min_digits.saturating_sub(integer_len)));
+    result.push_str(text);
+    result
+}
+
+fn format_integer(value: i128, format: &str) -> Option<String> {
+    match format {
+        "x" => return Some(format!("{value:x}")),
+        "X" => return Some(format!("{value:X}")),
+        "e" => return Some(format!("{value:e}")),
+        "E" => return Some(format!("{value:E}")),
+        _ => { },
+    }
+    let (integer_digits, decimals) = parse_zero_pattern(format)?;
+    let mut text = value.unsigned_abs().to_string();
+    if decimals > 0 {
+        text.push('.');
+        text.extend(core::iter::repeat('0').take(decimals));
+    }
+    Some(pad_integer_digits(&text, value < 0, integer_digits))
+}
+
+fn format_float(value: f64, format: &str) -> Option<String> {
+    if !value.is_finite() {
+        return None;
+    }
+    match format {
+        "e" => return Some(format!("{value:e}")),
+        "E" => return Some(format!("{value:E}")),
+        _ => { },
+    }
+    let (integer_digits, decimals) = parse_zero_pattern(format)?;
+    let text = format!("{:.decimals$}", value.abs());
+    let is_negative = value < 0.0 && text.bytes().any(|chr| matches!(chr, b'1'..=b'9'));
+    Some(pad_integer_digits(&text, is_negative, integer_digits))
+}
+
+/// UTC date and time split into calendar fields.
+struct DateTimeParts {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanos: u32,
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+fn to_parts(time: SystemTime) -> DateTimeParts {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos()),
+        Err(err) => {
+            let duration = err.duration();
+            let mut secs = -(duration.as_secs() as i64);
+            let mut nanos = duration.subsec_nanos();
+            if nanos > 0 {
+                secs -= 1;
+                nanos = 1_000_000_000 - nanos;
+            }
+            (secs, nanos)
+        },
+    };
+    let days = secs.div_euclid(86400);
+    let seconds_of_day = secs.rem_euclid(86400) as u32;
+
+    // Converts days since epoch to civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era/1460 + day_of_era/36524 - day_of_era/146_096) / 365;
+    let day_of_year = day_of_era - (365*year_of_era + year_of_era/4 - year_of_era/100);
+    let month_index = (5*day_of_year + 2) / 153;
+    let day = (day_of_year - (153*month_index + 2)/5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era*400 + i64::from(month <= 2);
+
+    DateTimeParts {
+        year: year,
+        month: month,
+        day: day,
+        hour: seconds_of_day / 3600,
+        minute: seconds_of_day % 3600 / 60,
+        second: seconds_of_day % 60,
+        nanos: nanos,
+    }
+}
+
+/// Renders time in RFC3339 format, in UTC, with as many fraction digits
+/// as needed: none, 3, 6 or 9.
+fn render_time(time: SystemTime, output: &mut String) {
+    let parts = to_parts(time);
+    let _ = write!(
+        output,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        parts.year, parts.month, parts.day, parts.hour, parts.minute, parts.second);
+    if parts.nanos == 0 {
+        // No fraction.
+    } else if parts.nanos % 1_000_000 == 0 {
+        let _ = write!(output, ".{:03}", parts.nanos / 1_000_000);
+    } else if parts.nanos % 1000 == 0 {
+        let _ = write!(output, ".{:06}", parts.nanos / 1000);
+    } else {
+        let _ = write!(output, ".{:09}", parts.nanos);
+    }
+    output.push('Z');
+}
+
+fn format_time(time: SystemTime, format: &str) -> String {
+    let parts = to_parts(time);
+    let mut output = String::with_capacity(format.len() + 16);
+    let mut chars = format.chars().peekable();
+    while let Some(chr) = chars.next() {
+        if chr != '%' {
+            output.push(chr);
+            continue;
+        }
+        let _ = match chars.next() {
+            Some('Y') => write!(output, "{:04}", parts.year),
+            Some('m') => write!(output, "{:02}", parts.month),
+            Some('d') => write!(output, "{:02}", parts.day),
+            Some('H') => write!(output, "{:02}", parts.hour),
+            Some('M') => write!(output, "{:02}", parts.minute),
+            Some('S') => write!(output, "{:02}", parts.second),
+            Some('F') => write!(output, "{:04}-{:02}-{:02}", parts.year, parts.month, parts.day),
+            Some('T') => write!(output, "{:02}:{:02}:{:02}", parts.hour, parts.minute, parts.second),
+            Some('%') | None => write!(output, "%"),
+            Some('3') if chars.next_if_eq(&'f').is_some() => {
+                write!(output, "{:03}", parts.nanos / 1_000_000)
+            },
+            Some(other) => write!(output, "%{other}"),
+        };
+    }
+    output
+}
@@ -0,0 +1,269 @@
+//! Holds helpers for testing code that logs, see [`CapturingHandler`].
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{Arc, LazyLock, Mutex, MutexGuard}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_readonly::readonly;
+
+use crate::{
+    core::{CoreLoggerFactory, CoreLoggerFactoryBuilder, DispatchMode},
+    models::{LogDataHolder, SLObject},
+    template::{
+        render_message,
+        render_parameter,
+        ParameterHint,
+        Template,
+        TemplateParameter},
+    traits::{LogLevel, StructuralLogHandler, StructuralLoggerFactoryBuilder}};
+
+static SNAPSHOT_PARAMETER: LazyLock<TemplateParameter> = LazyLock::new(|| {
+    TemplateParameter::new(
+        ImmutableString::new("value").unwrap(),
+        ParameterHint::Destructure,
+        None,
+        None)
+});
+
+/// Copy of [`LogDataHolder`] received by [`CapturingHandler`].
+#[readonly]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CapturedLog {
+    pub log_level: LogLevel,
+    pub logger_name: Option<ImmutableString>,
+    pub template: Template,
+    pub message: String,
+    pub template_params: HashMap<ImmutableString, SLObject>,
+    pub additional_data: HashMap<ImmutableString, SLObject>,
+    logger_name_key: Option<ImmutableString>,
+}
+
+impl CapturedLog {
+    fn from_log(log: &LogDataHolder) -> Self {
+        Self {
+            log_level: log.log_level(),
+            logger_name: log.logger_name().cloned(),
+            template: log.template().clone(),
+            message: render_message(log),
+            template_params: log.template_params().clone(),
+            additional_data: log.additional_data().clone(),
+            logger_name_key: log.logger_name_key().cloned(),
+        }
+    }
+
+    /// Returns value of `name` parameter, looked up in `template_params`
+    /// first, then in `additional_data`.
+    pub fn param(&self, name: &str) -> Option<&SLObject> {
+        let key = ImmutableString::new(name).ok()?;
+        self.template_params
+            .get(&key)
+            .or_else(|| self.additional_data.get(&key))
+    }
+}
+
+/// Renders log into a single deterministic line, suitable for snapshot
+/// tests: level, logger name and message, followed by sorted parameters
+/// not referenced by the template. The logger name entry is left out of
+/// these parameters, since it is already in the header.
+impl Display for CapturedLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let logger_name = self.logger_name.as_ref().map_or("", ImmutableString::as_str);
+        write!(f, "{:?} {logger_name}: {}", self.log_level, self.message)?;
+
+        let mut extra: Vec<(&ImmutableString, &SLObject)> = self.template_params
+            .iter()
+            .filter(|(key, _)| self.template.parameters().all(|param| param.name() != *key))
+            .chain(self.additional_data
+                .iter()
+                .filter(|(key, _)| Some(*key) != self.logger_name_key.as_ref() && !self.template_params.contains_key(*key)))
+            .collect();
+        if extra.is_empty() {
+            return Ok(());
+        }
+        extra.sort_by(|(left, _), (right, _)| left.as_str().cmp(right.as_str()));
+        let mut output = String::new();
+        for (idx, (key, value)) in extra.into_iter().enumerate() {
+            output.push_str(if idx == 0 { " {" } else { ", " });
+            output.push_str(key.as_str());
+            output.push('=');
+            render_parameter(value, &SNAPSHOT_PARAMETER, &mut output);
+        }
+        output.push('}');
+        f.write_str(&output)
+    }
+}
+
+/// Snapshot of captured logs narrowed down by filters, e.g.
+/// `handler.query().level(LogLevel::Warning).logger("db").assert_count(1)`.
+/// Assertions panic with the matching logs rendered one per line.
+#[derive(Clone, Debug)]
+pub struct LogQuery {
+    logs: Vec<CapturedLog>,
+}
+
+impl LogQuery {
+    /// Keeps logs for which `predicate` returns `true`.
+    #[must_use]
+    pub fn filter<F>(mut self, predicate: F) -> Self
+        where F: Fn(&CapturedLog) -> bool
+    {
+        self.logs.retain(predicate);
+        self
+    }
+
+    /// Keeps logs with exactly `log_level`.
+    #[must_use]
+    pub fn level(self, log_level: LogLevel) -> Self {
+        self.filter(|log| log.log_level == log_level)
+    }
+
+    /// Keeps logs with at least `log_level`.
+    #[must_use]
+    pub fn min_level(self, log_level: LogLevel) -> Self {
+        self.filter(|log| (log.log_level as i32) >= (log_level as i32))
+    }
+
+    /// Keeps logs sent by logger named `name`.
+    #[must_use]
+    pub fn logger(self, name: &str) -> Self {
+        self.filter(|log| log.logger_name.as_ref().is_some_and(|value| value.as_str() == name))
+    }
+
+    /// Keeps logs with template text equal to `template`.
+    #[must_use]
+    pub fn template(self, template: &str) -> Self {
+        self.filter(|log| log.template.as_immutable_string().as_str() == template)
+    }
+
+    /// Keeps logs whose rendered message contains `text`.
+    #[must_use]
+    pub fn message_contains(self, text: &str) -> Self {
+        self.filter(|log| log.message.contains(text))
+    }
+
+    /// Keeps logs with `name` parameter equal to `value`, see
+    /// [`CapturedLog::param`].
+    #[must_use]
+    pub fn param<T>(self, name: &str, value: T) -> Self
+        where T: Into<SLObject>
+    {
+        let value = value.into();
+        self.filter(|log| log.param(name) == Some(&value))
+    }
+
+    #[inline(always)]
+    pub fn logs(&self) -> &[CapturedLog] { &self.logs }
+
+    #[inline(always)]
+    pub fn into_logs(self) -> Vec<CapturedLog> { self.logs }
+
+    #[inline(always)]
+    pub fn count(&self) -> usize { self.logs.len() }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.logs.is_empty() }
+
+    /// Returns rendered messages of matching logs.
+    pub fn messages(&self) -> Vec<String> {
+        self.logs.iter().map(|log| log.message.clone()).collect()
+    }
+
+    /// Renders matching logs one per line, see [`CapturedLog`]'s
+    /// `Display` implementation.
+    pub fn snapshot(&self) -> String {
+        let lines: Vec<String> = self.logs.iter().map(ToString::to_string).collect();
+        lines.join("\n")
+    }
+
+    /// # Panics
+    /// If number of matching logs is not `expected`
+    #[track_caller]
+    pub fn assert_count(&self, expected: usize) {
+        assert!(
+            self.count() == expected,
+            "Expected {expected} matching logs, found {}:\n{}", self.count(), self.snapshot());
+    }
+
+    /// # Panics
+    /// If there are no matching logs
+    #[track_caller]
+    pub fn assert_any(&self) {
+        assert!(!self.is_empty(), "Expected at least one matching log, found none.");
+    }
+
+    /// # Panics
+    /// If there are matching logs
+    #[track_caller]
+    pub fn assert_none(&self) {
+        assert!(self.is_empty(), "Expected no matching logs, found:\n{}", self.snapshot());
+    }
+
+    /// Returns the only matching log.
+    ///
+    /// # Panics
+    /// If number of matching logs is not exactly one
+    #[track_caller]
+    pub fn assert_single(self) -> CapturedLog {
+        self.assert_count(1);
+        self.logs.into_iter().next().unwrap()
+    }
+}
+
+/// Handler that keeps copies of all received logs in memory, so that
+/// tests can query and assert them. Combine with
+/// [`DispatchMode::Synchronous`] so that logs are captured before
+/// [`StructuralLogger::log`][crate::traits::StructuralLogger::log]
+/// returns, e.g. through [`CapturingHandler::factory`].
+#[derive(Default)]
+pub struct CapturingHandler {
+    logs: Mutex<Vec<CapturedLog>>,
+}
+
+impl CapturingHandler {
+    pub fn new() -> Self { Self::default() }
+
+    /// Builds synchronous [`CoreLoggerFactory`] with new capturing
+    /// handler that receives logs of all levels.
+    pub fn factory() -> (CoreLoggerFactory, Arc<CapturingHandler>) {
+        let handler = Arc::new(Self::new());
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_dispatch_mode(DispatchMode::Synchronous);
+        builder.add_handler(handler.clone());
+        (builder.build(), handler)
+    }
+
+    /// Returns copies of all captured logs in order of arrival.
+    pub fn logs(&self) -> Vec<CapturedLog> { self.lock().clone() }
+
+    /// Returns and removes all captured logs.
+    pub fn take(&self) -> Vec<CapturedLog> { std::mem::take(&mut *self.lock()) }
+
+    pub fn clear(&self) { self.lock().clear(); }
+
+    pub fn len(&self) -> usize { self.lock().len() }
+
+    pub fn is_empty(&self) -> bool { self.lock().is_empty() }
+
+    /// Returns query over snapshot of currently captured logs.
+    pub fn query(&self) -> LogQuery {
+        LogQuery { logs: self.logs() }
+    }
+
+    /// Returns rendered messages of all captured logs.
+    pub fn messages(&self) -> Vec<String> { self.query().messages() }
+
+    /// Renders all captured logs one per line, see [`LogQuery::snapshot`].
+    pub fn snapshot(&self) -> String { self.query().snapshot() }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<CapturedLog>> {
+        self.logs.lock().expect("CapturingHandler - lock() fail")
+    }
+}
+
+impl StructuralLogHandler for CapturingHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        let captured = CapturedLog::from_log(log);
+        self.lock().push(captured);
+    }
+}
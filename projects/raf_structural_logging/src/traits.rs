@@ -26,6 +26,11 @@ pub trait StructuralLog {
 /// Rerpresents logger for structured logs.
 pub trait StructuralLogger {
     fn log<T>(&self, log: T) where T : StructuralLog;
+
+    /// Returns `false` if logs with given `log_level` would be discarded
+    /// by [`StructuralLogger::log`] anyway. Allows callers to skip building
+    /// expensive logs.
+    fn is_enabled(&self, _log_level: LogLevel) -> bool { true }
 }
 
 /// Rerpresents factory for structural loggers.
@@ -39,6 +44,26 @@ pub trait StructuralLoggerFactory {
             .expect("StructuralLoggerFactory - create_from_str() fail on new ImmutableString");
         self.create(&imm)
     }
+
+    /// Creates logger named after type `T`, see [`type_logger_name`].
+    /// Such names integrate with per-prefix level rules, e.g. a rule for
+    /// `my_crate.db` applies to loggers of all types in `my_crate::db`
+    /// module.
+    fn create_for<T: ?Sized>(&self) -> Self::Logger {
+        self.create_from_str(&type_logger_name::<T>())
+    }
+}
+
+/// Converts full path of type `T` into dotted logger name, e.g.
+/// `my_crate::db::Pool<u32>` into `my_crate.db.Pool`. Generic parameters
+/// are skipped.
+pub fn type_logger_name<T: ?Sized>() -> String {
+    let type_name = std::any::type_name::<T>();
+    let path = match type_name.find('<') {
+        Some(idx) => &type_name[..idx],
+        None => type_name,
+    };
+    path.replace("::", ".")
 }
 
 /// Rerpresents builder for structural logger factories.
@@ -55,4 +80,17 @@ pub trait StructuralLoggerFactoryBuilder {
 /// Rerpresents handlers for structural logs.
 pub trait StructuralLogHandler : Sync + Send {
     fn handle(&self, log: &LogDataHolder);
+
+    /// Handles consecutive logs at once, e.g. to write them with a single
+    /// I/O call. Called instead of [`StructuralLogHandler::handle`] in
+    /// batched dispatch mode. By default handles logs one by one.
+    fn handle_batch(&self, logs: &[LogDataHolder]) {
+        for log in logs {
+            self.handle(log);
+        }
+    }
+
+    /// Makes sure all handled logs reach their destination, e.g. flushes
+    /// internal buffers. Called by logger factories on flush and shutdown.
+    fn flush(&self) { }
 }
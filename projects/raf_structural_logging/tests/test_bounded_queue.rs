@@ -0,0 +1,185 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Condvar, LazyLock, Mutex},
+    thread,
+    time::Duration};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactoryBuilder, OverflowPolicy},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Received {
+    Log(i64),
+    Dropped(i64),
+}
+
+/// Handler that blocks on the first log until it is released.
+#[derive(Default)]
+struct GateHandler {
+    state: Mutex<(bool, bool)>,
+    changed: Condvar,
+    received: Mutex<Vec<Received>>,
+}
+
+impl GateHandler {
+    fn wait_until_entered(&self) {
+        let mut guard = self.state.lock().unwrap();
+        while !guard.0 {
+            guard = self.changed.wait(guard).unwrap();
+        }
+    }
+
+    fn release(&self) {
+        let mut guard = self.state.lock().unwrap();
+        guard.1 = true;
+        self.changed.notify_all();
+    }
+
+    fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+fn param(log: &LogDataHolder, key: &str) -> Option<i64> {
+    match log.template_params().get(&ImmutableString::new(key).unwrap()) {
+        Some(SLObject::Number(number)) => Some(number.value()),
+        _ => None,
+    }
+}
+
+impl StructuralLogHandler for GateHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        {
+            let mut guard = self.state.lock().unwrap();
+            guard.0 = true;
+            self.changed.notify_all();
+            while !guard.1 {
+                guard = self.changed.wait(guard).unwrap();
+            }
+        }
+
+        let item = match param(log, "idx") {
+            Some(idx) => Received::Log(idx),
+            None => {
+                assert_eq!(log.log_level(), LogLevel::Warning);
+                assert_eq!(log.template().as_immutable_string().as_str(), "{dropped_count} logs dropped");
+                Received::Dropped(param(log, "dropped_count").unwrap())
+            },
+        };
+        self.received.lock().unwrap().push(item);
+    }
+}
+
+struct TestLog(i64, LogLevel);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let mut params = HashMap::new();
+        params.insert(ImmutableString::new("idx").unwrap(), SLObject::from(self.0));
+        LogDataHolder::new(self.1, TMPL_BUILDER.create_from_str("Log {idx}"), params)
+    }
+}
+
+#[rstest]
+#[case(OverflowPolicy::DropNewest, &[LogLevel::Info; 5], vec![0, 1, 2], 2)]
+#[case(OverflowPolicy::DropOldest, &[LogLevel::Info; 5], vec![0, 3, 4], 2)]
+#[case(
+    OverflowPolicy::DropBelowLevel(LogLevel::Warning),
+    &[LogLevel::Error, LogLevel::Info, LogLevel::Info, LogLevel::Info, LogLevel::Debug],
+    vec![0, 1, 2],
+    2)]
+fn test_drop_policies(
+    #[case] policy: OverflowPolicy,
+    #[case] levels: &[LogLevel],
+    #[case] expected: Vec<i64>,
+    #[case] expected_dropped: u64)
+{
+    let handler = Arc::new(GateHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_queue_capacity(2);
+        builder.set_overflow_policy(policy);
+        builder.add_handler(handler.clone());
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+
+        logger.log(TestLog(0, levels[0]));
+        handler.wait_until_entered();
+        for (idx, level) in levels.iter().enumerate().skip(1) {
+            logger.log(TestLog(idx as i64, *level));
+        }
+        assert_eq!(factory.dropped_logs(), expected_dropped);
+        handler.release();
+    }
+
+    let mut expected: Vec<Received> = expected.into_iter().map(Received::Log).collect();
+    expected.push(Received::Dropped(expected_dropped as i64));
+    assert_eq!(handler.received(), expected);
+}
+
+#[test]
+fn test_block_policy() {
+    let handler = Arc::new(GateHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_queue_capacity(1);
+        builder.add_handler(handler.clone());
+        let factory = builder.build();
+        let logger = Arc::new(factory.create_from_str("misc"));
+
+        logger.log(TestLog(0, LogLevel::Info));
+        handler.wait_until_entered();
+        logger.log(TestLog(1, LogLevel::Info));
+
+        let sender = {
+            let logger = logger.clone();
+            thread::spawn(move || logger.log(TestLog(2, LogLevel::Info)))
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!sender.is_finished());
+
+        handler.release();
+        sender.join().unwrap();
+        assert_eq!(factory.dropped_logs(), 0);
+    }
+
+    let expected = vec![Received::Log(0), Received::Log(1), Received::Log(2)];
+    assert_eq!(handler.received(), expected);
+}
+
+#[test]
+fn test_unbounded_by_default() {
+    let handler = Arc::new(GateHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_overflow_policy(OverflowPolicy::DropNewest);
+        builder.add_handler(handler.clone());
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+        for idx in 0..100 {
+            logger.log(TestLog(idx, LogLevel::Info));
+        }
+        handler.release();
+        assert_eq!(factory.dropped_logs(), 0);
+    }
+
+    let expected: Vec<Received> = (0..100).map(Received::Log).collect();
+    assert_eq!(handler.received(), expected);
+}
@@ -0,0 +1,167 @@
+#![cfg(all(feature="log", feature="tracing"))]
+use std::sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    bridge::{from_log_level, from_tracing_level, LogBridge, TracingBridge},
+    core::{CoreLoggerFactory, CoreLoggerFactoryBuilder},
+    models::{LogDataHolder, SLObject},
+    traits::{LogLevel, StructuralLogHandler, StructuralLoggerFactoryBuilder}};
+use rstest::rstest;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+/// Level, logger name, template and sorted template params.
+type Entry = (LogLevel, String, String, Vec<(String, SLObject)>);
+
+#[derive(Default)]
+struct TestHandler {
+    logs: Mutex<Vec<Entry>>,
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        let key = ImmutableString::new("logger_name").unwrap();
+        let SLObject::String(name) = &log.additional_data()[&key] else {
+            panic!("Invalid logger_name");
+        };
+        let mut params: Vec<(String, SLObject)> = log.template_params()
+            .iter()
+            .map(|(key, value)| (key.as_str().to_owned(), value.clone()))
+            .collect();
+        params.sort_by(|left, right| left.0.cmp(&right.0));
+        self.logs.lock().unwrap().push((
+            log.log_level(),
+            name.value().as_str().to_owned(),
+            log.template().as_immutable_string().as_str().to_owned(),
+            params));
+    }
+}
+
+/// Layer that counts all events it receives.
+#[derive(Clone, Default)]
+struct CountingLayer {
+    count: Arc<AtomicUsize>,
+}
+
+impl<S: Subscriber> Layer<S> for CountingLayer {
+    fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn build_factory(handler: Arc<TestHandler>) -> Arc<CoreLoggerFactory> {
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_log_level(LogLevel::Info);
+    builder.add_level_rule("app.db", LogLevel::Debug);
+    builder.add_handler(handler);
+    Arc::new(builder.build())
+}
+
+fn param(key: &str, value: impl Into<SLObject>) -> (String, SLObject) {
+    (key.to_owned(), value.into())
+}
+
+#[rstest]
+#[case(log::Level::Trace, LogLevel::Debug)]
+#[case(log::Level::Debug, LogLevel::Debug)]
+#[case(log::Level::Info, LogLevel::Info)]
+#[case(log::Level::Warn, LogLevel::Warning)]
+#[case(log::Level::Error, LogLevel::Error)]
+fn test_log_levels(#[case] level: log::Level, #[case] expected: LogLevel) {
+    assert_eq!(from_log_level(level), expected);
+}
+
+#[rstest]
+#[case(tracing::Level::TRACE, LogLevel::Debug)]
+#[case(tracing::Level::DEBUG, LogLevel::Debug)]
+#[case(tracing::Level::INFO, LogLevel::Info)]
+#[case(tracing::Level::WARN, LogLevel::Warning)]
+#[case(tracing::Level::ERROR, LogLevel::Error)]
+fn test_tracing_levels(#[case] level: tracing::Level, #[case] expected: LogLevel) {
+    assert_eq!(from_tracing_level(level), expected);
+}
+
+#[test]
+fn test_log_bridge() {
+    let handler = Arc::new(TestHandler::default());
+    let factory = build_factory(handler.clone());
+    LogBridge::new(factory.clone()).install().unwrap();
+
+    log::debug!(target: "app::http", "skipped");
+    log::debug!(target: "app::db::pool", user = "john", retries = 3, ok = true; "Connected to {}", "db1");
+    log::warn!(target: "app::http", "Slow {{request}}");
+
+    // Loggers are reused per target, but still follow runtime changes.
+    assert!(!log::log_enabled!(target: "app::http", log::Level::Debug));
+    factory.config_handle().add_level_rule("app.http", LogLevel::Debug);
+    assert!(log::log_enabled!(target: "app::http", log::Level::Debug));
+    log::debug!(target: "app::http", "Logged");
+    log::logger().flush();
+
+    let logs = handler.logs.lock().unwrap().clone();
+    let expected = vec![
+        (
+            LogLevel::Debug,
+            "app.db.pool".to_owned(),
+            "{message} ok={ok} retries={retries} user={user}".to_owned(),
+            vec![
+                param("message", "Connected to db1"),
+                param("ok", true),
+                param("retries", 3i64),
+                param("user", "john"),
+            ],
+        ),
+        (
+            LogLevel::Warning,
+            "app.http".to_owned(),
+            "{message}".to_owned(),
+            vec![param("message", "Slow {request}")],
+        ),
+        (
+            LogLevel::Debug,
+            "app.http".to_owned(),
+            "{message}".to_owned(),
+            vec![param("message", "Logged")],
+        ),
+    ];
+    assert_eq!(logs, expected);
+}
+
+#[test]
+fn test_tracing_bridge() {
+    let handler = Arc::new(TestHandler::default());
+    let factory = build_factory(handler.clone());
+    let counter = CountingLayer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(TracingBridge::new(factory.clone()))
+        .with(counter.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug!(target: "app::http", "skipped");
+        let span = tracing::info_span!("request", id = 5);
+        let _entered = span.enter();
+        tracing::debug!(target: "app::db", user = "john", count = 7u64, big = u64::MAX, ratio = 0.5, "Query {}", 1);
+        tracing::error!(target: "app::http", ?span, "Failed");
+    });
+    factory.flush();
+
+    // Events skipped by the bridge still reach other layers.
+    assert_eq!(counter.count.load(Ordering::Relaxed), 3);
+    let logs = handler.logs.lock().unwrap().clone();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].0, LogLevel::Debug);
+    assert_eq!(logs[0].1, "app.db");
+    assert_eq!(logs[0].2, "{message} big={big} count={count} ratio={ratio} user={user}");
+    let expected = vec![
+        param("big", u64::MAX.to_string()),
+        param("count", 7i64),
+        param("message", "Query 1"),
+        param("ratio", "0.5"),
+        param("user", "john"),
+    ];
+    assert_eq!(logs[0].3, expected);
+    assert_eq!(logs[1].0, LogLevel::Error);
+    assert_eq!(logs[1].2, "{message} span={span}");
+    assert_eq!(logs[1].3[0], param("message", "Failed"));
+}
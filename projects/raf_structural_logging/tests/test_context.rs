@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+    thread};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{push_context, CoreLoggerFactoryBuilder},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct TestHandler {
+    logs: Mutex<Vec<HashMap<ImmutableString, SLObject>>>,
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        self.logs.lock().unwrap().push(log.additional_data().clone());
+    }
+}
+
+#[derive(Default)]
+struct TestLog {
+    data: Vec<(&'static str, &'static str)>,
+}
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let mut log = LogDataHolder::new(
+            LogLevel::Info,
+            TMPL_BUILDER.create_from_str("xyz"),
+            HashMap::new());
+        for (key, value) in &self.data {
+            log.update_data(ImmutableString::new(key).unwrap(), *value);
+        }
+        log
+    }
+}
+
+fn value(data: &HashMap<ImmutableString, SLObject>, key: &str) -> Option<SLObject> {
+    data.get(&ImmutableString::new(key).unwrap()).cloned()
+}
+
+fn text(value: &str) -> Option<SLObject> {
+    Some(SLObject::from(value))
+}
+
+#[test]
+fn test_enrichers() {
+    let handler = Arc::new(TestHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_static_data("host", "localhost");
+    builder.add_static_data("version", 3i64);
+    builder.add_enricher("thread", || {
+        SLObject::from(thread::current().name().unwrap_or("unnamed"))
+    });
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = Arc::new(factory.create_from_str("misc"));
+
+    logger.log(TestLog { data: vec![("version", "custom")] });
+    let other = logger.clone();
+    thread::Builder::new()
+        .name("worker-1".to_owned())
+        .spawn(move || other.log(TestLog::default()))
+        .unwrap()
+        .join()
+        .unwrap();
+    factory.flush();
+
+    let logs = handler.logs.lock().unwrap();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(value(&logs[0], "host"), text("localhost"));
+    assert_eq!(value(&logs[0], "version"), text("custom"));
+    assert_eq!(value(&logs[0], "logger_name"), text("misc"));
+    assert_eq!(value(&logs[1], "version"), Some(SLObject::from(3i64)));
+    assert_eq!(value(&logs[1], "thread"), text("worker-1"));
+}
+
+#[test]
+fn test_logger_context() {
+    let handler = Arc::new(TestHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_static_data("request_id", "none");
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+
+    let child = logger.with_context([("request_id", "r-1"), ("user_id", "u-1")]);
+    let grandchild = child.with_context([("user_id", "u-2")]);
+    logger.log(TestLog::default());
+    child.log(TestLog::default());
+    grandchild.log(TestLog { data: vec![("request_id", "own")] });
+    factory.flush();
+
+    let logs = handler.logs.lock().unwrap();
+    assert_eq!(value(&logs[0], "request_id"), text("none"));
+    assert_eq!(value(&logs[0], "user_id"), None);
+    assert_eq!(value(&logs[1], "request_id"), text("r-1"));
+    assert_eq!(value(&logs[1], "user_id"), text("u-1"));
+    assert_eq!(value(&logs[2], "request_id"), text("own"));
+    assert_eq!(value(&logs[2], "user_id"), text("u-2"));
+    assert_eq!(value(&logs[2], "logger_name"), text("misc"));
+}
+
+#[test]
+fn test_thread_context() {
+    let handler = Arc::new(TestHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+    let child = logger.with_context([("trace_id", "logger")]);
+
+    {
+        let _outer = push_context([("request_id", "r-1"), ("trace_id", "t-1")]);
+        logger.log(TestLog::default());
+        {
+            let _inner = push_context([("trace_id", "t-2")]);
+            logger.log(TestLog::default());
+            child.log(TestLog::default());
+            thread::scope(|scope| {
+                scope.spawn(|| logger.log(TestLog::default()));
+            });
+        }
+        logger.log(TestLog::default());
+    }
+    logger.log(TestLog::default());
+    factory.flush();
+
+    let logs = handler.logs.lock().unwrap();
+    let expected = [
+        (text("r-1"), text("t-1")),
+        (text("r-1"), text("t-2")),
+        (text("r-1"), text("logger")),
+        (None, None),
+        (text("r-1"), text("t-1")),
+        (None, None),
+    ];
+    let actual: Vec<_> = logs
+        .iter()
+        .map(|data| (value(data, "request_id"), value(data, "trace_id")))
+        .collect();
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, LazyLock, Mutex},
+    thread,
+    time::{Duration, Instant}};
+
+use raf_structural_logging::{
+    core::{CoreLoggerFactory, CoreLoggerFactoryBuilder, DispatchMode},
+    models::LogDataHolder,
+    template::{render_message, TemplateBuilder},
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct TestHandler {
+    is_blocked: AtomicBool,
+    messages: Mutex<Vec<String>>,
+    batches: Mutex<Vec<usize>>,
+    flushed: AtomicUsize,
+}
+
+impl TestHandler {
+    fn blocked() -> Self {
+        Self { is_blocked: AtomicBool::new(true), ..Default::default() }
+    }
+
+    fn unblock(&self) { self.is_blocked.store(false, Ordering::SeqCst); }
+
+    fn messages(&self) -> Vec<String> { self.messages.lock().unwrap().clone() }
+
+    fn batches(&self) -> Vec<usize> { self.batches.lock().unwrap().clone() }
+
+    fn flushed(&self) -> usize { self.flushed.load(Ordering::SeqCst) }
+
+    fn wait_blocked(&self) {
+        while self.is_blocked.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        self.wait_blocked();
+        self.messages.lock().unwrap().push(render_message(log));
+    }
+
+    fn handle_batch(&self, logs: &[LogDataHolder]) {
+        self.batches.lock().unwrap().push(logs.len());
+        self.wait_blocked();
+        for log in logs {
+            self.handle(log);
+        }
+    }
+
+    fn flush(&self) {
+        self.flushed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct TestLog(LogLevel, &'static str);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str(self.1), HashMap::new())
+    }
+}
+
+fn build_factory(dispatch_mode: DispatchMode, handlers: Vec<(Arc<TestHandler>, LogLevel)>) -> CoreLoggerFactory {
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(dispatch_mode);
+    for (handler, log_level) in handlers {
+        builder.add_handler_with_level(handler, log_level);
+    }
+    builder.build()
+}
+
+fn wait_for<F>(condition: F) -> bool
+    where F: Fn() -> bool
+{
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+#[test]
+fn test_per_handler_isolation() {
+    let slow = Arc::new(TestHandler::blocked());
+    let fast = Arc::new(TestHandler::default());
+    let factory = build_factory(
+        DispatchMode::PerHandler,
+        vec![(slow.clone(), LogLevel::Debug), (fast.clone(), LogLevel::Info)]);
+    let logger = factory.create_from_str("misc");
+
+    logger.log(TestLog(LogLevel::Debug, "a"));
+    logger.log(TestLog(LogLevel::Info, "b"));
+    logger.log(TestLog(LogLevel::Error, "c"));
+
+    assert!(wait_for(|| fast.messages().len() == 2));
+    assert_eq!(fast.messages(), vec!["b", "c"]);
+    assert!(slow.messages().is_empty());
+    assert!(!factory.flush_timeout(Duration::from_millis(20)));
+
+    slow.unblock();
+    factory.flush();
+    assert_eq!(slow.messages(), vec!["a", "b", "c"]);
+    assert_eq!(slow.flushed(), 1);
+    assert!(fast.flushed() >= 1);
+}
+
+#[test]
+fn test_per_handler_runtime_changes() {
+    let first = Arc::new(TestHandler::default());
+    let second = Arc::new(TestHandler::default());
+    let factory = build_factory(DispatchMode::PerHandler, vec![(first.clone(), LogLevel::Debug)]);
+    let handle = factory.config_handle();
+    let logger = factory.create_from_str("misc");
+
+    logger.log(TestLog(LogLevel::Info, "a"));
+    let id = handle.add_handler(second.clone());
+    logger.log(TestLog(LogLevel::Info, "b"));
+    factory.flush();
+    assert!(handle.remove_handler(handle.handler_ids()[0]));
+    assert!(handle.set_handler_level(id, LogLevel::Warning));
+    logger.log(TestLog(LogLevel::Info, "skipped"));
+    logger.log(TestLog(LogLevel::Warning, "c"));
+    factory.shutdown();
+    logger.log(TestLog(LogLevel::Info, "d"));
+
+    assert_eq!(first.messages(), vec!["a", "b"]);
+    assert_eq!(second.messages(), vec!["b", "c"]);
+    assert_eq!(first.flushed(), 1);
+    assert_eq!(second.flushed(), 2);
+    assert_eq!(handle.handler_ids(), vec![id]);
+}
+
+#[test]
+fn test_batched() {
+    let all = Arc::new(TestHandler::blocked());
+    let errors = Arc::new(TestHandler::default());
+    let factory = build_factory(
+        DispatchMode::Batched(4),
+        vec![(all.clone(), LogLevel::Debug), (errors.clone(), LogLevel::Error)]);
+    let logger = factory.create_from_str("misc");
+
+    logger.log(TestLog(LogLevel::Info, "0"));
+    assert!(wait_for(|| all.batches().len() == 1));
+    logger.log(TestLog(LogLevel::Info, "1"));
+    logger.log(TestLog(LogLevel::Error, "2"));
+    logger.log(TestLog(LogLevel::Error, "3"));
+    logger.log(TestLog(LogLevel::Info, "4"));
+    logger.log(TestLog(LogLevel::Error, "5"));
+    all.unblock();
+    factory.flush();
+
+    assert_eq!(all.messages(), vec!["0", "1", "2", "3", "4", "5"]);
+    assert_eq!(all.batches(), vec![1, 4, 1]);
+    assert_eq!(errors.messages(), vec!["2", "3", "5"]);
+    assert_eq!(errors.batches(), vec![2, 1]);
+}
+
+#[test]
+fn test_batched_default_handle_batch() {
+    struct CountingHandler(AtomicUsize);
+
+    impl StructuralLogHandler for CountingHandler {
+        fn handle(&self, _log: &LogDataHolder) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let handler = Arc::new(CountingHandler(AtomicUsize::new(0)));
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(DispatchMode::Batched(0));
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+    for _ in 0..50 {
+        logger.log(TestLog(LogLevel::Info, "x"));
+    }
+    factory.flush();
+    assert_eq!(handler.0.load(Ordering::SeqCst), 50);
+}
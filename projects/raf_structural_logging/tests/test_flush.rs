@@ -0,0 +1,162 @@
+use std::{
+    collections::HashMap,
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, LazyLock},
+    thread,
+    time::Duration};
+
+use raf_structural_logging::{
+    core::CoreLoggerFactoryBuilder,
+    models::LogDataHolder,
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct SlowHandler {
+    is_blocked: AtomicBool,
+    should_panic: bool,
+    handled: AtomicUsize,
+    flushed: AtomicUsize,
+}
+
+impl SlowHandler {
+    fn blocked() -> Self {
+        Self { is_blocked: AtomicBool::new(true), ..Default::default() }
+    }
+
+    fn panicking() -> Self {
+        Self { should_panic: true, ..Default::default() }
+    }
+
+    fn handled(&self) -> usize { self.handled.load(Ordering::SeqCst) }
+
+    fn flushed(&self) -> usize { self.flushed.load(Ordering::SeqCst) }
+}
+
+impl StructuralLogHandler for SlowHandler {
+    fn handle(&self, _log: &LogDataHolder) {
+        while self.is_blocked.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        thread::sleep(Duration::from_millis(2));
+        let handled = self.handled.fetch_add(1, Ordering::SeqCst);
+        assert!(!self.should_panic || handled % 2 == 1, "Handler failure");
+    }
+
+    fn flush(&self) {
+        self.flushed.fetch_add(1, Ordering::SeqCst);
+        assert!(!self.should_panic, "Flush failure");
+    }
+}
+
+struct TestLog;
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(LogLevel::Info, TMPL_BUILDER.create_from_str("xyz"), HashMap::new())
+    }
+}
+
+#[test]
+fn test_flush() {
+    let handler = Arc::new(SlowHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+
+    factory.flush();
+    assert_eq!(handler.flushed(), 1);
+
+    for _ in 0..20 {
+        logger.log(TestLog);
+    }
+    factory.flush();
+    assert_eq!(handler.handled(), 20);
+    assert_eq!(handler.flushed(), 2);
+
+    logger.log(TestLog);
+    assert!(factory.flush_timeout(Duration::from_secs(10)));
+    assert_eq!(handler.handled(), 21);
+    assert_eq!(handler.flushed(), 3);
+}
+
+#[test]
+fn test_flush_timeout() {
+    let handler = Arc::new(SlowHandler::blocked());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+
+    logger.log(TestLog);
+    logger.log(TestLog);
+    assert!(!factory.flush_timeout(Duration::from_millis(20)));
+    assert_eq!(handler.flushed(), 0);
+
+    handler.is_blocked.store(false, Ordering::SeqCst);
+    assert!(factory.flush_timeout(Duration::from_secs(10)));
+    assert_eq!(handler.handled(), 2);
+    assert_eq!(handler.flushed(), 1);
+}
+
+#[test]
+fn test_shutdown() {
+    let handler = Arc::new(SlowHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    let logger = factory.create_from_str("misc");
+
+    for _ in 0..10 {
+        logger.log(TestLog);
+    }
+    factory.shutdown();
+    assert_eq!(handler.handled(), 10);
+    assert_eq!(handler.flushed(), 1);
+
+    logger.log(TestLog);
+    factory.shutdown();
+    assert!(factory.flush_timeout(Duration::from_secs(10)));
+    drop(logger);
+    drop(factory);
+    assert_eq!(handler.handled(), 10);
+    assert_eq!(handler.flushed(), 2);
+}
+
+#[test]
+fn test_panicking_handler() {
+    let faulty = Arc::new(SlowHandler::panicking());
+    let handler = Arc::new(SlowHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.add_handler(faulty.clone());
+        builder.add_handler(handler.clone());
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+
+        for _ in 0..6 {
+            logger.log(TestLog);
+        }
+        factory.flush();
+        assert_eq!(faulty.handled(), 6);
+        assert_eq!(handler.handled(), 6);
+        assert_eq!(handler.flushed(), 1);
+
+        logger.log(TestLog);
+    }
+
+    assert_eq!(faulty.handled(), 7);
+    assert_eq!(handler.handled(), 7);
+    assert_eq!(faulty.flushed(), 2);
+    assert_eq!(handler.flushed(), 2);
+}
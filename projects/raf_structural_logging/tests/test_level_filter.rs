@@ -0,0 +1,138 @@
+use std::{collections::HashMap, sync::{Arc, LazyLock, Mutex}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactoryBuilder, LevelFilter, ParseLevelFilterError},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct TestHandler {
+    logs: Mutex<Vec<(String, LogLevel)>>,
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        let key = ImmutableString::new("logger_name").unwrap();
+        let SLObject::String(name) = &log.additional_data()[&key] else {
+            panic!("Invalid logger_name");
+        };
+        let mut guard = self.logs.lock().unwrap();
+        guard.push((name.value().as_str().to_owned(), log.log_level()));
+    }
+}
+
+struct TestLog(LogLevel);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str("xyz"), HashMap::new())
+    }
+}
+
+fn filter(directives: &str) -> LevelFilter {
+    let mut filter = LevelFilter::new(LogLevel::Info);
+    filter.apply_directives(directives).unwrap();
+    filter
+}
+
+#[rstest]
+#[case("", "db", LogLevel::Info)]
+#[case("error", "db", LogLevel::Error)]
+#[case("db=debug", "db", LogLevel::Debug)]
+#[case("db=debug", "db.pool", LogLevel::Debug)]
+#[case("db=debug", "dbx", LogLevel::Info)]
+#[case("db=debug", "x.db", LogLevel::Info)]
+#[case("db=debug,db.pool=error", "db.pool.conn", LogLevel::Error)]
+#[case("db.pool=error,db=debug", "db.pool.conn", LogLevel::Error)]
+#[case("db.pool=error,db=debug", "db.cache", LogLevel::Debug)]
+#[case(" http = WARN , ,warning", "http.server", LogLevel::Warning)]
+#[case("http=Warning,http=error", "http", LogLevel::Error)]
+fn test_level_for(#[case] directives: &str, #[case] name: &str, #[case] expected: LogLevel) {
+    assert_eq!(filter(directives).level_for(name), expected);
+}
+
+#[rstest]
+#[case("verbose", ParseLevelFilterError::InvalidLogLevel("verbose".to_owned()))]
+#[case("db=", ParseLevelFilterError::InvalidLogLevel(String::new()))]
+#[case("=debug", ParseLevelFilterError::InvalidDirective("=debug".to_owned()))]
+#[case("db=debug=info", ParseLevelFilterError::InvalidDirective("db=debug=info".to_owned()))]
+fn test_invalid_directives(#[case] directives: &str, #[case] expected: ParseLevelFilterError) {
+    let mut filter = LevelFilter::new(LogLevel::Info);
+    filter.add_rule("db", LogLevel::Error);
+    let result = filter.apply_directives(&format!("db=debug,{directives}"));
+    assert_eq!(result, Err(expected));
+    assert_eq!(filter.level_for("db"), LogLevel::Error);
+}
+
+#[test]
+fn test_apply_env() {
+    let mut filter = LevelFilter::new(LogLevel::Info);
+    filter.apply_env("RAF_STRUCTURAL_LOGGING_TEST_MISSING_VAR").unwrap();
+    assert_eq!(filter.level_for("db"), LogLevel::Info);
+
+    std::env::set_var("RAF_STRUCTURAL_LOGGING_TEST_LEVEL_VAR", "warning,db=debug");
+    filter.apply_env("RAF_STRUCTURAL_LOGGING_TEST_LEVEL_VAR").unwrap();
+    assert_eq!(filter.level_for("db.pool"), LogLevel::Debug);
+    assert_eq!(filter.level_for("http"), LogLevel::Warning);
+}
+
+#[test]
+fn test_logger_and_handler_levels() {
+    let all = Arc::new(TestHandler::default());
+    let errors = Arc::new(TestHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_log_level(LogLevel::Warning);
+        builder.apply_level_directives("db=debug,db.pool=info").unwrap();
+        builder.add_handler(all.clone());
+        builder.add_handler_with_level(errors.clone(), LogLevel::Error);
+        let factory = builder.build();
+
+        let db = factory.create_from_str("db");
+        let pool = factory.create_from_str("db.pool");
+        let http = factory.create_from_str("http");
+        assert!(db.is_enabled(LogLevel::Debug));
+        assert!(!pool.is_enabled(LogLevel::Debug));
+        assert!(!http.is_enabled(LogLevel::Info));
+
+        for level in [LogLevel::Debug, LogLevel::Info, LogLevel::Warning, LogLevel::Error] {
+            db.log(TestLog(level));
+            pool.log(TestLog(level));
+            http.log(TestLog(level));
+        }
+    }
+
+    let all = all.logs.lock().unwrap().clone();
+    let expected = [
+        ("db", LogLevel::Debug),
+        ("db", LogLevel::Info),
+        ("db.pool", LogLevel::Info),
+        ("db", LogLevel::Warning),
+        ("db.pool", LogLevel::Warning),
+        ("http", LogLevel::Warning),
+        ("db", LogLevel::Error),
+        ("db.pool", LogLevel::Error),
+        ("http", LogLevel::Error),
+    ];
+    let expected: Vec<(String, LogLevel)> = expected
+        .into_iter()
+        .map(|(name, level)| (name.to_owned(), level))
+        .collect();
+    assert_eq!(all, expected);
+
+    let errors = errors.logs.lock().unwrap().clone();
+    assert_eq!(errors, expected[6..].to_vec());
+}
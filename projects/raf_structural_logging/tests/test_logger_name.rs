@@ -0,0 +1,114 @@
+use std::{collections::HashMap, sync::{Arc, LazyLock}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactory, CoreLoggerFactoryBuilder, DispatchMode},
+    models::{LogDataHolder, SLObject},
+    template::{get_logger_name_key, TemplateBuilder},
+    testing::CapturingHandler,
+    traits::{
+        type_logger_name,
+        LogLevel,
+        StructuralLog,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+struct TestLog(LogLevel, &'static str);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str(self.1), HashMap::new())
+    }
+}
+
+mod db {
+    pub struct Pool<T>(pub T);
+}
+
+fn capturing_factory<F>(configure: F) -> (CoreLoggerFactory, Arc<CapturingHandler>)
+    where F: FnOnce(&mut CoreLoggerFactoryBuilder)
+{
+    let handler = Arc::new(CapturingHandler::new());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(DispatchMode::Synchronous);
+    builder.add_handler(handler.clone());
+    configure(&mut builder);
+    (builder.build(), handler)
+}
+
+#[rstest]
+#[case(type_logger_name::<db::Pool<u32>>(), "test_logger_name.db.Pool")]
+#[case(type_logger_name::<LogLevel>(), "raf_structural_logging.traits.LogLevel")]
+#[case(type_logger_name::<str>(), "str")]
+fn test_type_logger_name(#[case] name: String, #[case] expected: &str) {
+    assert_eq!(name, expected);
+}
+
+#[test]
+fn test_create_for() {
+    let (factory, handler) = capturing_factory(|builder| {
+        builder.set_log_level(LogLevel::Debug);
+        builder.add_level_rule("test_logger_name.db", LogLevel::Warning);
+    });
+    let pool_logger = factory.create_for::<db::Pool<String>>();
+    let level_logger = factory.create_for::<LogLevel>();
+    pool_logger.log(TestLog(LogLevel::Info, "skipped"));
+    pool_logger.log(TestLog(LogLevel::Warning, "pool"));
+    level_logger.log(TestLog(LogLevel::Info, "level"));
+
+    handler.query().logger("test_logger_name.db.Pool").template("pool").assert_count(1);
+    handler.query().logger("raf_structural_logging.traits.LogLevel").assert_count(1);
+    assert_eq!(handler.messages(), vec!["pool", "level"]);
+}
+
+#[test]
+fn test_child() {
+    let (factory, handler) = capturing_factory(|builder| {
+        builder.set_log_level(LogLevel::Debug);
+        builder.add_level_rule("db", LogLevel::Warning);
+        builder.add_level_rule("db.pool.idle", LogLevel::Debug);
+    });
+    let db = factory.create_from_str("db").with_context([("shard", 3i64)]);
+    let pool = db.child("pool");
+    let idle = pool.child("idle");
+    assert_eq!(pool.name().as_str(), "db.pool");
+    assert_eq!(idle.name().as_str(), "db.pool.idle");
+    assert_eq!(db.child("").name().as_str(), "db");
+    assert_eq!(factory.create_from_str("").child("db").name().as_str(), "db");
+    assert_eq!(pool.log_level(), LogLevel::Warning);
+    assert_eq!(idle.log_level(), LogLevel::Debug);
+
+    pool.log(TestLog(LogLevel::Info, "skipped"));
+    pool.log(TestLog(LogLevel::Error, "pool"));
+    idle.log(TestLog(LogLevel::Debug, "idle"));
+    let log = handler.query().logger("db.pool").assert_single();
+    assert_eq!(log.message(), "pool");
+    assert_eq!(log.param("shard"), Some(&SLObject::from(3i64)));
+    handler.query().logger("db.pool.idle").param("shard", 3i64).assert_count(1);
+}
+
+#[test]
+fn test_custom_logger_name_key() {
+    let key = ImmutableString::new("logger").unwrap();
+    let handler = Arc::new(CapturingHandler::new());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(DispatchMode::Synchronous);
+    builder.set_logger_name_key("logger");
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    assert_eq!(factory.logger_name_key(), &key);
+
+    factory.create_from_str("db").child("pool").log(TestLog(LogLevel::Info, "Connected"));
+    let log = handler.query().logger("db.pool").assert_single();
+    assert_eq!(log.additional_data().get(&key), Some(&SLObject::from("db.pool")));
+    assert!(!log.additional_data().contains_key(get_logger_name_key()));
+    assert_eq!(log.to_string(), "Info db.pool: Connected");
+
+    factory.create_from_str("db").log(TestLog(LogLevel::Info, "Hello from {logger_name}"));
+    assert_eq!(handler.query().logger("db").assert_single().message(), "Hello from db");
+}
@@ -0,0 +1,181 @@
+use std::{
+    backtrace::Backtrace,
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    hash::{DefaultHasher, Hash, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    thread,
+    time::{Duration, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{created_at_key, log_level_key, LogDataHolder, SLError, SLObject, SLUuid},
+    template::TemplateBuilder,
+    traits::LogLevel};
+use rstest::rstest;
+
+fn hash_of(obj: &SLObject) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    obj.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[rstest]
+#[case(f64::NAN, -f64::NAN, true)]
+#[case(f64::NAN, f64::from_bits(f64::NAN.to_bits() | 1), true)]
+#[case(0.0, -0.0, true)]
+#[case(1.5, 1.5, true)]
+#[case(f64::INFINITY, f64::INFINITY, true)]
+#[case(f64::INFINITY, f64::NEG_INFINITY, false)]
+#[case(1.5, 2.5, false)]
+#[case(f64::NAN, 0.0, false)]
+fn test_float_eq_and_hash(#[case] left: f64, #[case] right: f64, #[case] expected: bool) {
+    let left = SLObject::from(left);
+    let right = SLObject::from(right);
+    assert_eq!(left == right, expected);
+    if expected {
+        assert_eq!(hash_of(&left), hash_of(&right));
+    }
+}
+
+#[test]
+fn test_from_impls() {
+    assert!(matches!(SLObject::from(1.5f32), SLObject::Float(inner) if inner.value() == 1.5));
+    assert!(matches!(SLObject::from(u64::MAX), SLObject::UInt64(inner) if inner.value() == u64::MAX));
+    assert!(matches!(SLObject::from(u128::MAX), SLObject::UInt128(inner) if *inner.value() == u128::MAX));
+    assert_eq!(SLObject::from(vec![1u8, 2]), SLObject::from([1u8, 2].as_slice()));
+    assert_ne!(SLObject::from(vec![1u8, 2]), SLObject::from(vec![SLObject::from(1i64), SLObject::from(2i64)]));
+
+    let ip = Ipv4Addr::new(127, 0, 0, 1);
+    assert_eq!(SLObject::from(ip), SLObject::from(IpAddr::V4(ip)));
+    let SLObject::IpAddr(inner) = SLObject::from(Ipv6Addr::LOCALHOST) else {
+        panic!("Expected ip address.");
+    };
+    assert_eq!(inner.value().to_string(), "::1");
+}
+
+#[test]
+fn test_uuid() {
+    let bytes = [
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f,
+        0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8];
+    let uuid = SLUuid::new(bytes);
+    assert_eq!(uuid.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(SLObject::from(uuid), SLObject::Uuid(uuid));
+}
+
+#[cfg(feature="uuid")]
+#[test]
+fn test_uuid_feature() {
+    let uuid = uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+    let SLObject::Uuid(inner) = SLObject::from(uuid) else {
+        panic!("Expected uuid.");
+    };
+    assert_eq!(inner.to_string(), uuid.hyphenated().to_string());
+}
+
+#[derive(Debug)]
+struct ChainError {
+    message: &'static str,
+    source: Option<Box<ChainError>>,
+}
+
+impl Display for ChainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl Error for ChainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+fn chain(messages: &[&'static str]) -> ChainError {
+    let mut result: Option<ChainError> = None;
+    for message in messages.iter().rev() {
+        result = Some(ChainError {
+            message,
+            source: result.map(Box::new),
+        });
+    }
+    result.unwrap()
+}
+
+#[rstest]
+#[case(&["top"])]
+#[case(&["top", "middle"])]
+#[case(&["top", "middle", "bottom"])]
+fn test_error_chain(#[case] messages: &[&'static str]) {
+    let error = chain(messages);
+    let sl_error = SLError::from_error(&error);
+    assert_eq!(sl_error.message().as_str(), messages[0]);
+    let sources: Vec<&str> = sl_error.sources().iter().map(|source| source.as_str()).collect();
+    assert_eq!(sources, &messages[1..]);
+    assert!(sl_error.backtrace().is_none());
+
+    let error_ref: &(dyn Error + 'static) = &error;
+    assert_eq!(SLObject::from(error_ref), SLObject::from(sl_error));
+}
+
+#[test]
+fn test_error_backtrace() {
+    let error = chain(&["top", "bottom"]);
+    let sl_error = SLError::with_backtrace(&error, &Backtrace::disabled());
+    assert!(sl_error.backtrace().is_none());
+
+    let sl_error = SLError::with_backtrace(&error, &Backtrace::force_capture());
+    assert!(sl_error.backtrace().is_some());
+    assert_eq!(sl_error.sources().len(), 1);
+}
+
+fn imm(text: &str) -> ImmutableString { ImmutableString::new(text).unwrap() }
+
+#[test]
+fn test_log_data_holder_get() {
+    let created_at = UNIX_EPOCH + Duration::from_secs(5);
+    let mut params = HashMap::new();
+    params.insert(imm("user"), SLObject::from("alice"));
+    let mut log = LogDataHolder::new_with_created_at(
+        LogLevel::Warning,
+        TemplateBuilder::global().create_from_str("Hello {user}"),
+        params,
+        created_at);
+    log.update_data(imm("user"), "bob");
+    log.update_data(imm("request_id"), 7i64);
+    log.update_data(created_at_key().clone(), UNIX_EPOCH);
+    log.update_data(log_level_key().clone(), LogLevel::Debug);
+
+    assert_eq!(log.get(&imm("user")), Some(&SLObject::from("alice")));
+    assert_eq!(log.get(&imm("request_id")), Some(&SLObject::from(7i64)));
+    assert_eq!(log.get(created_at_key()), Some(&SLObject::from(created_at)));
+    assert_eq!(log.get(log_level_key()), Some(&SLObject::from(LogLevel::Warning)));
+    assert_eq!(log.get(&imm("missing")), None);
+    assert_eq!(log.additional_data().len(), 2);
+    assert!(!log.additional_data().contains_key(created_at_key()));
+}
+
+#[test]
+fn test_log_data_holder_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() { }
+    assert_send_sync::<LogDataHolder>();
+
+    let mut log = LogDataHolder::new(
+        LogLevel::Info,
+        TemplateBuilder::global().create_from_str("At {created_at} from {host}"),
+        HashMap::new());
+    log.update_data(imm("host"), "db1");
+    let copy = log.clone();
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                assert_eq!(log.get(&imm("host")), Some(&SLObject::from("db1")));
+                assert_eq!(log.get(created_at_key()), Some(&SLObject::from(log.created_at())));
+            });
+        }
+    });
+    assert_eq!(copy.additional_data(), log.additional_data());
+    assert_eq!(copy.created_at(), log.created_at());
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerConfigHandle, CoreLoggerFactoryBuilder, DispatchMode, LevelFilter},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct TestHandler {
+    logs: Mutex<Vec<(String, LogLevel)>>,
+}
+
+impl TestHandler {
+    fn logs(&self) -> Vec<(String, LogLevel)> {
+        self.logs.lock().unwrap().clone()
+    }
+
+    /// Logs are dispatched in background, wait until they reach handler.
+    fn wait_for(&self, count: usize) {
+        let start = Instant::now();
+        while self.logs.lock().unwrap().len() < count {
+            assert!(start.elapsed() < Duration::from_secs(10), "Timeout on wait_for");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        let key = ImmutableString::new("logger_name").unwrap();
+        let SLObject::String(name) = &log.additional_data()[&key] else {
+            panic!("Invalid logger_name");
+        };
+        let mut guard = self.logs.lock().unwrap();
+        guard.push((name.value().as_str().to_owned(), log.log_level()));
+    }
+}
+
+struct TestLog(LogLevel);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str("xyz"), HashMap::new())
+    }
+}
+
+fn entry(name: &str, log_level: LogLevel) -> (String, LogLevel) {
+    (name.to_owned(), log_level)
+}
+
+#[test]
+fn test_change_levels() {
+    let handler = Arc::new(TestHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_log_level(LogLevel::Warning);
+        builder.add_handler(handler.clone());
+        let factory = builder.build();
+        let handle = factory.config_handle();
+        let db = factory.create_from_str("db.pool");
+        let http = factory.create_from_str("http");
+
+        db.log(TestLog(LogLevel::Debug));
+        http.log(TestLog(LogLevel::Info));
+
+        handle.add_level_rule("db", LogLevel::Debug);
+        assert_eq!(db.log_level(), LogLevel::Debug);
+        assert_eq!(http.log_level(), LogLevel::Warning);
+        db.log(TestLog(LogLevel::Debug));
+        http.log(TestLog(LogLevel::Info));
+
+        handle.set_log_level(LogLevel::Info);
+        http.log(TestLog(LogLevel::Info));
+
+        let mut level_filter = LevelFilter::new(LogLevel::Error);
+        level_filter.add_rule("http", LogLevel::Debug);
+        handle.set_level_filter(level_filter);
+        db.log(TestLog(LogLevel::Warning));
+        http.log(TestLog(LogLevel::Debug));
+
+        assert!(handle.apply_level_directives("db=info,invalid=xyz").is_err());
+        assert_eq!(db.log_level(), LogLevel::Error);
+        handle.apply_level_directives("db=info").unwrap();
+        db.log(TestLog(LogLevel::Info));
+        assert_eq!(handle.level_filter().level_for("db"), LogLevel::Info);
+
+        let late = factory.create_from_str("late");
+        assert_eq!(late.log_level(), LogLevel::Error);
+    }
+
+    let expected = vec![
+        entry("db.pool", LogLevel::Debug),
+        entry("http", LogLevel::Info),
+        entry("http", LogLevel::Debug),
+        entry("db.pool", LogLevel::Info),
+    ];
+    assert_eq!(handler.logs(), expected);
+}
+
+#[test]
+fn test_concurrent_level_directives() {
+    let factory = CoreLoggerFactoryBuilder::default().build();
+    let handle = factory.config_handle();
+    let threads: Vec<_> = (0..8)
+        .map(|idx| {
+            let handle = handle.clone();
+            thread::spawn(move || {
+                for round in 0..50 {
+                    handle.apply_level_directives(&format!("t{idx}.r{round}=error")).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let level_filter = handle.level_filter();
+    for idx in 0..8 {
+        for round in 0..50 {
+            assert_eq!(level_filter.level_for(&format!("t{idx}.r{round}")), LogLevel::Error);
+        }
+    }
+}
+
+#[test]
+fn test_change_handlers() {
+    let first = Arc::new(TestHandler::default());
+    let second = Arc::new(TestHandler::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.add_handler(first.clone());
+        let factory = builder.build();
+        let handle = factory.config_handle();
+        let logger = factory.create_from_str("misc");
+        let first_id = handle.handler_ids()[0];
+
+        logger.log(TestLog(LogLevel::Info));
+        first.wait_for(1);
+        let second_id = handle.add_handler_with_level(second.clone(), LogLevel::Warning);
+        assert_eq!(handle.handler_ids(), vec![first_id, second_id]);
+
+        logger.log(TestLog(LogLevel::Info));
+        logger.log(TestLog(LogLevel::Warning));
+        first.wait_for(3);
+        second.wait_for(1);
+
+        assert!(handle.set_handler_level(second_id, LogLevel::Debug));
+        assert!(handle.remove_handler(first_id));
+        assert!(!handle.remove_handler(first_id));
+        assert!(!handle.set_handler_level(first_id, LogLevel::Debug));
+        assert_eq!(handle.handler_ids(), vec![second_id]);
+
+        logger.log(TestLog(LogLevel::Debug));
+    }
+
+    let expected = vec![
+        entry("misc", LogLevel::Info),
+        entry("misc", LogLevel::Info),
+        entry("misc", LogLevel::Warning),
+    ];
+    assert_eq!(first.logs(), expected);
+
+    let expected = vec![
+        entry("misc", LogLevel::Warning),
+        entry("misc", LogLevel::Debug),
+    ];
+    assert_eq!(second.logs(), expected);
+}
+
+/// Handler that replaces itself with `replacement` on the first log.
+struct ReplacingHandler {
+    handle: OnceLock<CoreLoggerConfigHandle>,
+    replacement: Arc<TestHandler>,
+}
+
+impl StructuralLogHandler for ReplacingHandler {
+    fn handle(&self, _log: &LogDataHolder) {
+        let handle = self.handle.get().unwrap();
+        let ids = handle.handler_ids();
+        if ids.len() == 1 {
+            handle.add_handler(self.replacement.clone());
+            assert!(handle.remove_handler(ids[0]));
+        }
+    }
+}
+
+#[rstest]
+#[case(DispatchMode::Background)]
+#[case(DispatchMode::Synchronous)]
+#[case(DispatchMode::PerHandler)]
+fn test_change_handlers_from_handler(#[case] dispatch_mode: DispatchMode) {
+    let replacement = Arc::new(TestHandler::default());
+    let replacing = Arc::new(ReplacingHandler {
+        handle: OnceLock::new(),
+        replacement: replacement.clone(),
+    });
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(dispatch_mode);
+    builder.add_handler(replacing.clone());
+    let factory = builder.build();
+    replacing.handle.set(factory.config_handle()).ok().unwrap();
+    let logger = factory.create_from_str("misc");
+
+    logger.log(TestLog(LogLevel::Info));
+    factory.flush();
+    logger.log(TestLog(LogLevel::Warning));
+    replacement.wait_for(1);
+    assert_eq!(replacement.logs(), vec![entry("misc", LogLevel::Warning)]);
+}
@@ -0,0 +1,154 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+    thread,
+    time::Duration};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactory, CoreLoggerFactoryBuilder},
+    models::{LogDataHolder, SLObject},
+    template::{render_message, TemplateBuilder},
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+#[derive(Default)]
+struct TestHandler {
+    messages: Mutex<Vec<(LogLevel, String)>>,
+}
+
+impl TestHandler {
+    fn messages(&self) -> Vec<(LogLevel, String)> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+impl StructuralLogHandler for TestHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        self.messages.lock().unwrap().push((log.log_level(), render_message(log)));
+    }
+}
+
+struct TestLog(LogLevel, &'static str, i64);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let mut params = HashMap::new();
+        params.insert(ImmutableString::new("value").unwrap(), SLObject::from(self.2));
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str(self.1), params)
+    }
+}
+
+fn build_factory<F>(configure: F) -> (CoreLoggerFactory, Arc<TestHandler>)
+    where F: FnOnce(&mut CoreLoggerFactoryBuilder)
+{
+    let handler = Arc::new(TestHandler::default());
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.add_handler(handler.clone());
+    configure(&mut builder);
+    (builder.build(), handler)
+}
+
+fn message(log_level: LogLevel, text: &str) -> (LogLevel, String) {
+    (log_level, text.to_owned())
+}
+
+#[test]
+fn test_sampling_rate() {
+    let (factory, handler) = build_factory(|builder| {
+        builder.set_sampling_rate(LogLevel::Debug, 0.0);
+        builder.set_sampling_rate(LogLevel::Info, 0.5);
+        builder.set_sampling_rate(LogLevel::Warning, f64::NAN);
+        builder.set_sampling_rate(LogLevel::Error, 7.0);
+    });
+    let logger = factory.create_from_str("hot");
+    for idx in 0..1000 {
+        logger.log(TestLog(LogLevel::Debug, "Debug {value}", idx));
+        logger.log(TestLog(LogLevel::Info, "Info {value}", idx));
+        logger.log(TestLog(LogLevel::Warning, "Warning {value}", idx));
+        logger.log(TestLog(LogLevel::Error, "Error {value}", idx));
+    }
+    factory.flush();
+
+    let messages = handler.messages();
+    let count = |log_level: LogLevel| messages.iter().filter(|(level, _)| *level == log_level).count();
+    assert_eq!(count(LogLevel::Debug), 0);
+    assert!((350..650).contains(&count(LogLevel::Info)), "{}", count(LogLevel::Info));
+    assert_eq!(count(LogLevel::Warning), 0);
+    assert_eq!(count(LogLevel::Error), 1000);
+    assert_eq!(factory.sampled_out_logs(), 4000 - messages.len() as u64);
+}
+
+#[test]
+fn test_rate_limit() {
+    let (factory, handler) = build_factory(|builder| {
+        builder.set_rate_limit(2, Duration::from_millis(200));
+    });
+    let logger = factory.create_from_str("hot");
+    for idx in 0..5 {
+        logger.log(TestLog(LogLevel::Info, "A {value}", idx));
+        logger.log(TestLog(LogLevel::Info, "B {value}", idx));
+    }
+    thread::sleep(Duration::from_millis(250));
+    logger.log(TestLog(LogLevel::Info, "A {value}", 10));
+    factory.flush();
+
+    let expected = vec![
+        message(LogLevel::Info, "A 0"),
+        message(LogLevel::Info, "B 0"),
+        message(LogLevel::Info, "A 1"),
+        message(LogLevel::Info, "B 1"),
+        message(LogLevel::Info, "A 10"),
+    ];
+    assert_eq!(handler.messages(), expected);
+    assert_eq!(factory.sampled_out_logs(), 6);
+}
+
+#[test]
+fn test_deduplication() {
+    let (factory, handler) = build_factory(|builder| {
+        builder.set_deduplication(true);
+    });
+    let logger = factory.create_from_str("hot");
+    for _ in 0..3 {
+        logger.log(TestLog(LogLevel::Info, "A {value}", 1));
+    }
+    logger.log(TestLog(LogLevel::Info, "A {value}", 2));
+    logger.log(TestLog(LogLevel::Warning, "A {value}", 2));
+    logger.log(TestLog(LogLevel::Warning, "A {value}", 2));
+    factory.flush();
+    logger.log(TestLog(LogLevel::Warning, "A {value}", 2));
+    factory.create_from_str("other").log(TestLog(LogLevel::Warning, "A {value}", 2));
+    factory.flush();
+
+    let expected = vec![
+        message(LogLevel::Info, "A 1"),
+        message(LogLevel::Info, "Previous message repeated 2 times"),
+        message(LogLevel::Info, "A 2"),
+        message(LogLevel::Warning, "A 2"),
+        message(LogLevel::Warning, "Previous message repeated 1 times"),
+        message(LogLevel::Warning, "Previous message repeated 1 times"),
+        message(LogLevel::Warning, "A 2"),
+    ];
+    assert_eq!(handler.messages(), expected);
+    assert_eq!(factory.sampled_out_logs(), 0);
+}
+
+#[test]
+fn test_deduplication_disabled() {
+    let (factory, handler) = build_factory(|_| { });
+    let logger = factory.create_from_str("hot");
+    for _ in 0..3 {
+        logger.log(TestLog(LogLevel::Info, "A {value}", 1));
+    }
+    factory.flush();
+    assert_eq!(handler.messages(), vec![message(LogLevel::Info, "A 1"); 3]);
+}
@@ -0,0 +1,162 @@
+#![cfg(feature="serde")]
+use std::collections::{BTreeMap, HashMap};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{SLError, SLObject, SLSerializeError},
+    traits::LogLevel};
+use rstest::rstest;
+use serde::Serialize;
+
+fn imm(text: &str) -> ImmutableString {
+    ImmutableString::new(text).unwrap()
+}
+
+fn dict(items: Vec<(&str, SLObject)>) -> SLObject {
+    let map: HashMap<ImmutableString, SLObject> = items
+        .into_iter()
+        .map(|(key, value)| (imm(key), value))
+        .collect();
+    SLObject::from(map)
+}
+
+#[derive(Serialize)]
+struct Address {
+    city: &'static str,
+    zip: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct User {
+    id: u64,
+    name: String,
+    score: f64,
+    tags: Vec<&'static str>,
+    address: Address,
+    active: bool,
+}
+
+#[derive(Serialize)]
+struct Meters(i32);
+
+#[derive(Serialize)]
+enum Event {
+    Started,
+    Moved(i64),
+    Resized(i64, i64),
+    Renamed { from: &'static str, to: &'static str },
+}
+
+#[test]
+fn test_struct() {
+    let user = User {
+        id: 7,
+        name: "john".to_owned(),
+        score: 2.5,
+        tags: vec!["a", "b"],
+        address: Address { city: "Paris", zip: None },
+        active: true,
+    };
+    let expected = dict(vec![
+        ("id", SLObject::from(7u64)),
+        ("name", SLObject::from("john")),
+        ("score", SLObject::from(2.5)),
+        ("tags", SLObject::from(vec![SLObject::from("a"), SLObject::from("b")])),
+        ("address", dict(vec![
+            ("city", SLObject::from("Paris")),
+            ("zip", SLObject::Empty),
+        ])),
+        ("active", SLObject::from(true)),
+    ]);
+    assert_eq!(SLObject::from_serialize(&user), Ok(expected));
+}
+
+#[rstest]
+#[case(Event::Started, SLObject::from("Started"))]
+#[case(Event::Moved(5), dict(vec![("Moved", SLObject::from(5i64))]))]
+#[case(
+    Event::Resized(1, 2),
+    dict(vec![("Resized", SLObject::from(vec![SLObject::from(1i64), SLObject::from(2i64)]))]))]
+#[case(
+    Event::Renamed { from: "a", to: "b" },
+    dict(vec![("Renamed", dict(vec![("from", SLObject::from("a")), ("to", SLObject::from("b"))]))]))]
+fn test_enum(#[case] event: Event, #[case] expected: SLObject) {
+    assert_eq!(SLObject::from_serialize(&event), Ok(expected));
+}
+
+#[rstest]
+#[case(SLObject::from_serialize(&Meters(-3)), SLObject::from(-3i64))]
+#[case(SLObject::from_serialize(&(1u8, 'x')), SLObject::from(vec![SLObject::from(1i64), SLObject::from("x")]))]
+#[case(SLObject::from_serialize(&()), SLObject::Empty)]
+#[case(SLObject::from_serialize(&Some(u128::MAX)), SLObject::from(u128::MAX))]
+#[case(SLObject::from_serialize(&(i128::from(i64::MAX) + 1)), SLObject::from(1u128 << 63))]
+#[case(SLObject::from_serialize(&Bytes(&[1, 2])), SLObject::from(vec![1u8, 2]))]
+fn test_primitives(#[case] result: Result<SLObject, SLSerializeError>, #[case] expected: SLObject) {
+    assert_eq!(result, Ok(expected));
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[test]
+fn test_map_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(1, "one");
+    map.insert(2, "two");
+    let expected = dict(vec![("1", SLObject::from("one")), ("2", SLObject::from("two"))]);
+    assert_eq!(SLObject::from_serialize(&map), Ok(expected));
+
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), "pair");
+    assert_eq!(SLObject::from_serialize(&map), Err(SLSerializeError::InvalidMapKey));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        SLObject::from_serialize(&i128::MIN),
+        Err(SLSerializeError::IntegerOutOfRange(i128::MIN)));
+}
+
+#[test]
+fn test_json_round_trip() {
+    let obj = dict(vec![
+        ("id", SLObject::from(-7i64)),
+        ("name", SLObject::from("john")),
+        ("score", SLObject::from(2.5)),
+        ("big", SLObject::from(u64::MAX)),
+        ("none", SLObject::Empty),
+        ("list", SLObject::from(vec![SLObject::from(true), dict(vec![])])),
+    ]);
+    let text = serde_json::to_string(&obj).unwrap();
+    let restored: SLObject = serde_json::from_str(&text).unwrap();
+    assert_eq!(restored, obj);
+}
+
+#[test]
+fn test_serialized_forms() {
+    let obj = dict(vec![
+        ("level", SLObject::from(LogLevel::Warning)),
+        ("ip", SLObject::from(std::net::Ipv4Addr::LOCALHOST)),
+        ("err", SLObject::from(SLError::new(imm("top"), vec![imm("bottom")], None))),
+    ]);
+    let value = serde_json::to_value(&obj).unwrap();
+    assert_eq!(value["level"], "Warning");
+    assert_eq!(value["ip"], "127.0.0.1");
+    assert_eq!(value["err"]["message"], "top");
+    assert_eq!(value["err"]["sources"][0], "bottom");
+    assert!(value["err"]["backtrace"].is_null());
+
+    let restored: SLObject = serde_json::from_value(value).unwrap();
+    let SLObject::Dict(restored) = restored else {
+        panic!("Expected dict.");
+    };
+    assert_eq!(restored.value().get(&imm("level")), Some(&SLObject::from("Warning")));
+}
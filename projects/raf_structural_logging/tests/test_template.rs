@@ -0,0 +1,245 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::{
+        parse_template,
+        parse_template_lenient,
+        render_message,
+        Alignment,
+        ParameterHint,
+        TemplateBuilder,
+        TemplateCacheStats,
+        TemplateParameter,
+        TemplateParseError,
+        TemplateParseErrorReason,
+        TemplatePiece},
+    traits::LogLevel};
+use rstest::rstest;
+
+fn imm(text: &str) -> ImmutableString {
+    ImmutableString::new(text).unwrap()
+}
+
+fn raw(text: &str) -> TemplatePiece {
+    TemplatePiece::RawString(imm(text))
+}
+
+fn param(name: &str, hint: ParameterHint, alignment: Option<Alignment>, format: Option<&str>) -> TemplatePiece {
+    TemplatePiece::Parameter(TemplateParameter::new(imm(name), hint, alignment, format.map(imm)))
+}
+
+fn plain(name: &str) -> TemplatePiece {
+    param(name, ParameterHint::Default, None, None)
+}
+
+#[rstest]
+#[case("", vec![])]
+#[case("abc", vec![raw("abc")])]
+#[case("a {{b}} c", vec![raw("a {b}} c")])]
+#[case("User {user} done", vec![raw("User "), plain("user"), raw(" done")])]
+#[case("{ user }{x}", vec![plain("user"), plain("x")])]
+#[case("{@user}", vec![param("user", ParameterHint::Destructure, None, None)])]
+#[case("{$user}", vec![param("user", ParameterHint::Stringify, None, None)])]
+#[case("{value:0.00}", vec![param("value", ParameterHint::Default, None, Some("0.00"))])]
+#[case("{value,8}", vec![param("value", ParameterHint::Default, Some(Alignment::Right(8)), None)])]
+#[case("{value, -8 :x}", vec![param("value", ParameterHint::Default, Some(Alignment::Left(8)), Some("x"))])]
+#[case("{when:%F %T}!", vec![param("when", ParameterHint::Default, None, Some("%F %T")), raw("!")])]
+fn test_parse(#[case] template: &str, #[case] expected: Vec<TemplatePiece>) {
+    assert_eq!(parse_template(template), Ok(expected));
+}
+
+#[rstest]
+#[case("abc {", 4, TemplateParseErrorReason::UnterminatedParameter)]
+#[case("abc {user", 4, TemplateParseErrorReason::UnterminatedParameter)]
+#[case("{user:0.00", 0, TemplateParseErrorReason::UnterminatedParameter)]
+#[case("x {}", 3, TemplateParseErrorReason::EmptyParameterName)]
+#[case("{@ }", 2, TemplateParseErrorReason::EmptyParameterName)]
+#[case("{a b}", 3, TemplateParseErrorReason::UnexpectedCharacter('b'))]
+#[case("{a{b}", 2, TemplateParseErrorReason::UnexpectedCharacter('{'))]
+#[case("{a,}", 3, TemplateParseErrorReason::InvalidAlignment)]
+#[case("{a,x}", 3, TemplateParseErrorReason::InvalidAlignment)]
+#[case("{a,5 5}", 5, TemplateParseErrorReason::UnexpectedCharacter('5'))]
+fn test_parse_errors(
+    #[case] template: &str,
+    #[case] position: usize,
+    #[case] reason: TemplateParseErrorReason)
+{
+    assert_eq!(parse_template(template), Err(TemplateParseError { position, reason }));
+}
+
+#[test]
+#[should_panic(expected = "Invalid template at position 0: unterminated parameter.")]
+fn test_builder_panics_on_invalid_template() {
+    let _ = TemplateBuilder::default().create_from_str("{abc");
+}
+
+#[test]
+fn test_builder_try_create() {
+    let builder = TemplateBuilder::default();
+    assert!(!builder.is_lenient());
+    assert_eq!(
+        builder.try_create_from_str("User {user").unwrap_err(),
+        TemplateParseError { position: 5, reason: TemplateParseErrorReason::UnterminatedParameter });
+    let template = builder.try_create_from_str("User {user}").unwrap();
+    assert_eq!(template.pieces(), &[raw("User "), plain("user")]);
+}
+
+#[rstest]
+#[case("{", vec![raw("{")])]
+#[case("a {b c} {d}", vec![raw("a {b c} "), plain("d")])]
+#[case("{} {{x}} {a,x}", vec![raw("{} {x}} {a,x}")])]
+#[case("{a{b}", vec![raw("{a"), plain("b")])]
+#[case("{a} {", vec![plain("a"), raw(" {")])]
+fn test_parse_lenient(#[case] template: &str, #[case] expected: Vec<TemplatePiece>) {
+    assert_eq!(parse_template_lenient(template), expected);
+}
+
+#[test]
+fn test_lenient_builder_renders_literally() {
+    let builder = TemplateBuilder::lenient();
+    assert!(builder.is_lenient());
+    let template = builder.try_create_from_str("Got {v} from {bad key} {").unwrap();
+    let mut params = HashMap::new();
+    params.insert(imm("v"), SLObject::from(5i64));
+    let log = LogDataHolder::new(LogLevel::Info, template, params);
+    assert_eq!(render_message(&log), "Got 5 from {bad key} {");
+}
+
+#[test]
+fn test_missing_parameters() {
+    let template = TemplateBuilder::default().create_from_str("{a} {b} {@a} {c,3} {b:x}");
+    let mut params = HashMap::new();
+    params.insert(imm("a"), SLObject::from(1i64));
+    assert_eq!(template.missing_parameters(&params), vec![&imm("b"), &imm("c")]);
+    let names: Vec<&str> = template.parameters().map(|param| param.name().as_str()).collect();
+    assert_eq!(names, vec!["a", "b", "a", "c", "b"]);
+
+    params.insert(imm("b"), SLObject::from(2i64));
+    params.insert(imm("c"), SLObject::from(3i64));
+    let log = LogDataHolder::new(LogLevel::Info, template, params);
+    assert!(log.missing_template_params().is_empty());
+}
+
+#[test]
+fn test_cache_stats() {
+    let builder = TemplateBuilder::default();
+    let first = builder.create_from_str("A {a}");
+    let second = builder.create_from_str("A {a}");
+    assert_eq!(first, second);
+    let _ = builder.try_create_from_str("B {b");
+    assert_eq!(builder.stats(), TemplateCacheStats::new(1, 2, 0, 1));
+
+    builder.clear();
+    assert_eq!(builder.stats(), TemplateCacheStats::new(1, 2, 0, 0));
+}
+
+#[test]
+fn test_cache_eviction() {
+    let builder = TemplateBuilder::new(2, false);
+    assert_eq!(builder.capacity(), 2);
+    let _ = builder.create_from_str("{a}");
+    let _ = builder.create_from_str("{b}");
+    let _ = builder.create_from_str("{a}");
+    let _ = builder.create_from_str("{c}");
+    let stats = builder.stats();
+    assert_eq!(*stats.len(), 2);
+    assert_eq!(stats.evictions(), 1);
+
+    let builder = TemplateBuilder::new(1, false);
+    let _ = builder.create_from_str("{a}");
+    let _ = builder.create_from_str("{b}");
+    let _ = builder.create_from_str("{b}");
+    let _ = builder.create_from_str("{a}");
+    assert_eq!(builder.stats(), TemplateCacheStats::new(1, 3, 2, 1));
+}
+
+#[test]
+fn test_cache_concurrent() {
+    let builder = TemplateBuilder::new(64, false);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for idx in 0..100 {
+                    let template = builder.create_from_str(&format!("Item {{item{idx}}}"));
+                    assert_eq!(template.pieces().len(), 2);
+                }
+            });
+        }
+    });
+    let stats = builder.stats();
+    assert!(*stats.len() <= 64);
+    assert_eq!(stats.hits() + stats.misses(), 400);
+}
+
+#[test]
+fn test_global_builder() {
+    let builder = TemplateBuilder::global();
+    assert!(std::ptr::eq(builder, TemplateBuilder::global()));
+    assert!(!builder.is_lenient());
+    let _ = builder.create_from_str("Global {value}");
+    let hits = builder.stats().hits();
+    let _ = builder.create_from_str("Global {value}");
+    assert!(builder.stats().hits() > hits);
+}
+
+fn render(template: &str, value: SLObject) -> String {
+    let mut params = HashMap::new();
+    params.insert(imm("v"), value);
+    let template = TemplateBuilder::default().create_from_str(template);
+    render_message(&LogDataHolder::new(LogLevel::Info, template, params))
+}
+
+fn dict(items: Vec<(&str, SLObject)>) -> SLObject {
+    let map: HashMap<ImmutableString, SLObject> = items
+        .into_iter()
+        .map(|(key, value)| (imm(key), value))
+        .collect();
+    SLObject::from(map)
+}
+
+#[rstest]
+#[case("[{v}]", SLObject::from(5i64), "[5]")]
+#[case("[{v,4}]", SLObject::from(5i64), "[   5]")]
+#[case("[{v,-4}]", SLObject::from("ab"), "[ab  ]")]
+#[case("[{v,1}]", SLObject::from("abc"), "[abc]")]
+#[case("[{v:000}]", SLObject::from(-5i64), "[-005]")]
+#[case("[{v:0.00}]", SLObject::from(5u64), "[5.00]")]
+#[case("[{v:0.00}]", SLObject::from(1.23456), "[1.23]")]
+#[case("[{v:00.0}]", SLObject::from(-2.25), "[-02.2]")]
+#[case("[{v:0.0}]", SLObject::from(-0.01), "[0.0]")]
+#[case("[{v:x}]", SLObject::from(255i64), "[ff]")]
+#[case("[{v:X}]", SLObject::from(u128::from(u64::MAX)), "[FFFFFFFFFFFFFFFF]")]
+#[case("[{v:e}]", SLObject::from(1500.0), "[1.5e3]")]
+#[case("[{v:x}]", SLObject::from(1.5), "[1.5]")]
+#[case("[{v:0.00}]", SLObject::from(f64::NAN), "[NaN]")]
+#[case("[{v:unknown}]", SLObject::from(5i64), "[5]")]
+#[case("[{v:0.00}]", SLObject::from("text"), "[text]")]
+#[case("[{v,8:0.0}]", SLObject::from(2.5), "[     2.5]")]
+#[case("[{v}]", SLObject::from(UNIX_EPOCH + Duration::from_millis(1_714_557_600_123)), "[2024-05-01T10:00:00.123Z]")]
+#[case("[{v}]", SLObject::from(UNIX_EPOCH - Duration::from_secs(1)), "[1969-12-31T23:59:59Z]")]
+#[case("[{v:%F %T.%3f}]", SLObject::from(UNIX_EPOCH + Duration::from_millis(951_825_661_005)), "[2000-02-29 12:01:01.005]")]
+#[case("[{v:%d/%m/%Y %H:%M:%S %% %q}]", SLObject::from(UNIX_EPOCH), "[01/01/1970 00:00:00 % %q]")]
+#[case("[{$v}]", SLObject::from(5i64), "[\"5\"]")]
+#[case("[{$v}]", SLObject::from("say \"hi\""), "[\"say \\\"hi\\\"\"]")]
+#[case("[{@v}]", SLObject::from("x"), "[\"x\"]")]
+#[case("[{@v}]", SLObject::from(7i64), "[7]")]
+#[case(
+    "[{@v}]",
+    dict(vec![("b", SLObject::from(vec![SLObject::from("x")])), ("a", SLObject::from(1i64))]),
+    "[{a: 1, b: [\"x\"]}]")]
+#[case("[{@v:0.00}]", SLObject::from(1i64), "[1]")]
+fn test_render(#[case] template: &str, #[case] value: SLObject, #[case] expected: &str) {
+    assert_eq!(render(template, value), expected);
+}
+
+#[test]
+fn test_render_missing_parameter() {
+    let template = TemplateBuilder::default().create_from_str("[{missing,3}] [{logger_name}]");
+    let mut log = LogDataHolder::new(LogLevel::Info, template, HashMap::new());
+    log.update_data(imm("logger_name"), "db");
+    assert_eq!(render_message(&log), "[   ] [db]");
+}
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, sync::LazyLock, thread};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    testing::CapturingHandler,
+    traits::{LogLevel, StructuralLog, StructuralLogger, StructuralLoggerFactory}};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+struct TestLog(LogLevel, &'static str, Vec<(&'static str, SLObject)>);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let params: HashMap<ImmutableString, SLObject> = self.2
+            .iter()
+            .map(|(key, value)| (ImmutableString::new(key).unwrap(), value.clone()))
+            .collect();
+        LogDataHolder::new(self.0, TMPL_BUILDER.create_from_str(self.1), params)
+    }
+}
+
+fn log_sample(factory: &impl StructuralLoggerFactory) {
+    let db = factory.create_from_str("db");
+    let http = factory.create_from_str("http");
+    db.log(TestLog(LogLevel::Info, "Connected to {host}", vec![("host", SLObject::from("db1"))]));
+    http.log(TestLog(LogLevel::Warning, "Slow request {path}", vec![
+        ("path", SLObject::from("/users")),
+        ("elapsed_ms", SLObject::from(1500i64)),
+    ]));
+    db.log(TestLog(LogLevel::Error, "Query failed", vec![("host", SLObject::from("db1"))]));
+}
+
+#[test]
+fn test_synchronous_capture() {
+    let (factory, handler) = CapturingHandler::factory();
+    log_sample(&factory);
+    // No flush needed, logs are handled on the calling thread.
+    assert_eq!(handler.len(), 3);
+    assert_eq!(
+        handler.messages(),
+        vec!["Connected to db1", "Slow request /users", "Query failed"]);
+}
+
+#[test]
+fn test_synchronous_capture_from_threads() {
+    let (factory, handler) = CapturingHandler::factory();
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let logger = factory.create_from_str("worker");
+                for _ in 0..25 {
+                    logger.log(TestLog(LogLevel::Debug, "tick", vec![]));
+                }
+            });
+        }
+    });
+    handler.query().logger("worker").template("tick").assert_count(100);
+}
+
+#[test]
+fn test_query() {
+    let (factory, handler) = CapturingHandler::factory();
+    log_sample(&factory);
+
+    handler.query().logger("db").assert_count(2);
+    handler.query().level(LogLevel::Warning).assert_count(1);
+    handler.query().min_level(LogLevel::Warning).assert_count(2);
+    handler.query().param("host", "db1").assert_count(2);
+    handler.query().param("host", "db2").assert_none();
+    handler.query().message_contains("request").assert_any();
+    handler.query().template("Connected to {host}").logger("http").assert_none();
+
+    let log = handler.query().logger("http").assert_single();
+    assert_eq!(*log.log_level(), LogLevel::Warning);
+    assert_eq!(log.logger_name().as_ref().map(ImmutableString::as_str), Some("http"));
+    assert_eq!(log.param("elapsed_ms"), Some(&SLObject::from(1500i64)));
+    assert_eq!(log.param("logger_name"), Some(&SLObject::from("http")));
+    assert_eq!(log.param("missing"), None);
+
+    let errors = handler.query().filter(|log| log.message().starts_with("Query"));
+    assert_eq!(errors.count(), 1);
+    assert_eq!(*errors.into_logs()[0].log_level(), LogLevel::Error);
+}
+
+#[test]
+fn test_snapshot() {
+    let (factory, handler) = CapturingHandler::factory();
+    log_sample(&factory);
+    let expected = "\
+Info db: Connected to db1
+Warning http: Slow request /users {elapsed_ms=1500}
+Error db: Query failed {host=\"db1\"}";
+    assert_eq!(handler.snapshot(), expected);
+    assert_eq!(handler.query().level(LogLevel::Info).snapshot(), "Info db: Connected to db1");
+
+    assert_eq!(handler.take().len(), 3);
+    assert!(handler.is_empty());
+    assert_eq!(handler.snapshot(), "");
+}
+
+#[test]
+#[should_panic(expected = "Expected 1 matching logs, found 2:\nInfo db: Connected to db1\nError db: Query failed")]
+fn test_assert_count_failure() {
+    let (factory, handler) = CapturingHandler::factory();
+    log_sample(&factory);
+    handler.query().logger("db").assert_count(1);
+}
+
+#[test]
+#[should_panic(expected = "Expected at least one matching log, found none.")]
+fn test_assert_any_failure() {
+    let (factory, handler) = CapturingHandler::factory();
+    log_sample(&factory);
+    handler.clear();
+    handler.query().assert_any();
+}
+
+#[test]
+fn test_shutdown() {
+    let (factory, handler) = CapturingHandler::factory();
+    let logger = factory.create_from_str("db");
+    logger.log(TestLog(LogLevel::Info, "before", vec![]));
+    factory.shutdown();
+    logger.log(TestLog(LogLevel::Info, "after", vec![]));
+    assert_eq!(handler.messages(), vec!["before"]);
+}
@@ -1,9 +1,9 @@
 #![allow(clippy::cast_sign_loss)]
-use std::{collections::HashMap, io::Write, time::SystemTime};
+use std::{collections::HashMap, fmt::Write as _, io::Write, net::IpAddr, time::SystemTime};
 
 use chrono::{DateTime, SecondsFormat, Utc};
 use raf_array::immutable_string::ImmutableString;
-use raf_structural_logging::{models::SLObject, traits::LogLevel};
+use raf_structural_logging::{models::{SLError, SLObject, SLUuid}, traits::LogLevel};
 use termcolor::{Color, ColorSpec, StandardStreamLock, WriteColor};
 
 pub struct Context<'a> {
@@ -23,6 +23,25 @@ impl<'a> Context<'a> {
         self.stdout.write_all(txt.as_bytes()).expect("stdout.write_all() fail");
     }
 
+    /// Writes text of already formatted `value` with the color of its type.
+    pub fn write_formatted(&mut self, txt: &str, value: Option<&SLObject>) {
+        let color = match value {
+            Some(
+                SLObject::Number(_)
+                | SLObject::Float(_)
+                | SLObject::UInt64(_)
+                | SLObject::UInt128(_)) => Color::Blue,
+            Some(SLObject::Duration(_) | SLObject::Bytes(_)) => Color::Cyan,
+            Some(SLObject::Bool(_)) => Color::Magenta,
+            Some(SLObject::Uuid(_) | SLObject::IpAddr(_)) => Color::Green,
+            Some(SLObject::Error(_)) => Color::Red,
+            Some(SLObject::Array(_) | SLObject::Dict(_)) => Color::Yellow,
+            Some(SLObject::SystemTime(_) | SLObject::LogLevel(_)) => Color::White,
+            Some(SLObject::String(_) | SLObject::Empty) | None => Color::Ansi256(7),
+        };
+        self.write(txt, &color_spec(color));
+    }
+
     pub fn flush(&mut self) {
         self.stdout.write_all(b"\n").expect("stdout.write_all() fail");
         if self.is_terminal {
@@ -171,6 +190,62 @@ impl ConsoleWrite for i64 {
     }
 }
 
+impl ConsoleWrite for f64 {
+    fn write(&self, ctx: &mut Context) {
+        ctx.write(&self.to_string(), &color_spec(Color::Blue));
+    }
+}
+
+impl ConsoleWrite for u64 {
+    fn write(&self, ctx: &mut Context) {
+        ctx.write(&self.to_string(), &color_spec(Color::Blue));
+    }
+}
+
+impl ConsoleWrite for u128 {
+    fn write(&self, ctx: &mut Context) {
+        ctx.write(&self.to_string(), &color_spec(Color::Blue));
+    }
+}
+
+impl ConsoleWrite for Vec<u8> {
+    fn write(&self, ctx: &mut Context) {
+        let mut text = String::with_capacity(2 + 2*self.len());
+        text.push_str("0x");
+        for byte in self {
+            let _ = write!(text, "{byte:02x}");
+        }
+        ctx.write(&text, &color_spec(Color::Cyan));
+    }
+}
+
+impl ConsoleWrite for SLUuid {
+    fn write(&self, ctx: &mut Context) {
+        ctx.write(&self.to_string(), &color_spec(Color::Green));
+    }
+}
+
+impl ConsoleWrite for IpAddr {
+    fn write(&self, ctx: &mut Context) {
+        ctx.write(&self.to_string(), &color_spec(Color::Green));
+    }
+}
+
+impl ConsoleWrite for SLError {
+    fn write(&self, ctx: &mut Context) {
+        let color = color_spec(Color::Red);
+        ctx.write(self.message().as_str(), &color);
+        for source in self.sources() {
+            ctx.write(": ", &color);
+            ctx.write(source.as_str(), &color);
+        }
+        if let Some(backtrace) = self.backtrace() {
+            ctx.write("\n", &color);
+            ctx.write(backtrace.as_str(), &color);
+        }
+    }
+}
+
 impl ConsoleWrite for bool {
     fn write(&self, ctx: &mut Context) {
         match self {
@@ -223,7 +298,14 @@ impl ConsoleWrite for SLObject {
             SLObject::Duration(inner) => inner.value().write(ctx),
             SLObject::String(inner) => inner.value().write(ctx),
             SLObject::Number(inner) => inner.value().write(ctx),
+            SLObject::Float(inner) => inner.value().write(ctx),
+            SLObject::UInt64(inner) => inner.value().write(ctx),
+            SLObject::UInt128(inner) => inner.value().write(ctx),
             SLObject::Bool(inner) => inner.value().write(ctx),
+            SLObject::Bytes(inner) => inner.value().write(ctx),
+            SLObject::Uuid(inner) => inner.write(ctx),
+            SLObject::IpAddr(inner) => inner.value().write(ctx),
+            SLObject::Error(inner) => inner.as_ref().write(ctx),
             SLObject::Array(inner) => inner.value().write(ctx),
             SLObject::Dict(inner) => inner.value().write(ctx),
             SLObject::Empty => { },
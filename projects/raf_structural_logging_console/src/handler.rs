@@ -1,8 +1,8 @@
 use std::io::{self, IsTerminal};
 
 use raf_structural_logging::{
-    models::LogDataHolder,
-    template::TemplatePiece,
+    models::{LogDataHolder, SLObject},
+    template::{render_parameter, TemplatePiece},
     traits::StructuralLogHandler};
 use termcolor::{ColorChoice, StandardStream};
 
@@ -20,8 +20,6 @@ impl StructuralLogHandler for ConsoleHandler {
         }
 
         let template = log.template();
-        let template_params = log.template_params();
-        let additional_data = log.additional_data();
 
         let is_terminal = io::stdout().is_terminal();
         let stdout = StandardStream::stdout(ColorChoice::Always);
@@ -34,11 +32,16 @@ impl StructuralLogHandler for ConsoleHandler {
                 TemplatePiece::RawString(txt) => {
                     txt.write(&mut ctx);
                 },
-                TemplatePiece::Parameter(txt) => {
-                    if let Some(value) = template_params.get(txt) {
-                        value.write(&mut ctx);
-                    } else if let Some(value) = additional_data.get(txt) {
-                        value.write(&mut ctx);
+                TemplatePiece::Parameter(parameter) => {
+                    let value = log.get(parameter.name());
+                    if parameter.is_plain() {
+                        if let Some(value) = value {
+                            value.write(&mut ctx);
+                        }
+                    } else {
+                        let mut text = String::new();
+                        render_parameter(value.unwrap_or(&SLObject::Empty), parameter, &mut text);
+                        ctx.write_formatted(&text, value);
                     }
                 },
             }
@@ -1,7 +1,7 @@
-use std::{collections::HashMap, sync::LazyLock, time::{Duration, SystemTime}};
+use std::{collections::HashMap, net::Ipv4Addr, sync::LazyLock, time::{Duration, SystemTime}};
 
 use raf_array::immutable_string::ImmutableString;
-use raf_structural_logging::{models::{LogDataHolder, SLObject}, template::TemplateBuilder, traits::{LogLevel, StructuralLogHandler}};
+use raf_structural_logging::{models::{LogDataHolder, SLError, SLObject, SLUuid}, template::TemplateBuilder, traits::{LogLevel, StructuralLogHandler}};
 use raf_structural_logging_console::ConsoleHandler;
 
 static TMPL_BUILDER: LazyLock<TemplateBuilder>
@@ -75,6 +75,13 @@ fn test2() {
     insert!("i64", 1234215);
     insert!("bazzzzz true", true);
     insert!("bazzzzz false", false);
+    insert!("f64", -2.5);
+    insert!("u64", u64::MAX);
+    insert!("u128", u128::MAX);
+    insert!("bytes", vec![0u8, 171, 255]);
+    insert!("uuid", SLUuid::new([0x11; 16]));
+    insert!("ip", Ipv4Addr::LOCALHOST);
+    insert!("err", SLError::new(ImmutableString::new("top").unwrap(), vec![ImmutableString::new("bottom").unwrap()], None));
 
     let key = ImmutableString::new("dct").unwrap();
     log_data2.update_data(key, map);
@@ -0,0 +1,71 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf}};
+
+use flate2::{write::GzEncoder, Compression};
+
+/// Returns path of archive with given `index`, e.g. `logs/app.log.3`
+/// or `logs/app.log.3.gz`.
+pub(crate) fn archive_path(directory: &Path, file_name: &str, index: usize, compressed: bool)
+    -> PathBuf
+{
+    let name = if compressed {
+        format!("{file_name}.{index}.gz")
+    } else {
+        format!("{file_name}.{index}")
+    };
+    directory.join(name)
+}
+
+/// Moves `active` file into archive at index `1`, shifting existing
+/// archives by one and removing those that exceed `max_archived_files`.
+/// Both compressed and uncompressed archives are shifted, so that
+/// changing compression setting between runs keeps retention intact.
+pub(crate) fn archive(
+    active: &Path,
+    directory: &Path,
+    file_name: &str,
+    max_archived_files: usize,
+    compress: bool) -> Result<(), io::Error>
+{
+    if max_archived_files == 0 {
+        return remove_if_exists(active);
+    }
+
+    for compressed in [false, true] {
+        remove_if_exists(&archive_path(directory, file_name, max_archived_files, compressed))?;
+        for index in (1..max_archived_files).rev() {
+            let source = archive_path(directory, file_name, index, compressed);
+            if source.exists() {
+                let target = archive_path(directory, file_name, index + 1, compressed);
+                fs::rename(source, target)?;
+            }
+        }
+    }
+
+    let first = archive_path(directory, file_name, 1, false);
+    fs::rename(active, &first)?;
+
+    if compress {
+        gzip(&first, &archive_path(directory, file_name, 1, true))?;
+        fs::remove_file(first)?;
+    }
+
+    Ok(())
+}
+
+fn gzip(source: &Path, target: &Path) -> Result<(), io::Error> {
+    let mut input = BufReader::new(File::open(source)?);
+    let output = BufWriter::new(File::create(target)?);
+    let mut encoder = GzEncoder::new(output, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.flush()
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), io::Error> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
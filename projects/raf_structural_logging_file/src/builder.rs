@@ -0,0 +1,68 @@
+use std::{io, path::PathBuf};
+
+use crate::{RollingFileHandler, RotationPeriod};
+
+/// Builder of [`RollingFileHandler`]. By default files are never rotated,
+/// up to 7 archived files are kept and archives are not compressed.
+pub struct RollingFileHandlerBuilder {
+    directory: PathBuf,
+    file_name: String,
+    max_file_size: Option<u64>,
+    rotation_period: Option<RotationPeriod>,
+    max_archived_files: usize,
+    compress: bool,
+}
+
+impl RollingFileHandlerBuilder {
+    pub fn new(directory: impl Into<PathBuf>, file_name: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_name: file_name.into(),
+            max_file_size: None,
+            rotation_period: None,
+            max_archived_files: 7,
+            compress: false,
+        }
+    }
+
+    /// Rotates active file when writing next log would make it exceed
+    /// `max_file_size` bytes. A single log is never split, so a file
+    /// may exceed the limit when it contains one log only.
+    pub fn set_max_file_size(&mut self, max_file_size: u64) {
+        self.max_file_size = Some(max_file_size);
+    }
+
+    /// Rotates active file when a log is created in a later
+    /// [`RotationPeriod`] than logs already written to it, according to
+    /// `LogDataHolder::created_at`.
+    pub fn set_rotation_period(&mut self, rotation_period: RotationPeriod) {
+        self.rotation_period = Some(rotation_period);
+    }
+
+    /// Sets how many rotated files are kept. With `0` rotated files are
+    /// removed immediately.
+    pub fn set_max_archived_files(&mut self, max_archived_files: usize) {
+        self.max_archived_files = max_archived_files;
+    }
+
+    /// Turns gzip compression of rotated files on or off.
+    pub fn set_compression(&mut self, compress: bool) {
+        self.compress = compress;
+    }
+
+    /// Builds [`RollingFileHandler`], creating the directory and opening
+    /// active file for appending.
+    /// 
+    /// # Errors
+    /// * [`io::Error`] if the directory cannot be created or
+    ///   the file cannot be opened
+    pub fn build(self) -> Result<RollingFileHandler, io::Error> {
+        RollingFileHandler::new(
+            self.directory,
+            self.file_name,
+            self.max_file_size,
+            self.rotation_period,
+            self.max_archived_files,
+            self.compress)
+    }
+}
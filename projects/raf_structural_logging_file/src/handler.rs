@@ -0,0 +1,180 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicU64, Ordering}, Mutex}};
+
+use raf_structural_logging::{
+    models::LogDataHolder,
+    traits::StructuralLogHandler};
+use raf_structural_logging_json::log_to_json_line;
+
+use crate::{archive::archive, RotationPeriod};
+
+struct ActiveFile {
+    file: Option<File>,
+    size: u64,
+    period_index: Option<u64>,
+}
+
+/// Handler that writes logs as JSON lines into a file, rotating it by size
+/// and/or by [`RotationPeriod`]. Use [`crate::RollingFileHandlerBuilder`]
+/// to construct it.
+/// 
+/// When the file cannot be rotated, logs are appended to the active file
+/// and rotation is retried with the next log. When a log cannot be written
+/// even after reopening the file, it is dropped and counted, see
+/// [`RollingFileHandler::failed_logs`]. The file is reopened again with
+/// the next log.
+pub struct RollingFileHandler {
+    directory: PathBuf,
+    file_name: String,
+    path: PathBuf,
+    max_file_size: Option<u64>,
+    rotation_period: Option<RotationPeriod>,
+    max_archived_files: usize,
+    compress: bool,
+    active: Mutex<ActiveFile>,
+    failed_logs: AtomicU64,
+}
+
+impl RollingFileHandler {
+    pub(crate) fn new(
+        directory: PathBuf,
+        file_name: String,
+        max_file_size: Option<u64>,
+        rotation_period: Option<RotationPeriod>,
+        max_archived_files: usize,
+        compress: bool) -> Result<Self, io::Error>
+    {
+        let path = directory.join(&file_name);
+        let mut active = ActiveFile { file: None, size: 0, period_index: None };
+        open_active(&directory, &path, rotation_period, &mut active)?;
+        Ok(Self {
+            directory: directory,
+            file_name: file_name,
+            path: path,
+            max_file_size: max_file_size,
+            rotation_period: rotation_period,
+            max_archived_files: max_archived_files,
+            compress: compress,
+            active: Mutex::new(active),
+            failed_logs: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns path of the active file.
+    #[inline(always)]
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns number of logs dropped because they couldn't be written.
+    #[inline(always)]
+    pub fn failed_logs(&self) -> u64 { self.failed_logs.load(Ordering::Relaxed) }
+
+    fn should_rotate(&self, active: &ActiveFile, line_length: u64, period_index: Option<u64>)
+        -> bool
+    {
+        if active.size == 0 {
+            return false;
+        }
+
+        if let Some(max_file_size) = self.max_file_size {
+            if active.size + line_length > max_file_size {
+                return true;
+            }
+        }
+
+        match (active.period_index, period_index) {
+            (Some(current), Some(new)) => new > current,
+            _ => false,
+        }
+    }
+
+    fn rotate(&self, active: &mut ActiveFile) -> Result<(), io::Error> {
+        active.file = None;
+        if self.path.exists() {
+            archive(
+                &self.path,
+                &self.directory,
+                &self.file_name,
+                self.max_archived_files,
+                self.compress)?;
+        }
+        active.size = 0;
+        active.period_index = None;
+        open_active(&self.directory, &self.path, self.rotation_period, active)
+    }
+
+    fn write_line(&self, active: &mut ActiveFile, line: &[u8]) -> Result<(), io::Error> {
+        if active.file.is_none() || !self.path.exists() {
+            open_active(&self.directory, &self.path, self.rotation_period, active)?;
+        }
+
+        let file = active.file.as_mut().expect("RollingFileHandler - missing file");
+        file.write_all(line)?;
+        file.flush()?;
+        active.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// (Re)creates directory and opens active file for appending. Period of
+/// an already existing, non-empty file is taken from its modification time.
+fn open_active(
+    directory: &Path,
+    path: &Path,
+    rotation_period: Option<RotationPeriod>,
+    active: &mut ActiveFile) -> Result<(), io::Error>
+{
+    fs::create_dir_all(directory)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let metadata = file.metadata()?;
+    active.size = metadata.len();
+    active.period_index = match rotation_period {
+        Some(period) if active.size > 0 => Some(period.period_index(metadata.modified()?)),
+        _ => None,
+    };
+    active.file = Some(file);
+    Ok(())
+}
+
+impl StructuralLogHandler for RollingFileHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+
+        let mut line = log_to_json_line(log);
+        line.push('\n');
+        let line = line.as_bytes();
+        let period_index = self.rotation_period
+            .map(|period| period.period_index(log.created_at()));
+
+        let mut guard = self.active.lock().expect("RollingFileHandler - lock() fail");
+        let active = &mut *guard;
+
+        if self.should_rotate(active, line.len() as u64, period_index) {
+            // On failure the active file is closed, so that writing below
+            // reopens it and rotation is retried with the next log.
+            let _ = self.rotate(active);
+        }
+
+        if self.write_line(active, line).is_err() {
+            // The file could have been lost in the meantime, e.g. together
+            // with its directory. Reopen it and try once more.
+            active.file = None;
+            if self.write_line(active, line).is_err() {
+                active.file = None;
+                self.failed_logs.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        if period_index > active.period_index {
+            active.period_index = period_index;
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Implements structural logging handler that writes logs into files,
+//! rotating them by size and/or by wall-clock period.
+//! 
+//! Logs are written as JSON lines, in the format of
+//! `raf_structural_logging_json`, so that both the active and archived
+//! files can be read back with `JsonLinesReader`.
+//! 
+//! Given directory `logs` and file name `app.log` the handler writes into
+//! `logs/app.log`. On rotation the active file becomes `logs/app.log.1`,
+//! previous `logs/app.log.1` becomes `logs/app.log.2` and so on, up to the
+//! configured number of archived files. Older archives are removed. When
+//! compression is turned on, archives are gzipped and get additional
+//! `.gz` extension, e.g. `logs/app.log.1.gz`.
+//! 
+//! If the directory or the active file disappears while the handler is
+//! running, both are recreated on the next log.
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+pub(crate) mod archive;
+
+mod rotation_period;
+mod builder;
+mod handler;
+pub use rotation_period::*;
+pub use builder::*;
+pub use handler::*;
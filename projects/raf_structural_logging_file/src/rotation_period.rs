@@ -0,0 +1,29 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Wall-clock period after which active file is rotated. Periods are
+/// aligned to UTC, e.g. [`RotationPeriod::Daily`] rotates at midnight UTC.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RotationPeriod {
+    Hourly,
+    Daily,
+}
+
+impl RotationPeriod {
+    #[inline(always)]
+    pub const fn seconds(self) -> u64 {
+        match self {
+            RotationPeriod::Hourly => 60 * 60,
+            RotationPeriod::Daily => 24 * 60 * 60,
+        }
+    }
+
+    /// Returns the number of whole periods between unix epoch and `time`.
+    /// Two times with equal index belong to the same period. Times before
+    /// unix epoch all belong to period `0`.
+    pub fn period_index(self, time: SystemTime) -> u64 {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() / self.seconds(),
+            Err(_) => 0,
+        }
+    }
+}
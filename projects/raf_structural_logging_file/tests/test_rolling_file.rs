@@ -0,0 +1,247 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+    sync::{Arc, LazyLock},
+    time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use flate2::read::GzDecoder;
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::CoreLoggerFactoryBuilder,
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use raf_structural_logging_file::{RollingFileHandlerBuilder, RotationPeriod};
+use raf_structural_logging_json::JsonLinesReader;
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+const START: u64 = 1714557600;
+
+fn build_log(idx: i64, created_at: SystemTime) -> LogDataHolder {
+    let template = TMPL_BUILDER.create_from_str("Log number {idx}");
+    let mut params = HashMap::new();
+    params.insert(ImmutableString::new("idx").unwrap(), SLObject::from(idx));
+    LogDataHolder::new_with_created_at(LogLevel::Info, template, params, created_at)
+}
+
+struct TestLog(i64);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        build_log(self.0, SystemTime::now())
+    }
+}
+
+fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn read_indexes<R: std::io::Read>(input: R) -> Vec<i64> {
+    JsonLinesReader::new(BufReader::new(input))
+        .map(|log| {
+            let log = log.unwrap();
+            let SLObject::Number(number) = &log.template_params()[&ImmutableString::new("idx").unwrap()] else {
+                panic!("Expected number");
+            };
+            number.value()
+        })
+        .collect()
+}
+
+fn read_file(path: &Path) -> Vec<i64> {
+    read_indexes(File::open(path).unwrap())
+}
+
+fn read_gz_file(path: &Path) -> Vec<i64> {
+    read_indexes(GzDecoder::new(File::open(path).unwrap()))
+}
+
+fn line_length() -> u64 {
+    let dir = tempfile::tempdir().unwrap();
+    let handler = RollingFileHandlerBuilder::new(dir.path(), "probe.log").build().unwrap();
+    handler.handle(&build_log(0, at(START)));
+    fs::metadata(handler.path()).unwrap().len()
+}
+
+#[test]
+fn test_no_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    let handler = RollingFileHandlerBuilder::new(dir.path(), "app.log").build().unwrap();
+    for idx in 0..5 {
+        handler.handle(&build_log(idx, at(START + idx as u64 * 86400)));
+    }
+    assert_eq!(read_file(&dir.path().join("app.log")), vec![0, 1, 2, 3, 4]);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[rstest]
+#[case(0, vec![vec![8, 9]])]
+#[case(1, vec![vec![8, 9], vec![6, 7]])]
+#[case(2, vec![vec![8, 9], vec![6, 7], vec![4, 5]])]
+#[case(10, vec![vec![8, 9], vec![6, 7], vec![4, 5], vec![2, 3], vec![0, 1]])]
+fn test_size_rotation(#[case] max_archived_files: usize, #[case] expected: Vec<Vec<i64>>) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = RollingFileHandlerBuilder::new(dir.path(), "app.log");
+    builder.set_max_file_size(2 * line_length() + 1);
+    builder.set_max_archived_files(max_archived_files);
+    let handler = builder.build().unwrap();
+    for idx in 0..10 {
+        handler.handle(&build_log(idx, at(START)));
+    }
+
+    assert_eq!(read_file(handler.path()), expected[0]);
+    for (index, archived) in expected.iter().enumerate().skip(1) {
+        let path = dir.path().join(format!("app.log.{index}"));
+        assert_eq!(&read_file(&path), archived);
+    }
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), expected.len());
+}
+
+#[rstest]
+#[case(RotationPeriod::Hourly, vec![vec![4], vec![2, 3], vec![1], vec![0]])]
+#[case(RotationPeriod::Daily, vec![vec![4], vec![0, 1, 2, 3]])]
+fn test_period_rotation(#[case] period: RotationPeriod, #[case] expected: Vec<Vec<i64>>) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = RollingFileHandlerBuilder::new(dir.path(), "app.log");
+    builder.set_rotation_period(period);
+    let handler = builder.build().unwrap();
+    let times = [
+        START,
+        START + 3600,
+        START + 2 * 3600 + 10,
+        START + 2 * 3600 + 20,
+        START + 86400,
+    ];
+    for (idx, time) in times.iter().enumerate() {
+        handler.handle(&build_log(idx as i64, at(*time)));
+    }
+
+    assert_eq!(read_file(handler.path()), expected[0]);
+    for (index, archived) in expected.iter().enumerate().skip(1) {
+        let path = dir.path().join(format!("app.log.{index}"));
+        assert_eq!(&read_file(&path), archived);
+    }
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), expected.len());
+}
+
+#[test]
+fn test_older_log_does_not_rotate() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = RollingFileHandlerBuilder::new(dir.path(), "app.log");
+    builder.set_rotation_period(RotationPeriod::Hourly);
+    let handler = builder.build().unwrap();
+    handler.handle(&build_log(0, at(START + 3600)));
+    handler.handle(&build_log(1, at(START)));
+    handler.handle(&build_log(2, at(START + 3600)));
+    assert_eq!(read_file(handler.path()), vec![0, 1, 2]);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_compression() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = RollingFileHandlerBuilder::new(dir.path(), "app.log");
+    builder.set_max_file_size(1);
+    builder.set_max_archived_files(2);
+    builder.set_compression(true);
+    let handler = builder.build().unwrap();
+    for idx in 0..4 {
+        handler.handle(&build_log(idx, at(START)));
+    }
+
+    assert_eq!(read_file(handler.path()), vec![3]);
+    assert_eq!(read_gz_file(&dir.path().join("app.log.1.gz")), vec![2]);
+    assert_eq!(read_gz_file(&dir.path().join("app.log.2.gz")), vec![1]);
+    assert!(!dir.path().join("app.log.1").exists());
+    assert!(!dir.path().join("app.log.3.gz").exists());
+}
+
+#[test]
+fn test_directory_recovery() {
+    let dir = tempfile::tempdir().unwrap();
+    let logs_dir = dir.path().join("nested").join("logs");
+    let mut builder = RollingFileHandlerBuilder::new(&logs_dir, "app.log");
+    builder.set_max_file_size(2 * line_length() + 1);
+    let handler = builder.build().unwrap();
+    handler.handle(&build_log(0, at(START)));
+    handler.handle(&build_log(1, at(START)));
+
+    fs::remove_dir_all(dir.path().join("nested")).unwrap();
+    handler.handle(&build_log(2, at(START)));
+    assert_eq!(read_file(handler.path()), vec![2]);
+
+    fs::remove_file(handler.path()).unwrap();
+    handler.handle(&build_log(3, at(START)));
+    handler.handle(&build_log(4, at(START)));
+    handler.handle(&build_log(5, at(START)));
+    assert_eq!(read_file(handler.path()), vec![5]);
+    assert_eq!(read_file(&logs_dir.join("app.log.1")), vec![3, 4]);
+}
+
+#[test]
+fn test_write_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let logs_dir = dir.path().join("logs");
+    let mut builder = RollingFileHandlerBuilder::new(&logs_dir, "app.log");
+    builder.set_max_file_size(line_length() + 1);
+    let handler = builder.build().unwrap();
+    handler.handle(&build_log(0, at(START)));
+
+    // A file in place of the directory makes both rotation and writing fail.
+    fs::remove_dir_all(&logs_dir).unwrap();
+    fs::write(&logs_dir, "").unwrap();
+    handler.handle(&build_log(1, at(START)));
+    handler.handle(&build_log(2, at(START)));
+    assert_eq!(handler.failed_logs(), 2);
+
+    fs::remove_file(&logs_dir).unwrap();
+    handler.handle(&build_log(3, at(START)));
+    handler.handle(&build_log(4, at(START)));
+    assert_eq!(read_file(handler.path()), vec![4]);
+    assert_eq!(read_file(&logs_dir.join("app.log.1")), vec![3]);
+    assert_eq!(handler.failed_logs(), 2);
+}
+
+#[test]
+fn test_existing_file_is_appended() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let handler = RollingFileHandlerBuilder::new(dir.path(), "app.log").build().unwrap();
+        handler.handle(&build_log(0, at(START)));
+    }
+    let mut builder = RollingFileHandlerBuilder::new(dir.path(), "app.log");
+    builder.set_max_file_size(2 * line_length() + 1);
+    let handler = builder.build().unwrap();
+    handler.handle(&build_log(1, at(START)));
+    handler.handle(&build_log(2, at(START)));
+    assert_eq!(read_file(handler.path()), vec![2]);
+    assert_eq!(read_file(&dir.path().join("app.log.1")), vec![0, 1]);
+}
+
+#[test]
+fn test_with_core_logger() {
+    let dir = tempfile::tempdir().unwrap();
+    let handler = RollingFileHandlerBuilder::new(dir.path(), "app.log").build().unwrap();
+    let path = handler.path().to_path_buf();
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.add_handler(Arc::new(handler));
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+        for idx in 0..3 {
+            logger.log(TestLog(idx));
+        }
+    }
+    assert_eq!(read_file(&path), vec![0, 1, 2]);
+}
@@ -0,0 +1,60 @@
+use std::{io::Write, sync::{atomic::{AtomicU64, Ordering}, Mutex}};
+
+use raf_structural_logging::{
+    models::LogDataHolder,
+    traits::StructuralLogHandler};
+
+use crate::json_mapping::log_to_json_line;
+
+/// Handler that writes each log as a single JSON object per line into
+/// underlying [`Write`]. Logs that cannot be written are dropped and
+/// counted, see [`JsonHandler::failed_logs`]. The output is flushed only
+/// by [`StructuralLogHandler::flush`], not after every line.
+pub struct JsonHandler<TWrite: Write + Send> {
+    output: Mutex<TWrite>,
+    failed_logs: AtomicU64,
+}
+
+impl<TWrite: Write + Send> JsonHandler<TWrite> {
+    pub fn new(output: TWrite) -> Self {
+        Self {
+            output: Mutex::new(output),
+            failed_logs: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns number of logs dropped because they couldn't be written.
+    #[inline(always)]
+    pub fn failed_logs(&self) -> u64 { self.failed_logs.load(Ordering::Relaxed) }
+
+    /// Releases underlying [`Write`].
+    /// 
+    /// # Panics
+    /// Only when internal lock is poisoned.
+    pub fn into_inner(self) -> TWrite {
+        self.output.into_inner().expect("JsonHandler - into_inner() fail")
+    }
+}
+
+impl<TWrite: Write + Send> StructuralLogHandler for JsonHandler<TWrite> {
+    fn handle(&self, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+
+        let mut line = log_to_json_line(log);
+        line.push('\n');
+
+        let mut guard = self.output.lock().expect("JsonHandler - lock() fail");
+        if guard.write_all(line.as_bytes()).is_err() {
+            self.failed_logs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn flush(&self) {
+        let mut guard = self.output.lock().expect("JsonHandler - lock() fail");
+        // Nothing to count here, logs lost in buffers of the output
+        // cannot be told apart from written ones.
+        let _ = guard.flush();
+    }
+}
@@ -0,0 +1,368 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    net::IpAddr,
+    time::{Duration, SystemTime}};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLError, SLObject, SLUuid},
+    template::{render_message, TemplateBuilder},
+    traits::LogLevel};
+use serde_json::{Map, Value};
+
+use crate::JsonReadError;
+
+pub(crate) const CREATED_AT: &str = "created_at";
+pub(crate) const LEVEL: &str = "level";
+pub(crate) const MESSAGE: &str = "message";
+pub(crate) const TEMPLATE: &str = "template";
+pub(crate) const TEMPLATE_PARAMS: &str = "template_params";
+pub(crate) const ADDITIONAL_DATA: &str = "additional_data";
+
+const LEVEL_TAG: &str = "$level";
+const TIME_TAG: &str = "$time";
+const DURATION_TAG: &str = "$duration";
+const FLOAT_TAG: &str = "$float";
+const U64_TAG: &str = "$u64";
+const U128_TAG: &str = "$u128";
+const BYTES_TAG: &str = "$bytes";
+const UUID_TAG: &str = "$uuid";
+const IP_TAG: &str = "$ip";
+const ERROR_TAG: &str = "$error";
+const SECS: &str = "secs";
+const NANOS: &str = "nanos";
+const ERROR_MESSAGE: &str = "message";
+const ERROR_SOURCES: &str = "sources";
+const ERROR_BACKTRACE: &str = "backtrace";
+
+pub(crate) fn level_to_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Debug => "Debug",
+        LogLevel::Info => "Info",
+        LogLevel::Warning => "Warning",
+        LogLevel::Error => "Error",
+    }
+}
+
+fn level_from_str(text: &str) -> Result<LogLevel, JsonReadError> {
+    match text {
+        "Debug" => Ok(LogLevel::Debug),
+        "Info" => Ok(LogLevel::Info),
+        "Warning" => Ok(LogLevel::Warning),
+        "Error" => Ok(LogLevel::Error),
+        _ => Err(JsonReadError::format(format!("Invalid log level: [{text}]."))),
+    }
+}
+
+pub(crate) fn time_to_str(time: SystemTime) -> String {
+    let dt: DateTime<Utc> = time.into();
+    dt.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn time_from_str(text: &str) -> Result<SystemTime, JsonReadError> {
+    match DateTime::parse_from_rfc3339(text) {
+        Ok(dt) => Ok(dt.with_timezone(&Utc).into()),
+        Err(err) => Err(JsonReadError::format(format!("Invalid RFC3339 time [{text}]: {err}."))),
+    }
+}
+
+fn tagged(tag: &str, value: Value) -> Value {
+    let mut map = Map::with_capacity(1);
+    map.insert(tag.to_owned(), value);
+    Value::Object(map)
+}
+
+pub(crate) fn object_to_json(obj: &SLObject) -> Value {
+    match obj {
+        SLObject::Empty => Value::Null,
+        SLObject::LogLevel(inner) => {
+            tagged(LEVEL_TAG, Value::from(level_to_str(*inner.value())))
+        },
+        SLObject::SystemTime(inner) => {
+            tagged(TIME_TAG, Value::from(time_to_str(*inner.value())))
+        },
+        SLObject::Duration(inner) => {
+            let mut map = Map::with_capacity(2);
+            map.insert(SECS.to_owned(), Value::from(inner.value().as_secs()));
+            map.insert(NANOS.to_owned(), Value::from(inner.value().subsec_nanos()));
+            tagged(DURATION_TAG, Value::Object(map))
+        },
+        SLObject::String(inner) => Value::from(inner.value().as_str()),
+        SLObject::Number(inner) => Value::from(inner.value()),
+        SLObject::Float(inner) => float_to_json(inner.value()),
+        SLObject::UInt64(inner) => tagged(U64_TAG, Value::from(inner.value())),
+        SLObject::UInt128(inner) => tagged(U128_TAG, Value::from(inner.value().to_string())),
+        SLObject::Bool(inner) => Value::from(inner.value()),
+        SLObject::Bytes(inner) => tagged(BYTES_TAG, Value::from(bytes_to_hex(inner.value()))),
+        SLObject::Uuid(inner) => tagged(UUID_TAG, Value::from(inner.to_string())),
+        SLObject::IpAddr(inner) => tagged(IP_TAG, Value::from(inner.value().to_string())),
+        SLObject::Error(inner) => tagged(ERROR_TAG, error_to_json(inner)),
+        SLObject::Array(inner) => {
+            Value::Array(inner.value().iter().map(object_to_json).collect())
+        },
+        SLObject::Dict(inner) => Value::Object(dict_to_json(inner.value())),
+    }
+}
+
+/// Finite floats are written as plain JSON numbers, the remaining ones
+/// are tagged since JSON doesn't support them.
+fn float_to_json(value: f64) -> Value {
+    if value.is_finite() {
+        return Value::from(value);
+    }
+    let text = if value.is_nan() {
+        "NaN"
+    } else if value > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    };
+    tagged(FLOAT_TAG, Value::from(text))
+}
+
+fn bytes_to_hex(value: &[u8]) -> String {
+    let mut result = String::with_capacity(2*value.len());
+    for byte in value {
+        let _ = write!(result, "{byte:02x}");
+    }
+    result
+}
+
+fn error_to_json(error: &SLError) -> Value {
+    let mut map = Map::with_capacity(3);
+    map.insert(ERROR_MESSAGE.to_owned(), Value::from(error.message().as_str()));
+    let sources = error.sources().iter().map(|source| Value::from(source.as_str())).collect();
+    map.insert(ERROR_SOURCES.to_owned(), Value::Array(sources));
+    let backtrace = error.backtrace()
+        .as_ref()
+        .map_or(Value::Null, |backtrace| Value::from(backtrace.as_str()));
+    map.insert(ERROR_BACKTRACE.to_owned(), backtrace);
+    Value::Object(map)
+}
+
+pub(crate) fn dict_to_json(dict: &HashMap<ImmutableString, SLObject>) -> Map<String, Value> {
+    let mut map = Map::with_capacity(dict.len());
+    for (key, value) in dict {
+        map.insert(key.as_str().to_owned(), object_to_json(value));
+    }
+    map
+}
+
+fn new_key(text: &str) -> Result<ImmutableString, JsonReadError> {
+    ImmutableString::new(text)
+        .map_err(|err| JsonReadError::format(format!("Couldn't create string: {err:?}.")))
+}
+
+fn str_from_json<'a>(value: &'a Value, name: &str) -> Result<&'a str, JsonReadError> {
+    value.as_str()
+        .ok_or_else(|| JsonReadError::format(format!("Expected string for [{name}].")))
+}
+
+fn duration_from_json(value: &Value) -> Result<Duration, JsonReadError> {
+    let invalid = || JsonReadError::format("Invalid duration.");
+    let secs = value.get(SECS).and_then(Value::as_u64).ok_or_else(invalid)?;
+    let nanos = value.get(NANOS)
+        .and_then(Value::as_u64)
+        .and_then(|val| u32::try_from(val).ok())
+        .ok_or_else(invalid)?;
+    Ok(Duration::new(secs, nanos))
+}
+
+fn float_from_json(value: &Value) -> Result<f64, JsonReadError> {
+    match str_from_json(value, FLOAT_TAG)? {
+        "NaN" => Ok(f64::NAN),
+        "inf" => Ok(f64::INFINITY),
+        "-inf" => Ok(f64::NEG_INFINITY),
+        text => Err(JsonReadError::format(format!("Invalid float: [{text}]."))),
+    }
+}
+
+fn bytes_from_hex(text: &str) -> Result<Vec<u8>, JsonReadError> {
+    let invalid = || JsonReadError::format(format!("Invalid hex bytes: [{text}]."));
+    if text.len() % 2 != 0 || !text.bytes().all(|chr| chr.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(&text[idx..idx+2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+fn uuid_from_str(text: &str) -> Result<SLUuid, JsonReadError> {
+    let hex: String = text.chars().filter(|chr| *chr != '-').collect();
+    let bytes = bytes_from_hex(&hex)?;
+    let Ok(value) = <[u8; 16]>::try_from(bytes) else {
+        return Err(JsonReadError::format(format!("Invalid uuid: [{text}].")));
+    };
+    Ok(SLUuid::new(value))
+}
+
+fn error_from_json(value: &Value) -> Result<SLError, JsonReadError> {
+    let invalid = || JsonReadError::format("Invalid error.");
+    let message = new_key(value.get(ERROR_MESSAGE).and_then(Value::as_str).ok_or_else(invalid)?)?;
+    let mut sources = Vec::new();
+    for source in value.get(ERROR_SOURCES).and_then(Value::as_array).ok_or_else(invalid)? {
+        sources.push(new_key(str_from_json(source, ERROR_SOURCES)?)?);
+    }
+    let backtrace = match value.get(ERROR_BACKTRACE) {
+        None | Some(Value::Null) => None,
+        Some(backtrace) => Some(new_key(str_from_json(backtrace, ERROR_BACKTRACE)?)?),
+    };
+    Ok(SLError::new(message, sources, backtrace))
+}
+
+fn tagged_from_json(tag: &str, value: &Value) -> Option<Result<SLObject, JsonReadError>> {
+    let result = match tag {
+        LEVEL_TAG => str_from_json(value, tag)
+            .and_then(level_from_str)
+            .map(SLObject::from),
+        TIME_TAG => str_from_json(value, tag)
+            .and_then(time_from_str)
+            .map(SLObject::from),
+        DURATION_TAG => duration_from_json(value).map(SLObject::from),
+        FLOAT_TAG => float_from_json(value).map(SLObject::from),
+        U64_TAG => value.as_u64()
+            .map(SLObject::from)
+            .ok_or_else(|| JsonReadError::format(format!("Expected u64 for [{tag}]."))),
+        U128_TAG => str_from_json(value, tag)
+            .and_then(|text| text.parse::<u128>()
+                .map_err(|_| JsonReadError::format(format!("Invalid u128: [{text}]."))))
+            .map(SLObject::from),
+        BYTES_TAG => str_from_json(value, tag)
+            .and_then(bytes_from_hex)
+            .map(SLObject::from),
+        UUID_TAG => str_from_json(value, tag)
+            .and_then(uuid_from_str)
+            .map(SLObject::from),
+        IP_TAG => str_from_json(value, tag)
+            .and_then(|text| text.parse::<IpAddr>()
+                .map_err(|_| JsonReadError::format(format!("Invalid ip address: [{text}]."))))
+            .map(SLObject::from),
+        ERROR_TAG => error_from_json(value).map(SLObject::from),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub(crate) fn object_from_json(value: &Value) -> Result<SLObject, JsonReadError> {
+    let obj = match value {
+        Value::Null => SLObject::Empty,
+        Value::Bool(val) => SLObject::from(*val),
+        Value::Number(val) => {
+            if let Some(number) = val.as_i64() {
+                SLObject::from(number)
+            } else if let Some(number) = val.as_u64() {
+                SLObject::from(number)
+            } else if let Some(number) = val.as_f64() {
+                SLObject::from(number)
+            } else {
+                return Err(JsonReadError::format(format!("Invalid number [{val}].")));
+            }
+        },
+        Value::String(val) => SLObject::from(new_key(val)?),
+        Value::Array(val) => {
+            let mut result = Vec::with_capacity(val.len());
+            for item in val {
+                result.push(object_from_json(item)?);
+            }
+            SLObject::from(result)
+        },
+        Value::Object(val) => {
+            if let Some((tag, inner)) = val.iter().next().filter(|_| val.len() == 1) {
+                if let Some(result) = tagged_from_json(tag, inner) {
+                    return result;
+                }
+            }
+            SLObject::from(dict_from_json(val)?)
+        },
+    };
+    Ok(obj)
+}
+
+pub(crate) fn dict_from_json(map: &Map<String, Value>)
+    -> Result<HashMap<ImmutableString, SLObject>, JsonReadError>
+{
+    let mut result = HashMap::with_capacity(map.len());
+    for (key, value) in map {
+        result.insert(new_key(key)?, object_from_json(value)?);
+    }
+    Ok(result)
+}
+
+/// Writes log as a single line JSON object, without trailing new line.
+pub fn log_to_json_line(log: &LogDataHolder) -> String {
+    // Fields are written manually to keep their order stable and readable.
+    let fields = [
+        (CREATED_AT, Value::from(time_to_str(log.created_at()))),
+        (LEVEL, Value::from(level_to_str(log.log_level()))),
+        (MESSAGE, Value::from(render_message(log))),
+        (TEMPLATE, Value::from(log.template().as_immutable_string().as_str())),
+        (TEMPLATE_PARAMS, Value::Object(dict_to_json(log.template_params()))),
+        (ADDITIONAL_DATA, Value::Object(dict_to_json(log.additional_data()))),
+    ];
+
+    let mut result = String::with_capacity(256);
+    result.push('{');
+    for (idx, (key, value)) in fields.iter().enumerate() {
+        if idx > 0 {
+            result.push(',');
+        }
+        result.push_str(&Value::from(*key).to_string());
+        result.push(':');
+        result.push_str(&value.to_string());
+    }
+    result.push('}');
+    result
+}
+
+fn required<'a>(map: &'a Map<String, Value>, key: &str) -> Result<&'a Value, JsonReadError> {
+    map.get(key)
+        .ok_or_else(|| JsonReadError::format(format!("Missing [{key}] field.")))
+}
+
+fn required_dict<'a>(map: &'a Map<String, Value>, key: &str)
+    -> Result<&'a Map<String, Value>, JsonReadError>
+{
+    required(map, key)?
+        .as_object()
+        .ok_or_else(|| JsonReadError::format(format!("Expected object for [{key}].")))
+}
+
+/// Restores log out of a single JSON line. The `message` field is ignored,
+/// since it is always rendered out of template and parameters. String
+/// under `logger_name_key` is restored as the logger name.
+pub(crate) fn log_from_json_line(
+    line: &str,
+    template_builder: &TemplateBuilder,
+    logger_name_key: &ImmutableString)
+    -> Result<LogDataHolder, JsonReadError>
+{
+    let value: Value = serde_json::from_str(line)?;
+    let Some(map) = value.as_object() else {
+        return Err(JsonReadError::format("Expected JSON object."));
+    };
+
+    let created_at = time_from_str(str_from_json(required(map, CREATED_AT)?, CREATED_AT)?)?;
+    let log_level = level_from_str(str_from_json(required(map, LEVEL)?, LEVEL)?)?;
+    let template = new_key(str_from_json(required(map, TEMPLATE)?, TEMPLATE)?)?;
+    let template = template_builder
+        .try_create(&template)
+        .map_err(|err| JsonReadError::format(format!("Invalid template: {err}.")))?;
+    let template_params = dict_from_json(required_dict(map, TEMPLATE_PARAMS)?)?;
+    let additional_data = dict_from_json(required_dict(map, ADDITIONAL_DATA)?)?;
+
+    let mut log = LogDataHolder::new_with_created_at(
+        log_level,
+        template,
+        template_params,
+        created_at);
+    for (key, value) in additional_data {
+        match value {
+            SLObject::String(name) if key == *logger_name_key => log.set_logger_name(key, name.value().clone()),
+            value => log.update_data(key, value),
+        }
+    }
+    Ok(log)
+}
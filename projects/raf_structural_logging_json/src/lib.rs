@@ -0,0 +1,46 @@
+//! Implements structural logging handler that writes logs as JSON lines,
+//! and a reader that restores logs from such lines.
+//! 
+//! Each log is written as a single JSON object of the form:
+//! 
+//! ```json
+//! {
+//!     "created_at": "2024-05-01T10:00:00.123Z",
+//!     "level": "Info",
+//!     "message": "User john logged in",
+//!     "template": "User {user} logged in",
+//!     "template_params": { "user": "john" },
+//!     "additional_data": { "logger_name": "auth" }
+//! }
+//! ```
+//! 
+//! Strings, `i64` numbers, finite floats, booleans, arrays and dicts map
+//! to corresponding JSON values, while `SLObject::Empty` maps to `null`.
+//! Remaining values are wrapped in single key objects, so that they can
+//! be restored:
+//! * log level as `{"$level": "Warning"}`
+//! * system time as `{"$time": "2024-05-01T10:00:00.123Z"}`
+//! * duration as `{"$duration": {"secs": 1, "nanos": 500000000}}`
+//! * non-finite float as `{"$float": "NaN"}`, `"inf"` or `"-inf"`
+//! * unsigned integers as `{"$u64": 7}` and `{"$u128": "7"}`
+//! * bytes as hex string `{"$bytes": "00abff"}`
+//! * UUID as `{"$uuid": "67e55044-10b1-426f-9247-bb680e5fe0c8"}`
+//! * IP address as `{"$ip": "127.0.0.1"}`
+//! * error as `{"$error": {"message": "...", "sources": [...], "backtrace": null}}`
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+pub(crate) mod json_mapping;
+
+mod handler;
+mod reader;
+pub use handler::*;
+pub use reader::*;
+pub use json_mapping::log_to_json_line;
+pub use raf_structural_logging::template::render_message;
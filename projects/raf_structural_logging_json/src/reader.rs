@@ -0,0 +1,100 @@
+use std::io::BufRead;
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::LogDataHolder,
+    template::{get_logger_name_key, TemplateBuilder}};
+
+use crate::json_mapping::log_from_json_line;
+
+#[derive(Debug)]
+pub enum JsonReadError {
+    InputError(std::io::Error),
+    JsonError(serde_json::Error),
+    FormatError(String),
+}
+
+impl JsonReadError {
+    pub(crate) fn format(text: impl Into<String>) -> Self {
+        Self::FormatError(text.into())
+    }
+}
+
+impl From<std::io::Error> for JsonReadError {
+    fn from(value: std::io::Error) -> Self {
+        Self::InputError(value)
+    }
+}
+
+impl From<serde_json::Error> for JsonReadError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::JsonError(value)
+    }
+}
+
+/// Restores [`LogDataHolder`] out of a single line written by
+/// [`JsonHandler`][crate::JsonHandler]. Logger name is expected under
+/// [`get_logger_name_key`].
+/// 
+/// # Errors
+/// * [`JsonReadError::JsonError`] if `line` is not a valid JSON
+/// * [`JsonReadError::FormatError`] if JSON does not represent a log,
+///   including logs with templates that cannot be parsed
+pub fn parse_json_line(line: &str, template_builder: &TemplateBuilder)
+    -> Result<LogDataHolder, JsonReadError>
+{
+    log_from_json_line(line, template_builder, get_logger_name_key())
+}
+
+/// Iterates over logs stored as JSON lines in underlying [`BufRead`].
+/// Empty lines are skipped.
+pub struct JsonLinesReader<TRead: BufRead> {
+    input: TRead,
+    template_builder: TemplateBuilder,
+    logger_name_key: ImmutableString,
+    line: String,
+}
+
+impl<TRead: BufRead> JsonLinesReader<TRead> {
+    pub fn new(input: TRead) -> Self {
+        Self {
+            input: input,
+            template_builder: TemplateBuilder::default(),
+            logger_name_key: get_logger_name_key().clone(),
+            line: String::new(),
+        }
+    }
+
+    /// Sets `key` under which logger name is expected, the same as passed
+    /// to `CoreLoggerFactoryBuilder::set_logger_name_key` of the loggers
+    /// that wrote the logs. Defaults to [`get_logger_name_key`].
+    /// 
+    /// # Panics
+    /// When `key` cannot be converted into [`ImmutableString`].
+    pub fn set_logger_name_key(&mut self, key: &str) {
+        self.logger_name_key = ImmutableString::new(key)
+            .expect("JsonLinesReader - new ImmutableString fail");
+    }
+}
+
+impl<TRead: BufRead> Iterator for JsonLinesReader<TRead> {
+    type Item = Result<LogDataHolder, JsonReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.input.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => { },
+                Err(err) => return Some(Err(err.into())),
+            }
+
+            let line = self.line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            return Some(log_from_json_line(line, &self.template_builder, &self.logger_name_key));
+        }
+    }
+}
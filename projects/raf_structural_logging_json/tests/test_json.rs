@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufReader, Write},
+    sync::{Arc, LazyLock, Mutex},
+    net::Ipv4Addr,
+    time::{Duration, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::CoreLoggerFactoryBuilder,
+    models::{LogDataHolder, SLError, SLObject, SLUuid},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use raf_structural_logging_json::{parse_json_line, JsonHandler, JsonLinesReader, JsonReadError};
+use serde_json::Value;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+fn imm(text: &str) -> ImmutableString {
+    ImmutableString::new(text).unwrap()
+}
+
+fn build_log() -> LogDataHolder {
+    let template = TMPL_BUILDER.create_from_str("User {user} waited {elapsed} at {when}: {details}");
+    let mut nested = HashMap::new();
+    nested.insert(imm("level"), SLObject::from(LogLevel::Warning));
+    nested.insert(imm("list"), SLObject::from(vec![SLObject::from(1i64), SLObject::Empty]));
+    let mut params = HashMap::new();
+    params.insert(imm("user"), SLObject::from("john"));
+    params.insert(imm("elapsed"), SLObject::from(Duration::new(1, 500_000_000)));
+    params.insert(imm("when"), SLObject::from(UNIX_EPOCH + Duration::from_millis(1500)));
+    params.insert(imm("details"), SLObject::from(nested));
+    let mut log = LogDataHolder::new_with_created_at(
+        LogLevel::Info,
+        template,
+        params,
+        UNIX_EPOCH + Duration::new(1714557600, 123_000_000));
+    log.set_logger_name(imm("logger_name"), imm("auth"));
+    log.update_data(imm("flag"), false);
+    log
+}
+
+#[test]
+fn test_write_line() {
+    let handler = JsonHandler::new(Vec::new());
+    let log = build_log();
+    handler.handle(&log);
+    let output = String::from_utf8(handler.into_inner()).unwrap();
+    assert!(output.ends_with('\n'));
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.starts_with("{\"created_at\":\"2024-05-01T10:00:00.123Z\",\"level\":\"Info\","));
+
+    let value: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(value["template"], "User {user} waited {elapsed} at {when}: {details}");
+    assert!(value["message"].as_str().unwrap().starts_with("User john waited 1.500s at 1970-01-01T00:00:01.500Z: {"));
+    assert_eq!(value["template_params"]["user"], "john");
+    assert_eq!(value["template_params"]["elapsed"]["$duration"]["secs"], 1);
+    assert_eq!(value["template_params"]["elapsed"]["$duration"]["nanos"], 500_000_000);
+    assert_eq!(value["template_params"]["when"]["$time"], "1970-01-01T00:00:01.500Z");
+    assert_eq!(value["template_params"]["details"]["level"]["$level"], "Warning");
+    assert_eq!(value["template_params"]["details"]["list"][0], 1);
+    assert!(value["template_params"]["details"]["list"][1].is_null());
+    assert_eq!(value["additional_data"]["logger_name"], "auth");
+    assert_eq!(value["additional_data"]["flag"], false);
+    assert!(value["additional_data"].get("created_at").is_none());
+    assert!(value["additional_data"].get("log_level").is_none());
+}
+
+#[test]
+fn test_round_trip() {
+    let handler = JsonHandler::new(Vec::new());
+    let log = build_log();
+    handler.handle(&log);
+    handler.handle(&log);
+    let mut output = handler.into_inner();
+    output.write_all(b"\n\n").unwrap();
+
+    let reader = JsonLinesReader::new(BufReader::new(output.as_slice()));
+    let restored: Vec<LogDataHolder> = reader.map(Result::unwrap).collect();
+    assert_eq!(restored.len(), 2);
+    for item in &restored {
+        assert_eq!(item.created_at(), log.created_at());
+        assert_eq!(item.log_level(), log.log_level());
+        assert_eq!(item.template(), log.template());
+        assert_eq!(item.template_params(), log.template_params());
+        assert_eq!(item.additional_data(), log.additional_data());
+        assert_eq!(item.logger_name(), Some(&imm("auth")));
+    }
+}
+
+fn build_rich_log() -> LogDataHolder {
+    let template = TMPL_BUILDER.create_from_str("{f} {nan} {u64} {u128} {bytes} {uuid} {ip} {err}");
+    let mut params = HashMap::new();
+    params.insert(imm("f"), SLObject::from(1.25));
+    params.insert(imm("nan"), SLObject::from(f64::NAN));
+    params.insert(imm("u64"), SLObject::from(u64::MAX));
+    params.insert(imm("u128"), SLObject::from(u128::MAX));
+    params.insert(imm("bytes"), SLObject::from(vec![0u8, 171, 255]));
+    params.insert(imm("uuid"), SLObject::from(SLUuid::new([0x11; 16])));
+    params.insert(imm("ip"), SLObject::from(Ipv4Addr::new(10, 0, 0, 1)));
+    let error = SLError::new(imm("top"), vec![imm("middle"), imm("bottom")], None);
+    params.insert(imm("err"), SLObject::from(error));
+    LogDataHolder::new_with_created_at(LogLevel::Info, template, params, UNIX_EPOCH)
+}
+
+#[test]
+fn test_rich_values() {
+    let handler = JsonHandler::new(Vec::new());
+    let log = build_rich_log();
+    handler.handle(&log);
+    let output = handler.into_inner();
+
+    let value: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        value["message"],
+        "1.25 NaN 18446744073709551615 340282366920938463463374607431768211455 0x00abff \
+         11111111-1111-1111-1111-111111111111 10.0.0.1 top: middle: bottom");
+    let params = &value["template_params"];
+    assert_eq!(params["f"], 1.25);
+    assert_eq!(params["nan"]["$float"], "NaN");
+    assert_eq!(params["u64"]["$u64"], u64::MAX);
+    assert_eq!(params["u128"]["$u128"], u128::MAX.to_string());
+    assert_eq!(params["bytes"]["$bytes"], "00abff");
+    assert_eq!(params["uuid"]["$uuid"], "11111111-1111-1111-1111-111111111111");
+    assert_eq!(params["ip"]["$ip"], "10.0.0.1");
+    assert_eq!(params["err"]["$error"]["message"], "top");
+    assert_eq!(params["err"]["$error"]["sources"][1], "bottom");
+    assert!(params["err"]["$error"]["backtrace"].is_null());
+
+    let mut reader = JsonLinesReader::new(BufReader::new(output.as_slice()));
+    let restored = reader.next().unwrap().unwrap();
+    assert_eq!(restored.template_params(), log.template_params());
+}
+
+#[test]
+fn test_invalid_lines() {
+    let builder = TemplateBuilder::default();
+    let result = parse_json_line("{not json", &builder);
+    assert!(matches!(result, Err(JsonReadError::JsonError(_))));
+    let result = parse_json_line("{\"level\":\"Info\"}", &builder);
+    assert!(matches!(result, Err(JsonReadError::FormatError(_))));
+    let result = parse_json_line("[1, 2]", &builder);
+    assert!(matches!(result, Err(JsonReadError::FormatError(_))));
+}
+
+#[test]
+fn test_malformed_template() {
+    let handler = JsonHandler::new(Vec::new());
+    handler.handle(&build_log());
+    let line = String::from_utf8(handler.into_inner()).unwrap();
+    let template = "\"template\":\"User {user} waited {elapsed} at {when}: {details}\"";
+    assert!(line.contains(template));
+    let malformed = line.replace(template, "\"template\":\"{abc\"");
+
+    let result = parse_json_line(malformed.trim_end(), &TemplateBuilder::default());
+    assert!(matches!(result, Err(JsonReadError::FormatError(_))));
+
+    let input = format!("{line}{malformed}{line}");
+    let results: Vec<_> = JsonLinesReader::new(BufReader::new(input.as_bytes())).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(JsonReadError::FormatError(_))));
+    assert!(results[2].is_ok());
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    data: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+struct TestLog;
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let mut params = HashMap::new();
+        params.insert(imm("value"), SLObject::from(15i64));
+        LogDataHolder::new(
+            LogLevel::Error,
+            TMPL_BUILDER.create_from_str("[{logger_name}] value is {value}"),
+            params)
+    }
+}
+
+#[test]
+fn test_with_core_logger() {
+    let buffer = SharedBuffer::default();
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.add_handler(Arc::new(JsonHandler::new(buffer.clone())));
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+        logger.log(TestLog);
+    }
+
+    let output = buffer.data.lock().unwrap().clone();
+    let text = String::from_utf8(output).unwrap();
+    let value: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["level"], "Error");
+    assert_eq!(value["message"], "[misc] value is 15");
+    assert_eq!(value["additional_data"]["logger_name"], "misc");
+}
+
+#[test]
+fn test_custom_logger_name_key() {
+    let buffer = SharedBuffer::default();
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.set_logger_name_key("logger");
+        builder.add_handler(Arc::new(JsonHandler::new(buffer.clone())));
+        let factory = builder.build();
+        let logger = factory.create_from_str("misc");
+        logger.log(TestLog);
+    }
+
+    let output = buffer.data.lock().unwrap().clone();
+    let mut reader = JsonLinesReader::new(BufReader::new(output.as_slice()));
+    reader.set_logger_name_key("logger");
+    let log = reader.next().unwrap().unwrap();
+    assert_eq!(log.logger_name(), Some(&imm("misc")));
+    assert_eq!(log.logger_name_key(), Some(&imm("logger")));
+    assert!(reader.next().is_none());
+}
+
+/// Accepts writes until the first `capacity` bytes are used up, like
+/// a full disk.
+struct FullDisk {
+    capacity: usize,
+    flushes: usize,
+}
+
+impl Write for FullDisk {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.capacity == 0 {
+            return Err(io::Error::from(io::ErrorKind::WriteZero));
+        }
+        let len = buf.len().min(self.capacity);
+        self.capacity -= len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_errors() {
+    let handler = JsonHandler::new(FullDisk { capacity: 1000, flushes: 0 });
+    for _ in 0..10 {
+        handler.handle(&build_log());
+    }
+    assert!(handler.failed_logs() > 0 && handler.failed_logs() < 10, "{}", handler.failed_logs());
+    handler.flush();
+    let output = handler.into_inner();
+    assert_eq!((output.capacity, output.flushes), (0, 1));
+}
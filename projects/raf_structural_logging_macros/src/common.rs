@@ -0,0 +1,39 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use raf_structural_logging::template::{parse_template, TemplatePiece};
+use syn::{Error, LitStr};
+
+/// Parses template at compile time and returns names of its parameters.
+pub(crate) fn template_parameters(template: &LitStr) -> syn::Result<Vec<String>> {
+    let pieces = parse_template(&template.value())
+        .map_err(|err| Error::new(template.span(), err.to_string()))?;
+    let mut result = Vec::new();
+    for piece in pieces {
+        if let TemplatePiece::Parameter(parameter) = piece {
+            result.push(parameter.name().as_str().to_owned());
+        }
+    }
+    Ok(result)
+}
+
+/// Generates statics holding interned keys, and code that inserts
+/// `(key, value)` pairs into `params_var` map.
+pub(crate) fn generate_params(params_var: &Ident, args: &[(String, TokenStream)])
+    -> (TokenStream, TokenStream)
+{
+    let mut statics = TokenStream::new();
+    let mut inserts = TokenStream::new();
+    for (idx, (key, value)) in args.iter().enumerate() {
+        let static_name = format_ident!("__SL_KEY_{}", idx);
+        statics.extend(quote! {
+            static #static_name: ::std::sync::LazyLock<::raf_structural_logging::macro_helpers::ImmutableString>
+                = ::std::sync::LazyLock::new(|| ::raf_structural_logging::macro_helpers::new_key(#key));
+        });
+        inserts.extend(quote! {
+            #params_var.insert(
+                #static_name.clone(),
+                ::core::convert::Into::<::raf_structural_logging::models::SLObject>::into(#value));
+        });
+    }
+    (statics, inserts)
+}
@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use raf_structural_logging::macro_helpers::IMPLICIT_KEYS;
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, DeriveInput, Error, Ident, LitStr, Path};
+
+use crate::common::{generate_params, template_parameters};
+
+const LOG_ATTRIBUTE: &str = "log";
+const LEVEL: &str = "level";
+const TEMPLATE: &str = "template";
+const SKIP: &str = "skip";
+const LEVELS: [&str; 4] = ["Debug", "Info", "Warning", "Error"];
+
+struct StructConfig {
+    level: Ident,
+    template: LitStr,
+}
+
+fn parse_struct_config(input: &DeriveInput) -> syn::Result<StructConfig> {
+    let mut level = None;
+    let mut template = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident(LOG_ATTRIBUTE) {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(LEVEL) {
+                let path: Path = meta.value()?.parse()?;
+                let Some(segment) = path.segments.last() else {
+                    return Err(meta.error("#[log] level has to be a LogLevel variant."));
+                };
+                let ident = segment.ident.clone();
+                if !LEVELS.contains(&ident.to_string().as_str()) {
+                    let msg = format!("#[log] level has to be one of: {}.", LEVELS.join(", "));
+                    return Err(Error::new(ident.span(), msg));
+                }
+                level = Some(ident);
+                return Ok(());
+            }
+
+            if meta.path.is_ident(TEMPLATE) {
+                let value: LitStr = meta.value()?.parse()?;
+                template = Some(value);
+                return Ok(());
+            }
+
+            Err(meta.error("#[log] unknown key. Expected level or template."))
+        })?;
+    }
+
+    let Some(level) = level else {
+        return Err(Error::new(input.ident.span(), "#[log(level = ...)] attribute is required."));
+    };
+
+    let Some(template) = template else {
+        return Err(Error::new(input.ident.span(), "#[log(template = ...)] attribute is required."));
+    };
+
+    Ok(StructConfig { level, template })
+}
+
+fn is_skipped(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut skipped = false;
+    for attr in attrs {
+        if !attr.path().is_ident(LOG_ATTRIBUTE) {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(SKIP) {
+                skipped = true;
+                return Ok(());
+            }
+            Err(meta.error("#[log] unknown field key. Expected skip."))
+        })?;
+    }
+    Ok(skipped)
+}
+
+pub(crate) fn generate_structural_log(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let config = parse_struct_config(input)?;
+
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(Error::new(input.span(), "#[derive(StructuralLog)] supports structs only."));
+    };
+
+    let syn::Fields::Named(fields) = &data.fields else {
+        if data.fields.is_empty() {
+            return generate_code(input, &config, &[]);
+        }
+        return Err(Error::new(data.fields.span(), "#[derive(StructuralLog)] requires named fields."));
+    };
+
+    let mut field_names = Vec::with_capacity(fields.named.len());
+    for field in &fields.named {
+        if is_skipped(&field.attrs)? {
+            continue;
+        }
+        field_names.push(field.ident.clone().unwrap());
+    }
+
+    generate_code(input, &config, &field_names)
+}
+
+fn validate(config: &StructConfig, field_names: &[Ident]) -> syn::Result<()> {
+    let parameters = template_parameters(&config.template)?;
+
+    for field in field_names {
+        if !parameters.contains(&field.unraw().to_string()) {
+            let msg = format!("Field [{field}] is not used in template. Use #[log(skip)] to ignore it.");
+            return Err(Error::new(field.span(), msg));
+        }
+    }
+
+    let names: HashSet<String> = field_names.iter().map(|field| field.unraw().to_string()).collect();
+    for parameter in &parameters {
+        if !names.contains(parameter) && !IMPLICIT_KEYS.contains(&parameter.as_str()) {
+            let msg = format!("Template parameter [{parameter}] does not match any field.");
+            return Err(Error::new(config.template.span(), msg));
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_code(input: &DeriveInput, config: &StructConfig, field_names: &[Ident])
+    -> syn::Result<TokenStream>
+{
+    validate(config, field_names)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let level = &config.level;
+    let template = &config.template;
+    let params_len = field_names.len();
+    let params_var = Ident::new("params", Span::mixed_site());
+
+    let args: Vec<(String, TokenStream)> = field_names
+        .iter()
+        .map(|field| (field.unraw().to_string(), quote! { ::core::clone::Clone::clone(&self.#field) }))
+        .collect();
+    let (statics, inserts) = generate_params(&params_var, &args);
+
+    Ok(quote! {
+        impl #impl_generics ::raf_structural_logging::traits::StructuralLog for #name #ty_generics #where_clause {
+            fn log_data(&self) -> ::raf_structural_logging::models::LogDataHolder {
+                static __SL_TEMPLATE: ::std::sync::LazyLock<::raf_structural_logging::template::Template>
+                    = ::std::sync::LazyLock::new(|| {
+                        ::raf_structural_logging::macro_helpers::template_builder().create_from_str(#template)
+                    });
+                #statics
+
+                let mut #params_var = ::std::collections::HashMap::with_capacity(#params_len);
+                #inserts
+                ::raf_structural_logging::models::LogDataHolder::new(
+                    ::raf_structural_logging::traits::LogLevel::#level,
+                    __SL_TEMPLATE.clone(),
+                    #params_var)
+            }
+        }
+    })
+}
@@ -0,0 +1,264 @@
+//! proc-macro project that holds logging macros and [`macro@StructuralLog`]
+//! derive for `raf_structural_logging`.
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+
+mod common;
+mod log_macro;
+mod derive_log;
+
+use derive_log::generate_structural_log;
+use log_macro::{generate_log, LogInput};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::{ParseStream, Parser}, parse_macro_input, DeriveInput};
+
+
+/// Logs through any `StructuralLogger` with explicitly passed log level.
+/// 
+/// # Example:
+/// 
+/// ```rust
+/// # use raf_structural_logging::{core::CoreLoggerFactoryBuilder, traits::*};
+/// # use raf_structural_logging_macros::sl_log;
+/// # let logger = CoreLoggerFactoryBuilder::default().build().create_from_str("app");
+/// # let name = "john";
+/// # let duration = std::time::Duration::from_millis(150);
+/// sl_log!(logger, LogLevel::Info, "User {user} logged in after {elapsed}",
+///     user = name,
+///     elapsed = duration);
+/// ```
+/// 
+/// The template is parsed at compile time and every `{param}` has to have
+/// a corresponding argument, except for `created_at`, `log_level` and
+/// `logger_name` which loggers attach to every log. Arguments can be passed
+/// either as `key = value` or as a sole identifier, which is equivalent to
+/// `key = key`. Each value has to implement `Into<SLObject>`.
+/// 
+/// The template and keys are created once and stored in statics. Arguments
+/// are evaluated only if the logger accepts given log level (see
+/// `StructuralLogger::is_enabled`).
+/// 
+/// # Compile errors:
+/// 
+/// Every template parameter needs an argument:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging::{core::CoreLoggerFactoryBuilder, traits::*};
+/// # use raf_structural_logging_macros::sl_log;
+/// # let logger = CoreLoggerFactoryBuilder::default().build().create_from_str("app");
+/// sl_log!(logger, LogLevel::Info, "User {user} logged in");
+/// ```
+/// 
+/// Every argument has to be used in the template:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging::{core::CoreLoggerFactoryBuilder, traits::*};
+/// # use raf_structural_logging_macros::sl_log;
+/// # let logger = CoreLoggerFactoryBuilder::default().build().create_from_str("app");
+/// sl_log!(logger, LogLevel::Info, "User logged in", user = "john");
+/// ```
+/// 
+/// Arguments cannot be repeated:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging::{core::CoreLoggerFactoryBuilder, traits::*};
+/// # use raf_structural_logging_macros::sl_log;
+/// # let logger = CoreLoggerFactoryBuilder::default().build().create_from_str("app");
+/// sl_log!(logger, LogLevel::Info, "User {user} logged in", user = "john", user = "jane");
+/// ```
+/// 
+/// Template cannot contain empty parameters:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging::{core::CoreLoggerFactoryBuilder, traits::*};
+/// # use raf_structural_logging_macros::sl_log;
+/// # let logger = CoreLoggerFactoryBuilder::default().build().create_from_str("app");
+/// sl_log!(logger, LogLevel::Info, "User {} logged in");
+/// ```
+#[proc_macro]
+pub fn sl_log(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let log_input = parse_macro_input!(input as LogInput);
+    generate_log(&log_input).into()
+}
+
+macro_rules! level_macro {
+    ( $name: ident, $level: ident ) => {
+        #[doc = concat!(
+            "Logs through any `StructuralLogger` at `LogLevel::",
+            stringify!($level),
+            "` level.\n\nEquivalent to `sl_log!(logger, LogLevel::",
+            stringify!($level),
+            ", template, args...)`, see [`macro@sl_log`].")]
+        #[proc_macro]
+        pub fn $name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+            let level: TokenStream = quote! {
+                ::raf_structural_logging::traits::LogLevel::$level
+            };
+            let parser = |stream: ParseStream| LogInput::parse_with_level(stream, level);
+            match parser.parse(input) {
+                Ok(log_input) => generate_log(&log_input).into(),
+                Err(err) => err.to_compile_error().into(),
+            }
+        }
+    };
+}
+
+level_macro!(sl_debug, Debug);
+level_macro!(sl_info, Info);
+level_macro!(sl_warning, Warning);
+level_macro!(sl_error, Error);
+
+/// Implements `StructuralLog` trait for decorated struct. Every field
+/// becomes a template parameter under its own name.
+/// 
+/// # Options:
+/// 
+/// The struct has to be decorated with `#[log(...)]` attribute with
+/// the following keys:
+/// * `level = Info` which sets `LogLevel` of generated logs. Has to be one
+///   of `Debug`, `Info`, `Warning` or `Error`.
+/// * `template = "..."` which sets the template of generated logs.
+/// 
+/// Fields decorated with `#[log(skip)]` are ignored. Remaining fields have
+/// to implement both `Clone` and `Into<SLObject>`.
+/// 
+/// The template is parsed at compile time. Each parameter has to match some
+/// field, except for `created_at`, `log_level` and `logger_name` which
+/// loggers attach to every log. Each field has to be used in the template.
+/// The template and keys are created once and stored in statics.
+/// 
+/// # Example:
+/// 
+/// ```rust
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped to {city}")]
+/// struct OrderShipped {
+///     id: i64,
+///     city: String,
+///     #[log(skip)]
+///     internal: u8,
+/// }
+/// ```
+/// 
+/// # Compile errors:
+/// 
+/// Each template parameter has to match a field that is not skipped:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped to {city}")]
+/// struct OrderShipped {
+///     id: i64,
+///     #[log(skip)]
+///     city: String,
+/// }
+/// ```
+/// 
+/// Each field that is not skipped has to be used in the template:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped")]
+/// struct OrderShipped {
+///     id: i64,
+///     city: String,
+/// }
+/// ```
+/// 
+/// Both `level` and `template` are required:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(template = "Order {id} shipped")]
+/// struct OrderShipped {
+///     id: i64,
+/// }
+/// ```
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info)]
+/// struct OrderShipped {
+///     id: i64,
+/// }
+/// ```
+/// 
+/// Level has to be a `LogLevel` variant:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Trace, template = "Order {id} shipped")]
+/// struct OrderShipped {
+///     id: i64,
+/// }
+/// ```
+/// 
+/// Unknown keys are rejected:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped", name = "order")]
+/// struct OrderShipped {
+///     id: i64,
+/// }
+/// ```
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped")]
+/// struct OrderShipped {
+///     #[log(rename = "order_id")]
+///     id: i64,
+/// }
+/// ```
+/// 
+/// Template cannot contain empty parameters:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {} shipped")]
+/// struct OrderShipped;
+/// ```
+/// 
+/// Only structs with named fields are supported:
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order {id} shipped")]
+/// struct OrderShipped(i64);
+/// ```
+/// 
+/// ```rust,compile_fail
+/// # use raf_structural_logging_macros::StructuralLog;
+/// #[derive(StructuralLog)]
+/// #[log(level = Info, template = "Order shipped")]
+/// enum OrderShipped {
+///     Now,
+/// }
+/// ```
+#[proc_macro_derive(StructuralLog, attributes(log))]
+pub fn derive_structural_log(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match generate_structural_log(&derive_input) {
+        Ok(stream) => stream.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use raf_structural_logging::macro_helpers::IMPLICIT_KEYS;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error,
+    Expr,
+    Ident,
+    LitStr,
+    Token};
+
+use crate::common::{generate_params, template_parameters};
+
+pub(crate) struct LogArg {
+    key: Ident,
+    value: Expr,
+}
+
+impl Parse for LogArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            input.parse::<Expr>()?
+        } else {
+            syn::parse2::<Expr>(key.to_token_stream())?
+        };
+        Ok(Self { key, value })
+    }
+}
+
+pub(crate) struct LogInput {
+    logger: Expr,
+    level: TokenStream,
+    template: LitStr,
+    args: Vec<LogArg>,
+}
+
+impl LogInput {
+    pub(crate) fn parse_with_level(input: ParseStream, level: TokenStream) -> syn::Result<Self> {
+        let logger: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        Self::parse_rest(input, logger, level)
+    }
+
+    fn parse_rest(input: ParseStream, logger: Expr, level: TokenStream) -> syn::Result<Self> {
+        let template: LitStr = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            args.push(input.parse::<LogArg>()?);
+        }
+        Ok(Self { logger, level, template, args })
+    }
+}
+
+impl Parse for LogInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let logger: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let level: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        Self::parse_rest(input, logger, level.to_token_stream())
+    }
+}
+
+fn validate(input: &LogInput) -> syn::Result<()> {
+    let parameters = template_parameters(&input.template)?;
+
+    let mut arg_keys = HashSet::with_capacity(input.args.len());
+    for arg in &input.args {
+        let key = arg.key.to_string();
+        if !arg_keys.insert(key.clone()) {
+            let msg = format!("Argument [{key}] passed more than once.");
+            return Err(Error::new(arg.key.span(), msg));
+        }
+        if !parameters.contains(&key) {
+            let msg = format!("Argument [{key}] is not used in template.");
+            return Err(Error::new(arg.key.span(), msg));
+        }
+    }
+
+    for parameter in &parameters {
+        if !arg_keys.contains(parameter) && !IMPLICIT_KEYS.contains(&parameter.as_str()) {
+            let msg = format!("Missing argument for template parameter [{parameter}].");
+            return Err(Error::new(input.template.span(), msg));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn generate_log(input: &LogInput) -> TokenStream {
+    if let Err(err) = validate(input) {
+        return err.to_compile_error();
+    }
+
+    let logger = &input.logger;
+    let level = &input.level;
+    let template = &input.template;
+    let params_len = input.args.len();
+
+    // Locals use mixed site span, so they don't clash with identifiers
+    // used in passed expressions.
+    let logger_var = Ident::new("logger", Span::mixed_site());
+    let level_var = Ident::new("level", Span::mixed_site());
+    let params_var = Ident::new("params", Span::mixed_site());
+
+    let args: Vec<(String, TokenStream)> = input.args
+        .iter()
+        .map(|arg| (arg.key.to_string(), arg.value.to_token_stream()))
+        .collect();
+    let (statics, inserts) = generate_params(&params_var, &args);
+
+    quote! {
+        {
+            use ::raf_structural_logging::traits::StructuralLogger as _;
+
+            let #logger_var = &(#logger);
+            let #level_var: ::raf_structural_logging::traits::LogLevel = #level;
+            if #logger_var.is_enabled(#level_var) {
+                static __SL_TEMPLATE: ::std::sync::LazyLock<::raf_structural_logging::template::Template>
+                    = ::std::sync::LazyLock::new(|| {
+                        ::raf_structural_logging::macro_helpers::template_builder().create_from_str(#template)
+                    });
+                #statics
+
+                let mut #params_var = ::std::collections::HashMap::with_capacity(#params_len);
+                #inserts
+                #logger_var.log(::raf_structural_logging::macro_helpers::MacroLog::new(
+                    #level_var,
+                    __SL_TEMPLATE.clone(),
+                    #params_var));
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    traits::{LogLevel, StructuralLog}};
+use raf_structural_logging_macros::StructuralLog;
+
+#[derive(StructuralLog)]
+#[log(level = Info, template = "Order {id} shipped to {city}")]
+pub struct OrderShipped {
+    id: i64,
+    city: String,
+}
+
+#[derive(StructuralLog)]
+#[log(level = LogLevel::Warning, template = "[{logger_name}] Slow {type} request: {elapsed}")]
+pub struct SlowRequest<T: Clone + Into<SLObject>> {
+    r#type: T,
+    elapsed: Duration,
+    #[log(skip)]
+    _attempts: u32,
+}
+
+#[derive(StructuralLog)]
+#[log(level = Debug, template = "Heartbeat")]
+pub struct Heartbeat;
+
+fn param<'a>(log: &'a LogDataHolder, key: &str) -> &'a SLObject {
+    let (_, value) = log.template_params()
+        .iter()
+        .find(|(k, _)| k.as_str() == key)
+        .unwrap();
+    value
+}
+
+#[test]
+fn test_derive() {
+    let event = OrderShipped { id: 15, city: "Warsaw".to_owned() };
+    let log = event.log_data();
+    assert_eq!(log.log_level(), LogLevel::Info);
+    assert_eq!(log.template().as_immutable_string().as_str(), "Order {id} shipped to {city}");
+    assert_eq!(log.template_params().len(), 2);
+    assert_eq!(param(&log, "id"), &SLObject::from(15i64));
+    assert_eq!(param(&log, "city"), &SLObject::from("Warsaw"));
+
+    let second = event.log_data();
+    assert_eq!(second.template(), log.template());
+    assert_eq!(second.template_params(), log.template_params());
+}
+
+#[test]
+fn test_derive_generic_with_skip() {
+    let event = SlowRequest {
+        r#type: "GET",
+        elapsed: Duration::from_millis(1500),
+        _attempts: 3,
+    };
+    let log = event.log_data();
+    assert_eq!(log.log_level(), LogLevel::Warning);
+    assert_eq!(log.template_params().len(), 2);
+    assert_eq!(param(&log, "type"), &SLObject::from("GET"));
+    assert_eq!(param(&log, "elapsed"), &SLObject::from(Duration::from_millis(1500)));
+}
+
+#[test]
+fn test_derive_unit() {
+    let log = Heartbeat.log_data();
+    assert_eq!(log.log_level(), LogLevel::Debug);
+    assert!(log.template_params().is_empty());
+}
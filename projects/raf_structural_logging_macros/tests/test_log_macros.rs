@@ -0,0 +1,99 @@
+use std::{cell::{Cell, RefCell}, time::Duration};
+
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    traits::{LogLevel, StructuralLog, StructuralLogger}};
+use raf_structural_logging_macros::{sl_debug, sl_error, sl_info, sl_log, sl_warning};
+
+pub struct TestLogger {
+    log_level: LogLevel,
+    logs: RefCell<Vec<LogDataHolder>>,
+}
+
+impl TestLogger {
+    pub fn new(log_level: LogLevel) -> Self {
+        Self { log_level, logs: RefCell::default() }
+    }
+}
+
+impl StructuralLogger for TestLogger {
+    fn log<T>(&self, log: T) where T : StructuralLog {
+        self.logs.borrow_mut().push(log.log_data());
+    }
+
+    fn is_enabled(&self, log_level: LogLevel) -> bool {
+        (log_level as i32) >= (self.log_level as i32)
+    }
+}
+
+fn param<'a>(log: &'a LogDataHolder, key: &str) -> &'a SLObject {
+    let (_, value) = log.template_params()
+        .iter()
+        .find(|(k, _)| k.as_str() == key)
+        .unwrap();
+    value
+}
+
+#[test]
+fn test_sl_info() {
+    let logger = TestLogger::new(LogLevel::Debug);
+    let name = "john";
+    let elapsed = Duration::from_millis(150);
+    sl_info!(logger, "User {user} logged in after {elapsed}", user = name, elapsed);
+
+    let logs = logger.logs.borrow();
+    assert_eq!(logs.len(), 1);
+    let log = &logs[0];
+    assert_eq!(log.log_level(), LogLevel::Info);
+    assert_eq!(log.template().as_immutable_string().as_str(), "User {user} logged in after {elapsed}");
+    assert_eq!(log.template_params().len(), 2);
+    assert_eq!(param(log, "user"), &SLObject::from("john"));
+    assert_eq!(param(log, "elapsed"), &SLObject::from(elapsed));
+}
+
+#[test]
+fn test_levels() {
+    let logger = TestLogger::new(LogLevel::Debug);
+    sl_debug!(logger, "debug");
+    sl_info!(&logger, "info");
+    sl_warning!(logger, "warning {value}", value = 5i64,);
+    sl_error!(logger, "[{log_level}] error {flag}", flag = true);
+    sl_log!(logger, LogLevel::Warning, "log {value}", value = -1i64);
+
+    let logs = logger.logs.borrow();
+    let levels: Vec<LogLevel> = logs.iter().map(LogDataHolder::log_level).collect();
+    assert_eq!(levels, [LogLevel::Debug, LogLevel::Info, LogLevel::Warning, LogLevel::Error, LogLevel::Warning]);
+    assert_eq!(param(&logs[2], "value"), &SLObject::from(5i64));
+    assert_eq!(param(&logs[3], "flag"), &SLObject::from(true));
+    assert_eq!(param(&logs[4], "value"), &SLObject::from(-1i64));
+}
+
+#[test]
+fn test_arguments_not_evaluated_below_level() {
+    let logger = TestLogger::new(LogLevel::Warning);
+    let counter = Cell::new(0);
+    let evaluate = || {
+        counter.set(counter.get() + 1);
+        counter.get()
+    };
+
+    sl_info!(logger, "Value {value}", value = i64::from(evaluate()));
+    assert_eq!(counter.get(), 0);
+    assert!(logger.logs.borrow().is_empty());
+
+    sl_error!(logger, "Value {value}", value = i64::from(evaluate()));
+    assert_eq!(counter.get(), 1);
+    assert_eq!(logger.logs.borrow().len(), 1);
+}
+
+#[test]
+fn test_no_clash_with_local_names() {
+    let logger = TestLogger::new(LogLevel::Debug);
+    let level = "custom";
+    let params = 7i64;
+    sl_info!(logger, "{level} {params}", level, params);
+
+    let logs = logger.logs.borrow();
+    assert_eq!(param(&logs[0], "level"), &SLObject::from("custom"));
+    assert_eq!(param(&logs[0], "params"), &SLObject::from(7i64));
+}
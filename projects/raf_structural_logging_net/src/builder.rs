@@ -0,0 +1,77 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use crate::{spill::SpillBuffer, LogTransport, ShippingHandler};
+
+pub const DEFAULT_MAX_BATCH_LEN: usize = 100;
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+pub const DEFAULT_MAX_SPILL_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Builder of [`ShippingHandler`]. By default batches hold up to
+/// [`DEFAULT_MAX_BATCH_LEN`] logs, backoff starts at
+/// [`DEFAULT_INITIAL_BACKOFF`] and doubles up to [`DEFAULT_MAX_BACKOFF`],
+/// and there is no spill file, so that logs that cannot be sent are
+/// dropped.
+pub struct ShippingHandlerBuilder<TTransport: LogTransport> {
+    transport: TTransport,
+    max_batch_len: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    spill_directory: Option<PathBuf>,
+    max_spill_size: u64,
+}
+
+impl<TTransport: LogTransport> ShippingHandlerBuilder<TTransport> {
+    pub fn new(transport: TTransport) -> Self {
+        Self {
+            transport: transport,
+            max_batch_len: DEFAULT_MAX_BATCH_LEN,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            spill_directory: None,
+            max_spill_size: DEFAULT_MAX_SPILL_SIZE,
+        }
+    }
+
+    /// Sets maximal number of logs in a batch, at least one.
+    pub fn set_max_batch_len(&mut self, max_batch_len: usize) {
+        self.max_batch_len = max_batch_len.max(1);
+    }
+
+    /// Sets delay after the first failure, doubled after each next
+    /// failure up to `max_backoff`.
+    pub fn set_backoff(&mut self, initial_backoff: Duration, max_backoff: Duration) {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+    }
+
+    /// Turns spilling on, with spill file kept in `spill_directory`.
+    pub fn set_spill_directory(&mut self, spill_directory: impl Into<PathBuf>) {
+        self.spill_directory = Some(spill_directory.into());
+    }
+
+    /// Sets maximal size of the spill file in bytes. Replayed logs take
+    /// space until all spilled logs are replayed.
+    pub fn set_max_spill_size(&mut self, max_spill_size: u64) {
+        self.max_spill_size = max_spill_size;
+    }
+
+    /// Builds [`ShippingHandler`], creating spill directory if needed.
+    /// Nothing is sent until the first batch is ready.
+    /// 
+    /// # Errors
+    /// * [`io::Error`] if spill directory cannot be created or
+    ///   the existing spill file cannot be inspected
+    pub fn build(self) -> Result<ShippingHandler<TTransport>, io::Error> {
+        let spill = match &self.spill_directory {
+            Some(directory) => Some(SpillBuffer::new(directory, self.max_spill_size)?),
+            None => None,
+        };
+        Ok(ShippingHandler::new(
+            self.transport,
+            self.max_batch_len,
+            self.initial_backoff,
+            self.max_backoff,
+            spill))
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicU64, Ordering}, Mutex, MutexGuard},
+    time::{Duration, Instant}};
+
+use raf_structural_logging::{
+    models::LogDataHolder,
+    traits::StructuralLogHandler};
+use raf_structural_logging_json::log_to_json_line;
+
+use crate::{spill::SpillBuffer, LogTransport};
+
+/// Exponential backoff between failed attempts.
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    fn can_attempt(&self) -> bool {
+        match self.retry_at {
+            Some(retry_at) => Instant::now() >= retry_at,
+            None => true,
+        }
+    }
+
+    fn succeeded(&mut self) {
+        self.current = Duration::ZERO;
+        self.retry_at = None;
+    }
+
+    fn failed(&mut self) {
+        self.current = if self.current.is_zero() {
+            self.initial
+        } else {
+            (self.current * 2).min(self.max)
+        };
+        self.retry_at = Some(Instant::now() + self.current);
+    }
+}
+
+struct ShippingState {
+    pending: Vec<String>,
+    spill: Option<SpillBuffer>,
+    backoff: Backoff,
+}
+
+/// Handler that encodes logs as JSON lines and ships them in batches
+/// through [`LogTransport`]. Use [`crate::ShippingHandlerBuilder`]
+/// to construct it.
+/// 
+/// Logs are kept in memory until there are enough of them to fill
+/// a batch, or until the handler is flushed. Batches that cannot be sent,
+/// including those created while the handler backs off, are appended to
+/// the spill file and replayed before the next batch. Logs that don't fit
+/// into the spill file, or all failed logs when there is no spill file,
+/// are dropped and counted, see [`ShippingHandler::dropped_logs`].
+pub struct ShippingHandler<TTransport: LogTransport> {
+    transport: TTransport,
+    max_batch_len: usize,
+    spill_path: Option<PathBuf>,
+    state: Mutex<ShippingState>,
+    dropped_logs: AtomicU64,
+}
+
+impl<TTransport: LogTransport> ShippingHandler<TTransport> {
+    pub(crate) fn new(
+        transport: TTransport,
+        max_batch_len: usize,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        spill: Option<SpillBuffer>) -> Self
+    {
+        let backoff = Backoff {
+            initial: initial_backoff,
+            max: max_backoff.max(initial_backoff),
+            current: Duration::ZERO,
+            retry_at: None,
+        };
+        Self {
+            transport: transport,
+            max_batch_len: max_batch_len,
+            spill_path: spill.as_ref().map(|spill| spill.path().to_path_buf()),
+            state: Mutex::new(ShippingState {
+                pending: Vec::with_capacity(max_batch_len),
+                spill: spill,
+                backoff: backoff,
+            }),
+            dropped_logs: AtomicU64::new(0),
+        }
+    }
+
+    #[inline(always)]
+    pub fn transport(&self) -> &TTransport { &self.transport }
+
+    /// Returns path of the spill file, if spilling is turned on.
+    #[inline(always)]
+    pub fn spill_path(&self) -> Option<&Path> { self.spill_path.as_deref() }
+
+    /// Returns number of logs that were neither shipped nor spilled.
+    #[inline(always)]
+    pub fn dropped_logs(&self) -> u64 { self.dropped_logs.load(Ordering::Relaxed) }
+
+    /// Returns current delay between attempts, zero when the last attempt
+    /// succeeded.
+    pub fn backoff(&self) -> Duration { self.lock().backoff.current }
+
+    fn lock(&self) -> MutexGuard<'_, ShippingState> {
+        self.state.lock().expect("ShippingHandler - lock() fail")
+    }
+
+    fn push(&self, state: &mut ShippingState, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+        state.pending.push(log_to_json_line(log));
+        if state.pending.len() >= self.max_batch_len {
+            self.ship(state);
+        }
+    }
+
+    /// Sends pending logs, preceded by spilled ones, unless the handler
+    /// backs off. Whatever is not sent is spilled.
+    fn ship(&self, state: &mut ShippingState) {
+        let batch = std::mem::take(&mut state.pending);
+        let is_sent = state.backoff.can_attempt()
+            && self.replay(state)
+            && (batch.is_empty() || self.send(&mut state.backoff, &batch));
+        if !is_sent {
+            self.spill(state, &batch);
+        }
+    }
+
+    fn send(&self, backoff: &mut Backoff, lines: &[String]) -> bool {
+        let mut payload = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+        for line in lines {
+            payload.push_str(line);
+            payload.push('\n');
+        }
+        if self.transport.send(payload.as_bytes()).is_ok() {
+            backoff.succeeded();
+            true
+        } else {
+            backoff.failed();
+            false
+        }
+    }
+
+    /// Sends spilled logs in batches, reading one batch at a time. Returns
+    /// `false` if some of them couldn't be sent, in which case they stay
+    /// spilled and the next replay starts with the first of them.
+    fn replay(&self, state: &mut ShippingState) -> bool {
+        let Some(spill) = state.spill.as_mut() else { return true };
+        while !spill.is_empty() {
+            // New logs are spilled behind unreadable ones to keep the order.
+            let Ok((lines, len)) = spill.read_batch(self.max_batch_len) else { return false };
+            if len == 0 {
+                // The file was truncated by someone else.
+                let _ = spill.clear();
+                return true;
+            }
+            if !lines.is_empty() && !self.send(&mut state.backoff, &lines) {
+                return false;
+            }
+            // If the offset cannot be stored, at worst some lines are
+            // shipped twice after restart.
+            let _ = spill.consume(len);
+        }
+        true
+    }
+
+    fn spill(&self, state: &mut ShippingState, lines: &[String]) {
+        let spilled = match state.spill.as_mut() {
+            Some(spill) => spill.append(lines).unwrap_or(0),
+            None => 0,
+        };
+        let dropped = lines.len() - spilled;
+        if dropped > 0 {
+            self.dropped_logs.fetch_add(dropped as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<TTransport: LogTransport> StructuralLogHandler for ShippingHandler<TTransport> {
+    fn handle(&self, log: &LogDataHolder) {
+        let mut state = self.lock();
+        self.push(&mut state, log);
+    }
+
+    fn handle_batch(&self, logs: &[LogDataHolder]) {
+        let mut state = self.lock();
+        for log in logs {
+            self.push(&mut state, log);
+        }
+    }
+
+    /// Ships pending logs, or only replays spilled ones if there are
+    /// no pending logs.
+    fn flush(&self) {
+        let mut state = self.lock();
+        self.ship(&mut state);
+    }
+}
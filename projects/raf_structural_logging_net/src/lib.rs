@@ -0,0 +1,34 @@
+//! Implements structural logging handler that ships logs to a central
+//! collector, see [`ShippingHandler`].
+//! 
+//! Logs are encoded as JSON lines, in the format of
+//! `raf_structural_logging_json`, grouped into batches and passed to
+//! a pluggable [`LogTransport`]. Two transports are provided:
+//! * [`TcpLineTransport`] writes batches into a long-lived TCP connection
+//! * [`HttpTransport`] posts each batch as `application/x-ndjson` body
+//!   of an HTTP/1.1 request
+//! 
+//! When a batch cannot be sent, the handler backs off exponentially and
+//! meanwhile appends batches to a spill file on disk, if configured. On
+//! the next successful attempt spilled logs are replayed first, so that
+//! the collector receives logs in order. Spilled logs are read and sent
+//! one batch at a time, and the position of the first log not sent yet is
+//! stored next to the spill file. Both survive restarts of the application,
+//! so that the next handler using the same directory continues the replay.
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+pub(crate) mod spill;
+
+mod transport;
+mod builder;
+mod handler;
+pub use transport::*;
+pub use builder::*;
+pub use handler::*;
@@ -0,0 +1,135 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf}};
+
+/// Name of the spill file inside spill directory.
+pub(crate) const SPILL_FILE_NAME: &str = "spill.jsonl";
+
+/// Name of the file that holds number of bytes of the spill file already
+/// replayed.
+pub(crate) const OFFSET_FILE_NAME: &str = "spill.offset";
+
+/// Keeps lines that couldn't be shipped in a file, up to `max_size` bytes.
+/// Lines are replayed in batches read from the stored offset, which
+/// survives restarts. The file is truncated once all lines are replayed.
+pub(crate) struct SpillBuffer {
+    path: PathBuf,
+    offset_path: PathBuf,
+    max_size: u64,
+    size: u64,
+    offset: u64,
+}
+
+impl SpillBuffer {
+    /// Creates `directory` if needed. Lines left in the file by previous
+    /// runs are kept.
+    pub(crate) fn new(directory: &Path, max_size: u64) -> Result<Self, io::Error> {
+        fs::create_dir_all(directory)?;
+        let path = directory.join(SPILL_FILE_NAME);
+        let size = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+        let offset_path = directory.join(OFFSET_FILE_NAME);
+        // Missing or damaged offset means that lines are replayed from
+        // the beginning, at worst some of them twice.
+        let offset = fs::read_to_string(&offset_path)
+            .ok()
+            .and_then(|text| text.trim().parse::<u64>().ok())
+            .filter(|offset| *offset <= size)
+            .unwrap_or(0);
+        Ok(Self {
+            path: path,
+            offset_path: offset_path,
+            max_size: max_size,
+            size: size,
+            offset: offset,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn path(&self) -> &Path { &self.path }
+
+    /// Returns `true` if there are no lines left to replay.
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool { self.offset >= self.size }
+
+    /// Appends `lines` in order, as long as they fit. Returns number of
+    /// appended lines.
+    pub(crate) fn append(&mut self, lines: &[String]) -> Result<usize, io::Error> {
+        let mut content = String::new();
+        let mut count = 0;
+        for line in lines {
+            let new_size = self.size + (content.len() + line.len() + 1) as u64;
+            if new_size > self.max_size {
+                break;
+            }
+            content.push_str(line);
+            content.push('\n');
+            count += 1;
+        }
+        if count > 0 {
+            let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            if let Err(err) = file.write_all(content.as_bytes()).and_then(|()| file.flush()) {
+                // Drops partially written lines, so that the file matches `size`.
+                if file.set_len(self.size).is_err() {
+                    self.size = file.metadata().map_or(self.size, |metadata| metadata.len());
+                }
+                return Err(err);
+            }
+            self.size += content.len() as u64;
+        }
+        Ok(count)
+    }
+
+    /// Reads up to `max_len` lines following the offset. Returns the lines
+    /// together with the number of bytes they took, to be passed to
+    /// [`SpillBuffer::consume`] once the lines are shipped.
+    pub(crate) fn read_batch(&self, max_len: usize) -> Result<(Vec<String>, u64), io::Error> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+            Err(err) => return Err(err),
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut reader = BufReader::new(file);
+        let mut lines = Vec::with_capacity(max_len);
+        let mut consumed = 0;
+        let mut line = String::new();
+        while lines.len() < max_len {
+            line.clear();
+            let len = reader.read_line(&mut line)?;
+            if len == 0 {
+                break;
+            }
+            consumed += len as u64;
+            let trimmed = line.trim_end_matches('\n');
+            if !trimmed.is_empty() {
+                lines.push(trimmed.to_owned());
+            }
+        }
+        Ok((lines, consumed))
+    }
+
+    /// Marks `len` bytes following the offset as replayed.
+    pub(crate) fn consume(&mut self, len: u64) -> Result<(), io::Error> {
+        self.offset += len;
+        if self.is_empty() {
+            return self.clear();
+        }
+        fs::write(&self.offset_path, self.offset.to_string())
+    }
+
+    /// Drops all lines.
+    pub(crate) fn clear(&mut self) -> Result<(), io::Error> {
+        self.size = 0;
+        self.offset = 0;
+        File::create(&self.path)?;
+        match fs::remove_file(&self.offset_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpStream},
+    sync::Mutex,
+    time::Duration};
+
+/// Default timeout of connecting, reading and writing used by provided
+/// transports.
+pub const DEFAULT_NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delivers batches of logs to the collector.
+pub trait LogTransport : Sync + Send {
+    /// Sends `payload` made of JSON lines, each terminated with `\n`.
+    /// 
+    /// # Errors
+    /// * [`io::Error`] if the collector didn't accept the payload, in which
+    ///   case the whole payload is sent again later
+    fn send(&self, payload: &[u8]) -> Result<(), io::Error>;
+}
+
+impl<T: LogTransport + ?Sized> LogTransport for std::sync::Arc<T> {
+    fn send(&self, payload: &[u8]) -> Result<(), io::Error> {
+        (**self).send(payload)
+    }
+}
+
+fn connect(address: &SocketAddr, timeout: Duration) -> Result<TcpStream, io::Error> {
+    let stream = TcpStream::connect_timeout(address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    Ok(stream)
+}
+
+/// Writes payloads as they are into a single TCP connection, established
+/// lazily and reestablished after failures.
+pub struct TcpLineTransport {
+    address: SocketAddr,
+    timeout: Duration,
+    stream: Mutex<Option<TcpStream>>,
+}
+
+impl TcpLineTransport {
+    pub fn new(address: SocketAddr) -> Self {
+        Self::with_timeout(address, DEFAULT_NETWORK_TIMEOUT)
+    }
+
+    pub fn with_timeout(address: SocketAddr, timeout: Duration) -> Self {
+        Self {
+            address: address,
+            timeout: timeout,
+            stream: Mutex::new(None),
+        }
+    }
+
+    #[inline(always)]
+    pub fn address(&self) -> SocketAddr { self.address }
+}
+
+impl LogTransport for TcpLineTransport {
+    fn send(&self, payload: &[u8]) -> Result<(), io::Error> {
+        let mut guard = self.stream.lock().expect("TcpLineTransport - lock() fail");
+        if guard.is_none() {
+            *guard = Some(connect(&self.address, self.timeout)?);
+        }
+        let stream = guard.as_mut().expect("TcpLineTransport - missing stream");
+        let result = stream.write_all(payload).and_then(|()| stream.flush());
+        if result.is_err() {
+            *guard = None;
+        }
+        result
+    }
+}
+
+/// Posts each payload as `application/x-ndjson` body of a separate
+/// HTTP/1.1 request. Payload is accepted when the collector responds
+/// with `2xx` status.
+pub struct HttpTransport {
+    address: SocketAddr,
+    host: String,
+    path: String,
+    timeout: Duration,
+}
+
+impl HttpTransport {
+    /// Creates transport posting to `path` (e.g. `/logs`) on `address`.
+    /// `Host` header is set to `address`.
+    pub fn new(address: SocketAddr, path: &str) -> Self {
+        Self {
+            address: address,
+            host: address.to_string(),
+            path: path.to_owned(),
+            timeout: DEFAULT_NETWORK_TIMEOUT,
+        }
+    }
+
+    pub fn set_host(&mut self, host: &str) {
+        host.clone_into(&mut self.host);
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    #[inline(always)]
+    pub fn address(&self) -> SocketAddr { self.address }
+}
+
+impl LogTransport for HttpTransport {
+    fn send(&self, payload: &[u8]) -> Result<(), io::Error> {
+        let mut stream = connect(&self.address, self.timeout)?;
+        let header = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/x-ndjson\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            self.host,
+            payload.len());
+        stream.write_all(header.as_bytes())?;
+        stream.write_all(payload)?;
+        stream.flush()?;
+
+        let mut status_line = String::new();
+        BufReader::new(stream).read_line(&mut status_line)?;
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid HTTP response."))?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("Collector responded with HTTP status {status}.")))
+        }
+    }
+}
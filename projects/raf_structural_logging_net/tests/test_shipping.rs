@@ -0,0 +1,397 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener},
+    sync::{atomic::{AtomicUsize, Ordering}, Arc, LazyLock, Mutex},
+    thread,
+    time::{Duration, Instant}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactoryBuilder, DispatchMode},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use raf_structural_logging_net::{
+    HttpTransport,
+    LogTransport,
+    ShippingHandler,
+    ShippingHandlerBuilder,
+    TcpLineTransport};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+fn build_log(idx: i64) -> LogDataHolder {
+    let mut params = HashMap::new();
+    params.insert(ImmutableString::new("idx").unwrap(), SLObject::from(idx));
+    LogDataHolder::new(LogLevel::Info, TMPL_BUILDER.create_from_str("Log {idx}"), params)
+}
+
+struct TestLog(i64);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder { build_log(self.0) }
+}
+
+/// Extracts rendered messages out of JSON lines.
+fn messages(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            let start = line.find("\"message\":\"").unwrap() + 11;
+            let len = line[start..].find('"').unwrap();
+            line[start..start + len].to_owned()
+        })
+        .collect()
+}
+
+fn expected(indexes: impl IntoIterator<Item = i64>) -> Vec<String> {
+    indexes.into_iter().map(|idx| format!("Log {idx}")).collect()
+}
+
+fn wait_for<F>(condition: F) -> bool
+    where F: Fn() -> bool
+{
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+/// Returns address that nothing listens on, at least for now.
+fn free_address() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Stand-in of the collector that accepts JSON lines over TCP.
+fn start_line_server(listener: TcpListener) -> Arc<Mutex<Vec<String>>> {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let result = lines.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let lines = lines.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stream.unwrap()).lines() {
+                    let Ok(line) = line else { return };
+                    lines.lock().unwrap().push(line);
+                }
+            });
+        }
+    });
+    result
+}
+
+/// Request line, content type and body of HTTP request.
+type HttpRequest = (String, String, String);
+
+/// Stand-in of the collector that accepts HTTP requests, responding with
+/// queued statuses and then with 200. Records request line, content type
+/// and body of accepted requests.
+fn start_http_server(statuses: Vec<u16>) -> (SocketAddr, Arc<Mutex<Vec<HttpRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let result = requests.clone();
+    let mut statuses: VecDeque<u16> = statuses.into();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_type = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(": ").unwrap();
+                match name {
+                    "Content-Type" => content_type = value.to_owned(),
+                    "Content-Length" => content_length = value.parse().unwrap(),
+                    _ => { },
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let status = statuses.pop_front().unwrap_or(200);
+            if status == 200 {
+                requests.lock().unwrap().push((
+                    request_line.trim_end().to_owned(),
+                    content_type,
+                    String::from_utf8(body).unwrap()));
+            }
+            write!(stream, "HTTP/1.1 {status} Status\r\nContent-Length: 0\r\n\r\n").unwrap();
+        }
+    });
+    (address, result)
+}
+
+fn spill_lines<T: LogTransport>(handler: &ShippingHandler<T>) -> Vec<String> {
+    fs::read_to_string(handler.spill_path().unwrap())
+        .unwrap()
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+#[test]
+fn test_tcp_batches() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let lines = start_line_server(listener);
+
+    let mut builder = ShippingHandlerBuilder::new(TcpLineTransport::new(address));
+    builder.set_max_batch_len(3);
+    let handler = Arc::new(builder.build().unwrap());
+    let mut factory_builder = CoreLoggerFactoryBuilder::default();
+    factory_builder.set_dispatch_mode(DispatchMode::Synchronous);
+    factory_builder.add_handler(handler.clone());
+    let factory = factory_builder.build();
+    let logger = factory.create_from_str("shipping");
+
+    for idx in 0..7 {
+        logger.log(TestLog(idx));
+    }
+    assert!(wait_for(|| lines.lock().unwrap().len() == 6));
+    factory.flush();
+    assert!(wait_for(|| lines.lock().unwrap().len() == 7));
+    assert_eq!(messages(&lines.lock().unwrap()), expected(0..7));
+    assert_eq!(handler.dropped_logs(), 0);
+    assert!(handler.spill_path().is_none());
+}
+
+#[test]
+fn test_spill_and_replay() {
+    let address = free_address();
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = ShippingHandlerBuilder::new(TcpLineTransport::new(address));
+    builder.set_backoff(Duration::from_millis(200), Duration::from_secs(1));
+    builder.set_spill_directory(dir.path());
+    let handler = builder.build().unwrap();
+
+    handler.handle(&build_log(0));
+    handler.handle(&build_log(1));
+    handler.flush();
+    assert_eq!(handler.backoff(), Duration::from_millis(200));
+    assert_eq!(messages(&spill_lines(&handler)), expected(0..2));
+
+    // Still backing off, so the log is spilled right away.
+    handler.handle(&build_log(2));
+    handler.flush();
+    assert_eq!(handler.backoff(), Duration::from_millis(200));
+    assert_eq!(messages(&spill_lines(&handler)), expected(0..3));
+
+    let lines = start_line_server(TcpListener::bind(address).unwrap());
+    thread::sleep(Duration::from_millis(250));
+    handler.handle(&build_log(3));
+    handler.flush();
+    assert!(wait_for(|| lines.lock().unwrap().len() == 4));
+    assert_eq!(messages(&lines.lock().unwrap()), expected(0..4));
+    assert!(spill_lines(&handler).is_empty());
+    assert_eq!(handler.backoff(), Duration::ZERO);
+    assert_eq!(handler.dropped_logs(), 0);
+}
+
+#[test]
+fn test_spill_survives_restart() {
+    let address = free_address();
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let mut builder = ShippingHandlerBuilder::new(TcpLineTransport::new(address));
+        builder.set_spill_directory(dir.path());
+        let handler = builder.build().unwrap();
+        handler.handle(&build_log(0));
+        handler.flush();
+        assert_eq!(spill_lines(&handler).len(), 1);
+    }
+
+    let lines = start_line_server(TcpListener::bind(address).unwrap());
+    let mut builder = ShippingHandlerBuilder::new(TcpLineTransport::new(address));
+    builder.set_spill_directory(dir.path());
+    let handler = builder.build().unwrap();
+    handler.handle(&build_log(1));
+    handler.flush();
+    assert!(wait_for(|| lines.lock().unwrap().len() == 2));
+    assert_eq!(messages(&lines.lock().unwrap()), expected(0..2));
+}
+
+#[derive(Default)]
+struct FailingTransport {
+    attempts: AtomicUsize,
+}
+
+impl LogTransport for FailingTransport {
+    fn send(&self, _payload: &[u8]) -> Result<(), io::Error> {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        Err(io::Error::from(io::ErrorKind::ConnectionRefused))
+    }
+}
+
+#[test]
+fn test_backoff() {
+    let transport = Arc::new(FailingTransport::default());
+    let attempts = || transport.attempts.load(Ordering::SeqCst);
+    let mut builder = ShippingHandlerBuilder::new(transport.clone());
+    builder.set_max_batch_len(1);
+    builder.set_backoff(Duration::from_millis(50), Duration::from_millis(120));
+    let handler = builder.build().unwrap();
+
+    handler.handle(&build_log(0));
+    assert_eq!((attempts(), handler.backoff()), (1, Duration::from_millis(50)));
+    handler.handle(&build_log(1));
+    assert_eq!((attempts(), handler.backoff()), (1, Duration::from_millis(50)));
+
+    thread::sleep(Duration::from_millis(60));
+    handler.handle(&build_log(2));
+    assert_eq!((attempts(), handler.backoff()), (2, Duration::from_millis(100)));
+
+    thread::sleep(Duration::from_millis(110));
+    handler.handle(&build_log(3));
+    assert_eq!((attempts(), handler.backoff()), (3, Duration::from_millis(120)));
+
+    // Without spill file all logs are dropped.
+    assert_eq!(handler.dropped_logs(), 4);
+}
+
+/// Accepts only the first `accepted` payloads, recording their lines.
+#[derive(Default)]
+struct LimitedTransport {
+    accepted: AtomicUsize,
+    lines: Mutex<Vec<String>>,
+}
+
+impl LogTransport for LimitedTransport {
+    fn send(&self, payload: &[u8]) -> Result<(), io::Error> {
+        let accepted = self.accepted.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| count.checked_sub(1));
+        if accepted.is_err() {
+            return Err(io::Error::from(io::ErrorKind::ConnectionRefused));
+        }
+        let payload = std::str::from_utf8(payload).unwrap();
+        self.lines.lock().unwrap().extend(payload.lines().map(ToOwned::to_owned));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_partial_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let transport = Arc::new(LimitedTransport::default());
+    let build = || {
+        let mut builder = ShippingHandlerBuilder::new(transport.clone());
+        builder.set_max_batch_len(2);
+        builder.set_backoff(Duration::ZERO, Duration::ZERO);
+        builder.set_spill_directory(dir.path());
+        builder.build().unwrap()
+    };
+    {
+        let handler = build();
+        for idx in 0..5 {
+            handler.handle(&build_log(idx));
+        }
+        handler.flush();
+        assert_eq!(messages(&spill_lines(&handler)), expected(0..5));
+
+        // Only the first batch of spilled logs gets through.
+        transport.accepted.store(1, Ordering::SeqCst);
+        handler.flush();
+        assert_eq!(messages(&transport.lines.lock().unwrap()), expected(0..2));
+    }
+
+    // The next handler continues where the previous one stopped.
+    let handler = build();
+    transport.accepted.store(10, Ordering::SeqCst);
+    handler.handle(&build_log(5));
+    handler.flush();
+    assert_eq!(messages(&transport.lines.lock().unwrap()), expected(0..6));
+    assert!(spill_lines(&handler).is_empty());
+    assert_eq!(handler.dropped_logs(), 0);
+}
+
+#[test]
+fn test_unreadable_spill_keeps_order() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("spill.jsonl"), b"\xff\n").unwrap();
+    let transport = Arc::new(LimitedTransport::default());
+    transport.accepted.store(10, Ordering::SeqCst);
+    let mut builder = ShippingHandlerBuilder::new(transport.clone());
+    builder.set_spill_directory(dir.path());
+    let handler = builder.build().unwrap();
+
+    handler.handle(&build_log(0));
+    handler.flush();
+    assert!(transport.lines.lock().unwrap().is_empty());
+    let content = fs::read(handler.spill_path().unwrap()).unwrap();
+    let spilled = String::from_utf8_lossy(&content[2..]).lines().map(ToOwned::to_owned).collect::<Vec<_>>();
+    assert_eq!(messages(&spilled), expected(0..1));
+    assert_eq!(handler.dropped_logs(), 0);
+}
+
+#[test]
+fn test_max_spill_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = ShippingHandlerBuilder::new(FailingTransport::default());
+    builder.set_spill_directory(dir.path());
+    builder.set_max_batch_len(1);
+    builder.set_max_spill_size(400);
+    let handler = builder.build().unwrap();
+    for idx in 0..10 {
+        handler.handle(&build_log(idx));
+    }
+
+    let spilled = spill_lines(&handler);
+    assert!(!spilled.is_empty() && spilled.len() < 10, "{}", spilled.len());
+    assert!(fs::metadata(handler.spill_path().unwrap()).unwrap().len() <= 400);
+    assert_eq!(handler.dropped_logs(), 10 - spilled.len() as u64);
+    assert_eq!(messages(&spilled), expected(0..spilled.len() as i64));
+}
+
+#[test]
+fn test_http() {
+    let (address, requests) = start_http_server(vec![503]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = ShippingHandlerBuilder::new(HttpTransport::new(address, "/logs"));
+    builder.set_max_batch_len(2);
+    builder.set_backoff(Duration::from_millis(20), Duration::from_secs(1));
+    builder.set_spill_directory(dir.path());
+    let handler = builder.build().unwrap();
+
+    handler.handle(&build_log(0));
+    handler.handle(&build_log(1));
+    assert_eq!(handler.backoff(), Duration::from_millis(20));
+    assert!(requests.lock().unwrap().is_empty());
+
+    thread::sleep(Duration::from_millis(30));
+    handler.handle(&build_log(2));
+    handler.flush();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    for (request_line, content_type, _) in &requests {
+        assert_eq!(request_line, "POST /logs HTTP/1.1");
+        assert_eq!(content_type, "application/x-ndjson");
+    }
+    let bodies: Vec<Vec<String>> = requests
+        .iter()
+        .map(|(_, _, body)| messages(&body.lines().map(ToOwned::to_owned).collect::<Vec<_>>()))
+        .collect();
+    assert_eq!(bodies, vec![expected(0..2), expected(2..3)]);
+    assert!(spill_lines(&handler).is_empty());
+    assert_eq!(handler.dropped_logs(), 0);
+}
@@ -0,0 +1,98 @@
+use std::sync::Mutex;
+
+use raf_structural_logging::{
+    models::LogDataHolder,
+    traits::StructuralLogHandler};
+
+use crate::OtlpLogRecord;
+
+/// Sends [`OtlpLogRecord`]s to their destination, e.g. an OTLP collector.
+pub trait LogExporter : Sync + Send {
+    fn export(&self, records: &[OtlpLogRecord]);
+
+    /// Makes sure all exported records reach their destination.
+    fn flush(&self) { }
+}
+
+/// Exporter that keeps records in memory, useful for tests.
+#[derive(Default)]
+pub struct InMemoryExporter {
+    records: Mutex<Vec<OtlpLogRecord>>,
+}
+
+impl InMemoryExporter {
+    /// Returns copy of all exported records.
+    /// 
+    /// # Panics
+    /// Only when internal lock is poisoned.
+    pub fn records(&self) -> Vec<OtlpLogRecord> {
+        self.records.lock().expect("InMemoryExporter - lock() fail").clone()
+    }
+
+    /// Removes and returns all exported records.
+    /// 
+    /// # Panics
+    /// Only when internal lock is poisoned.
+    pub fn take_records(&self) -> Vec<OtlpLogRecord> {
+        let mut guard = self.records.lock().expect("InMemoryExporter - lock() fail");
+        core::mem::take(&mut *guard)
+    }
+}
+
+impl LogExporter for InMemoryExporter {
+    fn export(&self, records: &[OtlpLogRecord]) {
+        let mut guard = self.records.lock().expect("InMemoryExporter - lock() fail");
+        guard.extend_from_slice(records);
+    }
+}
+
+impl<T: LogExporter + ?Sized> LogExporter for std::sync::Arc<T> {
+    fn export(&self, records: &[OtlpLogRecord]) {
+        (**self).export(records);
+    }
+
+    fn flush(&self) {
+        (**self).flush();
+    }
+}
+
+/// Handler that maps logs into [`OtlpLogRecord`]s and passes them
+/// to [`LogExporter`]. Batches are exported with a single call.
+pub struct OtlpHandler<TExporter: LogExporter> {
+    exporter: TExporter,
+}
+
+impl<TExporter: LogExporter> OtlpHandler<TExporter> {
+    pub fn new(exporter: TExporter) -> Self {
+        Self { exporter }
+    }
+
+    #[inline(always)]
+    pub fn exporter(&self) -> &TExporter { &self.exporter }
+}
+
+impl<TExporter: LogExporter> StructuralLogHandler for OtlpHandler<TExporter> {
+    fn handle(&self, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+
+        let record = OtlpLogRecord::from_log(log);
+        self.exporter.export(core::slice::from_ref(&record));
+    }
+
+    fn handle_batch(&self, logs: &[LogDataHolder]) {
+        let records: Vec<OtlpLogRecord> = logs
+            .iter()
+            .filter(|log| !log.is_empty())
+            .map(OtlpLogRecord::from_log)
+            .collect();
+        if !records.is_empty() {
+            self.exporter.export(&records);
+        }
+    }
+
+    fn flush(&self) {
+        self.exporter.flush();
+    }
+}
@@ -0,0 +1,74 @@
+use core::fmt::{Display, Formatter};
+
+/// Identifies a trace, 16 random bytes that are never all zeros.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct TraceId {
+    value: [u8; 16],
+}
+
+/// Identifies a span within a trace, 8 random bytes that are never
+/// all zeros.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct SpanId {
+    value: [u8; 8],
+}
+
+macro_rules! impl_id {
+    ( $name: ident, $size: literal ) => {
+        impl $name {
+            /// Generates new random id.
+            /// 
+            /// # Panics
+            /// When system's source of randomness fails.
+            pub fn generate() -> Self {
+                let mut value = [0u8; $size];
+                while value.iter().all(|byte| *byte == 0) {
+                    getrandom::getrandom(&mut value)
+                        .expect(concat!(stringify!($name), " - getrandom() fail"));
+                }
+                Self { value: value }
+            }
+
+            /// Returns `None` if `value` is all zeros, which is not a valid id.
+            pub fn from_bytes(value: [u8; $size]) -> Option<Self> {
+                if value.iter().all(|byte| *byte == 0) {
+                    None
+                } else {
+                    Some(Self { value: value })
+                }
+            }
+
+            /// Parses id out of lowercase or uppercase hex string.
+            pub fn from_hex(text: &str) -> Option<Self> {
+                if text.len() != 2 * $size || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                    return None;
+                }
+                let mut value = [0u8; $size];
+                for (idx, byte) in value.iter_mut().enumerate() {
+                    *byte = u8::from_str_radix(&text[2*idx..2*idx+2], 16).ok()?;
+                }
+                Self::from_bytes(value)
+            }
+
+            #[inline(always)]
+            pub fn as_bytes(&self) -> &[u8; $size] { &self.value }
+
+            /// Returns lowercase hex representation of the id.
+            pub fn to_hex(&self) -> String {
+                self.to_string()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                for byte in &self.value {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_id!(TraceId, 16);
+impl_id!(SpanId, 8);
@@ -0,0 +1,29 @@
+//! Implements spans and trace correlation on top of `raf_structural_logging`,
+//! together with OpenTelemetry-style (OTLP) mapping and export of logs.
+//! 
+//! [`Span`] wraps any `StructuralLogger` and stamps `trace_id`, `span_id`
+//! and `parent_span_id` (as lowercase hex strings) into `additional_data`
+//! of every log sent through it. When a span ends, it sends a log with
+//! `span_name` and `elapsed` template parameters, the latter holding
+//! span's duration.
+//! 
+//! [`OtlpLogRecord`] maps `LogDataHolder` into a structure following
+//! the OTLP log data model, and [`OtlpHandler`] passes such records to
+//! a pluggable [`LogExporter`].
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+mod ids;
+mod span;
+mod record;
+mod exporter;
+pub use ids::*;
+pub use span::*;
+pub use record::*;
+pub use exporter::*;
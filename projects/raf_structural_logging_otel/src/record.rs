@@ -0,0 +1,181 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::render_message,
+    traits::LogLevel};
+
+use crate::{span_id_key, trace_id_key, SpanId, TraceId};
+
+/// Attribute under which raw template of the log is stored.
+pub const TEMPLATE_ATTRIBUTE: &str = "log.template";
+
+/// Value of an attribute, mirrors OTLP `AnyValue`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum OtlpValue {
+    Empty,
+    String(String),
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    Bytes(Vec<u8>),
+    Array(Vec<OtlpValue>),
+    KeyValueList(Vec<(String, OtlpValue)>),
+}
+
+/// Log record following OTLP log data model.
+#[derive(PartialEq, Clone, Debug)]
+pub struct OtlpLogRecord {
+    pub time_unix_nano: u64,
+    pub observed_time_unix_nano: u64,
+    pub severity_number: u8,
+    pub severity_text: &'static str,
+    pub body: String,
+    pub attributes: Vec<(String, OtlpValue)>,
+    pub trace_id: Option<TraceId>,
+    pub span_id: Option<SpanId>,
+    pub scope_name: Option<String>,
+}
+
+impl OtlpLogRecord {
+    /// Maps `log` into OTLP record:
+    /// * rendered message becomes `body`
+    /// * [`LogLevel`] becomes `severity_number` and `severity_text`
+    /// * `trace_id` and `span_id` are parsed out of `additional_data`
+    /// * [`LogDataHolder::logger_name`] becomes `scope_name`
+    /// * raw template is stored as [`TEMPLATE_ATTRIBUTE`] attribute
+    /// * remaining template params and additional data become attributes,
+    ///   sorted by key
+    pub fn from_log(log: &LogDataHolder) -> Self {
+        let additional_data = log.additional_data();
+        let trace_id = get_str(log, trace_id_key()).and_then(TraceId::from_hex);
+        let span_id = get_str(log, span_id_key()).and_then(SpanId::from_hex);
+        let scope_name = log.logger_name()
+            .map(|name| name.as_str().to_owned());
+
+        let mut attributes = Vec::with_capacity(
+            1 + log.template_params().len() + additional_data.len());
+        attributes.push((
+            TEMPLATE_ATTRIBUTE.to_owned(),
+            OtlpValue::String(log.template().as_immutable_string().as_str().to_owned())));
+        for (key, value) in log.template_params() {
+            attributes.push((key.as_str().to_owned(), to_otlp_value(value)));
+        }
+        for (key, value) in additional_data {
+            let is_mapped = (key == trace_id_key() && trace_id.is_some())
+                || (key == span_id_key() && span_id.is_some())
+                || (Some(key) == log.logger_name_key() && scope_name.is_some())
+                || log.template_params().contains_key(key);
+            if !is_mapped {
+                attributes.push((key.as_str().to_owned(), to_otlp_value(value)));
+            }
+        }
+        attributes.sort_by(|left, right| left.0.cmp(&right.0));
+
+        let (severity_number, severity_text) = severity(log.log_level());
+        Self {
+            time_unix_nano: unix_nano(log.created_at()),
+            observed_time_unix_nano: unix_nano(SystemTime::now()),
+            severity_number: severity_number,
+            severity_text: severity_text,
+            body: render_message(log),
+            attributes: attributes,
+            trace_id: trace_id,
+            span_id: span_id,
+            scope_name: scope_name,
+        }
+    }
+
+    /// Returns value of attribute with given `key`.
+    pub fn attribute(&self, key: &str) -> Option<&OtlpValue> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Returns OTLP `SeverityNumber` and `SeverityText` of `log_level`.
+pub fn severity(log_level: LogLevel) -> (u8, &'static str) {
+    match log_level {
+        LogLevel::Debug => (5, "DEBUG"),
+        LogLevel::Info => (9, "INFO"),
+        LogLevel::Warning => (13, "WARN"),
+        LogLevel::Error => (17, "ERROR"),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn unix_nano(time: SystemTime) -> u64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_nanos().min(u128::from(u64::MAX)) as u64,
+        Err(_) => 0,
+    }
+}
+
+fn get_str<'a>(log: &'a LogDataHolder, key: &ImmutableString) -> Option<&'a str> {
+    match log.get(key) {
+        Some(SLObject::String(value)) => Some(value.value().as_str()),
+        _ => None,
+    }
+}
+
+/// OTLP integers are signed 64-bit, bigger values are sent as strings.
+fn to_otlp_int(value: u128) -> OtlpValue {
+    match i64::try_from(value) {
+        Ok(value) => OtlpValue::Int(value),
+        Err(_) => OtlpValue::String(value.to_string()),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn to_otlp_value(obj: &SLObject) -> OtlpValue {
+    match obj {
+        SLObject::Empty => OtlpValue::Empty,
+        SLObject::LogLevel(inner) => OtlpValue::String(severity(*inner.value()).1.to_owned()),
+        SLObject::SystemTime(inner) => {
+            let dt: DateTime<Utc> = (*inner.value()).into();
+            OtlpValue::String(dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        },
+        SLObject::Duration(inner) => {
+            let nanos = inner.value().as_nanos().min(i64::MAX as u128);
+            OtlpValue::Int(nanos as i64)
+        },
+        SLObject::String(inner) => OtlpValue::String(inner.value().as_str().to_owned()),
+        SLObject::Number(inner) => OtlpValue::Int(inner.value()),
+        SLObject::Float(inner) => OtlpValue::Double(inner.value()),
+        SLObject::UInt64(inner) => to_otlp_int(u128::from(inner.value())),
+        SLObject::UInt128(inner) => to_otlp_int(*inner.value()),
+        SLObject::Bool(inner) => OtlpValue::Bool(inner.value()),
+        SLObject::Bytes(inner) => OtlpValue::Bytes(inner.value().clone()),
+        SLObject::Uuid(inner) => OtlpValue::String(inner.to_string()),
+        SLObject::IpAddr(inner) => OtlpValue::String(inner.value().to_string()),
+        SLObject::Error(inner) => {
+            let sources = inner.sources()
+                .iter()
+                .map(|source| OtlpValue::String(source.as_str().to_owned()))
+                .collect();
+            let mut items = vec![
+                ("message".to_owned(), OtlpValue::String(inner.message().as_str().to_owned())),
+                ("sources".to_owned(), OtlpValue::Array(sources)),
+            ];
+            if let Some(backtrace) = inner.backtrace() {
+                items.push(("backtrace".to_owned(), OtlpValue::String(backtrace.as_str().to_owned())));
+            }
+            OtlpValue::KeyValueList(items)
+        },
+        SLObject::Array(inner) => {
+            OtlpValue::Array(inner.value().iter().map(to_otlp_value).collect())
+        },
+        SLObject::Dict(inner) => {
+            let mut items: Vec<(String, OtlpValue)> = inner.value()
+                .iter()
+                .map(|(key, value)| (key.as_str().to_owned(), to_otlp_value(value)))
+                .collect();
+            items.sort_by(|left, right| left.0.cmp(&right.0));
+            OtlpValue::KeyValueList(items)
+        },
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::HashMap,
+    sync::LazyLock,
+    time::{Duration, Instant}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::{Template, TemplateBuilder},
+    traits::{LogLevel, StructuralLog, StructuralLogger}};
+
+use crate::{SpanId, TraceId};
+
+static SPAN_FINISHED_TEMPLATE: LazyLock<Template> = LazyLock::new(|| {
+    TemplateBuilder::global().create_from_str("Span {span_name} finished in {elapsed}")
+});
+
+static TRACE_ID: LazyLock<ImmutableString> = LazyLock::new(|| new_key("trace_id"));
+static SPAN_ID: LazyLock<ImmutableString> = LazyLock::new(|| new_key("span_id"));
+static PARENT_SPAN_ID: LazyLock<ImmutableString> = LazyLock::new(|| new_key("parent_span_id"));
+static SPAN_NAME: LazyLock<ImmutableString> = LazyLock::new(|| new_key("span_name"));
+static ELAPSED: LazyLock<ImmutableString> = LazyLock::new(|| new_key("elapsed"));
+
+fn new_key(key: &str) -> ImmutableString {
+    ImmutableString::new(key).unwrap()
+}
+
+/// Key under which [`SpanContext::trace_id`] is stored in
+/// `additional_data`.
+#[inline(always)]
+pub fn trace_id_key() -> &'static ImmutableString { &TRACE_ID }
+
+/// Key under which [`SpanContext::span_id`] is stored in
+/// `additional_data`.
+#[inline(always)]
+pub fn span_id_key() -> &'static ImmutableString { &SPAN_ID }
+
+/// Key under which [`SpanContext::parent_span_id`] is stored in
+/// `additional_data`, for spans that have a parent.
+#[inline(always)]
+pub fn parent_span_id_key() -> &'static ImmutableString { &PARENT_SPAN_ID }
+
+/// Identifies span and its position in a trace.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[allow(clippy::struct_field_names)]
+pub struct SpanContext {
+    trace_id: TraceId,
+    span_id: SpanId,
+    parent_span_id: Option<SpanId>,
+}
+
+impl SpanContext {
+    pub fn new(trace_id: TraceId, span_id: SpanId, parent_span_id: Option<SpanId>) -> Self {
+        Self {
+            trace_id: trace_id,
+            span_id: span_id,
+            parent_span_id: parent_span_id,
+        }
+    }
+
+    #[inline(always)]
+    pub fn trace_id(&self) -> TraceId { self.trace_id }
+
+    #[inline(always)]
+    pub fn span_id(&self) -> SpanId { self.span_id }
+
+    #[inline(always)]
+    pub fn parent_span_id(&self) -> Option<SpanId> { self.parent_span_id }
+
+    /// Stores ids in `additional_data` of `log_data`, replacing existing ones.
+    pub fn stamp(&self, log_data: &mut LogDataHolder) {
+        log_data.update_data(TRACE_ID.clone(), self.trace_id.to_hex());
+        log_data.update_data(SPAN_ID.clone(), self.span_id.to_hex());
+        if let Some(parent_span_id) = self.parent_span_id {
+            log_data.update_data(PARENT_SPAN_ID.clone(), parent_span_id.to_hex());
+        }
+    }
+}
+
+struct SpanLog<'a, T: StructuralLog> {
+    inner: T,
+    context: &'a SpanContext,
+}
+
+impl<T: StructuralLog> StructuralLog for SpanLog<'_, T> {
+    fn log_data(&self) -> LogDataHolder {
+        let mut log_data = self.inner.log_data();
+        self.context.stamp(&mut log_data);
+        log_data
+    }
+}
+
+struct SpanFinishedLog<'a> {
+    log_level: LogLevel,
+    name: &'a ImmutableString,
+    elapsed: Duration,
+}
+
+impl StructuralLog for SpanFinishedLog<'_> {
+    fn log_data(&self) -> LogDataHolder {
+        let mut template_params = HashMap::with_capacity(2);
+        template_params.insert(SPAN_NAME.clone(), SLObject::from(self.name.clone()));
+        template_params.insert(ELAPSED.clone(), SLObject::from(self.elapsed));
+        LogDataHolder::new(
+            self.log_level,
+            SPAN_FINISHED_TEMPLATE.clone(),
+            template_params)
+    }
+}
+
+/// Unit of work within a trace. Logs sent through a span are stamped
+/// with its [`SpanContext`]. When the span ends, i.e. on [`Span::exit`]
+/// or on drop, it sends a log with its name and duration through the
+/// underlying logger, by default with [`LogLevel::Info`].
+#[must_use = "span ends as soon as it is dropped"]
+pub struct Span<'a, L: StructuralLogger> {
+    logger: &'a L,
+    name: ImmutableString,
+    context: SpanContext,
+    log_level: LogLevel,
+    started_at: Instant,
+}
+
+impl<'a, L: StructuralLogger> Span<'a, L> {
+    /// Starts a span of a new trace.
+    /// 
+    /// # Panics
+    /// When `name` cannot be converted into [`ImmutableString`].
+    pub fn enter(logger: &'a L, name: &str) -> Self {
+        let context = SpanContext::new(TraceId::generate(), SpanId::generate(), None);
+        Self::enter_with_context(logger, name, context)
+    }
+
+    /// Starts a span with explicit context, e.g. continuing a trace
+    /// started by another service.
+    /// 
+    /// # Panics
+    /// When `name` cannot be converted into [`ImmutableString`].
+    pub fn enter_with_context(logger: &'a L, name: &str, context: SpanContext) -> Self {
+        let name = ImmutableString::new(name)
+            .expect("Span - enter() fail on new ImmutableString");
+        Self {
+            logger: logger,
+            name: name,
+            context: context,
+            log_level: LogLevel::Info,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Starts a child span within the same trace, logging through the
+    /// same logger and with the same [`LogLevel`].
+    /// 
+    /// # Panics
+    /// When `name` cannot be converted into [`ImmutableString`].
+    pub fn enter_child(&self, name: &str) -> Span<'a, L> {
+        let context = SpanContext::new(
+            self.context.trace_id,
+            SpanId::generate(),
+            Some(self.context.span_id));
+        let mut child = Self::enter_with_context(self.logger, name, context);
+        child.log_level = self.log_level;
+        child
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &ImmutableString { &self.name }
+
+    #[inline(always)]
+    pub fn context(&self) -> &SpanContext { &self.context }
+
+    /// Sets [`LogLevel`] of the log sent when the span ends.
+    #[inline(always)]
+    pub fn set_log_level(&mut self, log_level: LogLevel) {
+        self.log_level = log_level;
+    }
+
+    #[inline(always)]
+    pub fn elapsed(&self) -> Duration { self.started_at.elapsed() }
+
+    /// Ends the span. Same as dropping it.
+    pub fn exit(self) { }
+}
+
+impl<L: StructuralLogger> StructuralLogger for Span<'_, L> {
+    fn log<T>(&self, log: T) where T : StructuralLog {
+        self.logger.log(SpanLog { inner: log, context: &self.context });
+    }
+
+    #[inline(always)]
+    fn is_enabled(&self, log_level: LogLevel) -> bool {
+        self.logger.is_enabled(log_level)
+    }
+}
+
+impl<L: StructuralLogger> Drop for Span<'_, L> {
+    fn drop(&mut self) {
+        let log = SpanFinishedLog {
+            log_level: self.log_level,
+            name: &self.name,
+            elapsed: self.started_at.elapsed(),
+        };
+        self.log(log);
+    }
+}
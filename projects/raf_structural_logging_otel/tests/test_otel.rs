@@ -0,0 +1,234 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+    thread,
+    time::{Duration, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::CoreLoggerFactoryBuilder,
+    models::{LogDataHolder, SLError, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use raf_structural_logging_otel::{
+    severity,
+    InMemoryExporter,
+    LogExporter,
+    OtlpHandler,
+    OtlpLogRecord,
+    OtlpValue,
+    Span,
+    SpanContext,
+    SpanId,
+    TraceId};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+fn imm(text: &str) -> ImmutableString {
+    ImmutableString::new(text).unwrap()
+}
+
+struct TestLog(i64);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        let mut params = HashMap::new();
+        params.insert(imm("idx"), SLObject::from(self.0));
+        LogDataHolder::new(LogLevel::Warning, TMPL_BUILDER.create_from_str("Step {idx}"), params)
+    }
+}
+
+fn text(value: &str) -> OtlpValue {
+    OtlpValue::String(value.to_owned())
+}
+
+#[rstest]
+#[case("0123456789abcdef0123456789ABCDEF", true)]
+#[case("00000000000000000000000000000000", false)]
+#[case("0123456789abcdef0123456789abcde", false)]
+#[case("0123456789abcdef0123456789abcdeg", false)]
+#[case("+123456789abcdef0123456789abcdef", false)]
+fn test_trace_id_hex(#[case] hex: &str, #[case] is_valid: bool) {
+    let id = TraceId::from_hex(hex);
+    assert_eq!(id.is_some(), is_valid);
+    if let Some(id) = id {
+        assert_eq!(id.to_hex(), hex.to_ascii_lowercase());
+    }
+}
+
+#[test]
+fn test_generated_ids() {
+    let first = TraceId::generate();
+    let second = TraceId::generate();
+    assert_ne!(first, second);
+    assert_eq!(TraceId::from_hex(&first.to_hex()), Some(first));
+    let span_id = SpanId::generate();
+    assert_eq!(span_id.to_hex().len(), 16);
+    assert_eq!(SpanId::from_bytes(*span_id.as_bytes()), Some(span_id));
+}
+
+#[rstest]
+#[case(LogLevel::Debug, 5, "DEBUG")]
+#[case(LogLevel::Info, 9, "INFO")]
+#[case(LogLevel::Warning, 13, "WARN")]
+#[case(LogLevel::Error, 17, "ERROR")]
+fn test_severity(#[case] log_level: LogLevel, #[case] number: u8, #[case] name: &str) {
+    assert_eq!(severity(log_level), (number, name));
+}
+
+#[test]
+fn test_record_mapping() {
+    let mut params = HashMap::new();
+    params.insert(imm("user"), SLObject::from("john"));
+    params.insert(imm("elapsed"), SLObject::from(Duration::from_millis(1500)));
+    let mut nested = HashMap::new();
+    nested.insert(imm("b"), SLObject::from(true));
+    nested.insert(imm("a"), SLObject::from(vec![SLObject::from(1i64), SLObject::Empty]));
+    let mut log = LogDataHolder::new_with_created_at(
+        LogLevel::Error,
+        TMPL_BUILDER.create_from_str("User {user} waited {elapsed}"),
+        params,
+        UNIX_EPOCH + Duration::new(1714557600, 5));
+    let context = SpanContext::new(
+        TraceId::from_hex("0123456789abcdef0123456789abcdef").unwrap(),
+        SpanId::from_hex("0123456789abcdef").unwrap(),
+        SpanId::from_hex("fedcba9876543210"));
+    context.stamp(&mut log);
+    log.set_logger_name(imm("logger"), imm("auth"));
+    log.update_data(imm("details"), nested);
+    log.update_data(imm("when"), UNIX_EPOCH + Duration::from_millis(1500));
+
+    let record = OtlpLogRecord::from_log(&log);
+    assert_eq!(record.time_unix_nano, 1_714_557_600_000_000_005);
+    assert!(record.observed_time_unix_nano > record.time_unix_nano);
+    assert_eq!(record.severity_number, 17);
+    assert_eq!(record.severity_text, "ERROR");
+    assert_eq!(record.body, "User john waited 1.500s");
+    assert_eq!(record.trace_id, Some(context.trace_id()));
+    assert_eq!(record.span_id, Some(context.span_id()));
+    assert_eq!(record.scope_name.as_deref(), Some("auth"));
+
+    let expected = vec![
+        ("details".to_owned(), OtlpValue::KeyValueList(vec![
+            ("a".to_owned(), OtlpValue::Array(vec![OtlpValue::Int(1), OtlpValue::Empty])),
+            ("b".to_owned(), OtlpValue::Bool(true)),
+        ])),
+        ("elapsed".to_owned(), OtlpValue::Int(1_500_000_000)),
+        ("log.template".to_owned(), text("User {user} waited {elapsed}")),
+        ("parent_span_id".to_owned(), text("fedcba9876543210")),
+        ("user".to_owned(), text("john")),
+        ("when".to_owned(), text("1970-01-01T00:00:01.500Z")),
+    ];
+    assert_eq!(record.attributes, expected);
+    assert_eq!(record.attribute("user"), Some(&text("john")));
+    assert_eq!(record.attribute("trace_id"), None);
+}
+
+#[rstest]
+#[case(SLObject::from(2.5), OtlpValue::Double(2.5))]
+#[case(SLObject::from(7u64), OtlpValue::Int(7))]
+#[case(SLObject::from(u64::MAX), text("18446744073709551615"))]
+#[case(SLObject::from(u128::from(u64::MAX) + 1), text("18446744073709551616"))]
+#[case(SLObject::from(vec![1u8, 2]), OtlpValue::Bytes(vec![1, 2]))]
+#[case(SLObject::from(std::net::Ipv4Addr::LOCALHOST), text("127.0.0.1"))]
+#[case(
+    SLObject::from(SLError::new(imm("top"), vec![imm("bottom")], None)),
+    OtlpValue::KeyValueList(vec![
+        ("message".to_owned(), text("top")),
+        ("sources".to_owned(), OtlpValue::Array(vec![text("bottom")])),
+    ]))]
+fn test_rich_attributes(#[case] value: SLObject, #[case] expected: OtlpValue) {
+    let mut params = HashMap::new();
+    params.insert(imm("value"), value);
+    let log = LogDataHolder::new(LogLevel::Info, TMPL_BUILDER.create_from_str("{value}"), params);
+    let record = OtlpLogRecord::from_log(&log);
+    assert_eq!(record.attribute("value"), Some(&expected));
+}
+
+#[test]
+fn test_spans_with_exporter() {
+    let exporter = Arc::new(InMemoryExporter::default());
+
+    {
+        let mut builder = CoreLoggerFactoryBuilder::default();
+        builder.add_handler(Arc::new(OtlpHandler::new(exporter.clone())));
+        let factory = builder.build();
+        let logger = factory.create_from_str("jobs");
+
+        let mut root = Span::enter(&logger, "root");
+        root.set_log_level(LogLevel::Debug);
+        root.log(TestLog(1));
+        {
+            let child = root.enter_child("child");
+            child.log(TestLog(2));
+            thread::sleep(Duration::from_millis(5));
+        }
+        logger.log(TestLog(3));
+        root.exit();
+    }
+
+    let records = exporter.take_records();
+    assert!(exporter.records().is_empty());
+    let bodies: Vec<&str> = records.iter().map(|record| record.body.as_str()).collect();
+    assert_eq!(bodies[0], "Step 1");
+    assert_eq!(bodies[1], "Step 2");
+    assert!(bodies[2].starts_with("Span child finished in 0.0"));
+    assert_eq!(bodies[3], "Step 3");
+    assert!(bodies[4].starts_with("Span root finished in 0.0"));
+
+    let root_trace = records[0].trace_id.unwrap();
+    let root_span = records[0].span_id.unwrap();
+    assert_eq!(records[0].scope_name.as_deref(), Some("jobs"));
+    assert_eq!(records[0].attribute("parent_span_id"), None);
+
+    assert_eq!(records[1].trace_id, Some(root_trace));
+    assert_ne!(records[1].span_id, Some(root_span));
+    assert_eq!(records[1].attribute("parent_span_id"), Some(&text(&root_span.to_hex())));
+
+    assert_eq!(records[2].span_id, records[1].span_id);
+    assert_eq!(records[2].severity_text, "DEBUG");
+    assert_eq!(records[2].attribute("span_name"), Some(&text("child")));
+    let Some(OtlpValue::Int(elapsed)) = records[2].attribute("elapsed") else {
+        panic!("Expected elapsed duration");
+    };
+    assert!(*elapsed >= 5_000_000);
+
+    assert_eq!(records[3].trace_id, None);
+    assert_eq!(records[3].span_id, None);
+    assert_eq!(records[4].span_id, Some(root_span));
+}
+
+#[test]
+fn test_handler_skips_empty_log() {
+    let handler = OtlpHandler::new(InMemoryExporter::default());
+    handler.handle(&LogDataHolder::default());
+    assert!(handler.exporter().records().is_empty());
+}
+
+/// Exporter that keeps sizes of exported batches.
+#[derive(Default)]
+struct BatchSizeExporter {
+    sizes: Mutex<Vec<usize>>,
+}
+
+impl LogExporter for BatchSizeExporter {
+    fn export(&self, records: &[OtlpLogRecord]) {
+        self.sizes.lock().unwrap().push(records.len());
+    }
+}
+
+#[test]
+fn test_handler_exports_batch_at_once() {
+    let handler = OtlpHandler::new(BatchSizeExporter::default());
+    handler.handle_batch(&[TestLog(1).log_data(), LogDataHolder::default(), TestLog(2).log_data()]);
+    handler.handle_batch(&[LogDataHolder::default()]);
+    assert_eq!(*handler.exporter().sizes.lock().unwrap(), vec![2]);
+}
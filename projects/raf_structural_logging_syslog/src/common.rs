@@ -0,0 +1,47 @@
+use std::{fs, sync::LazyLock};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::{render_parameter, ParameterHint, TemplateParameter}};
+
+static PLAIN_PARAMETER: LazyLock<TemplateParameter> = LazyLock::new(|| {
+    TemplateParameter::new(
+        ImmutableString::new("value").unwrap(),
+        ParameterHint::Default,
+        None,
+        None)
+});
+
+/// Renders `value` as it would be rendered by `{name}` in a template.
+pub(crate) fn render_value(value: &SLObject) -> String {
+    let mut result = String::new();
+    render_parameter(value, &PLAIN_PARAMETER, &mut result);
+    result
+}
+
+#[inline(always)]
+pub(crate) fn logger_name(log: &LogDataHolder) -> Option<&str> {
+    log.logger_name().map(ImmutableString::as_str)
+}
+
+/// Returns name of the current host, or `None` if it cannot be detected.
+pub(crate) fn detect_hostname() -> Option<String> {
+    let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| fs::read_to_string("/etc/hostname"))
+        .ok()?;
+    let hostname = hostname.trim();
+    if hostname.is_empty() {
+        None
+    } else {
+        Some(hostname.to_owned())
+    }
+}
+
+/// Returns file name of the current executable, or `None` if it cannot
+/// be detected.
+pub(crate) fn detect_app_name() -> Option<String> {
+    let path = std::env::current_exe().ok()?;
+    let name = path.file_name()?.to_str()?;
+    Some(name.to_owned())
+}
@@ -0,0 +1,38 @@
+use raf_structural_logging::traits::LogLevel;
+
+/// Syslog facility, i.e. the kind of program sending logs.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum Facility {
+    Kernel = 0,
+    #[default]
+    User = 1,
+    Mail = 2,
+    Daemon = 3,
+    Auth = 4,
+    Syslog = 5,
+    Lpr = 6,
+    News = 7,
+    Uucp = 8,
+    Cron = 9,
+    AuthPriv = 10,
+    Ftp = 11,
+    Local0 = 16,
+    Local1 = 17,
+    Local2 = 18,
+    Local3 = 19,
+    Local4 = 20,
+    Local5 = 21,
+    Local6 = 22,
+    Local7 = 23,
+}
+
+/// Returns syslog severity of `log_level`, also used as journald's
+/// `PRIORITY`.
+pub fn severity(log_level: LogLevel) -> u8 {
+    match log_level {
+        LogLevel::Debug => 7,
+        LogLevel::Info => 6,
+        LogLevel::Warning => 4,
+        LogLevel::Error => 3,
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::HashSet,
+    io,
+    os::unix::net::UnixDatagram,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::render_message,
+    traits::StructuralLogHandler};
+
+use crate::{
+    common::{detect_app_name, render_value},
+    severity};
+
+/// Path of journald's native protocol socket.
+pub const DEFAULT_JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+const MAX_FIELD_NAME_LEN: usize = 64;
+
+/// Converts `key` into journald field name: letters are uppercased and
+/// other characters than ASCII letters and digits become `_`. Returns
+/// `None` if the result is not a valid name, i.e. it is empty, too long
+/// or starts with a digit or `_`, the latter being reserved for fields
+/// set by journald itself.
+pub fn journal_field_name(key: &str) -> Option<String> {
+    let name: String = key
+        .chars()
+        .map(|chr| if chr.is_ascii_alphanumeric() { chr.to_ascii_uppercase() } else { '_' })
+        .collect();
+    let first = name.chars().next()?;
+    if first == '_' || first.is_ascii_digit() || name.len() > MAX_FIELD_NAME_LEN {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Serializes `log` into journald's native protocol entry:
+/// * `MESSAGE` holds the rendered message
+/// * `PRIORITY` holds [`severity`] of the log
+/// * `SYSLOG_IDENTIFIER` holds `identifier`, if any
+/// * template parameters followed by additional data are written under
+///   names returned by [`journal_field_name`], keys that don't map to
+///   a valid or not yet written name are skipped
+/// 
+/// Values are rendered as in templates. Values containing new lines are
+/// written in the binary form, prefixed with their length.
+pub fn journal_entry(log: &LogDataHolder, identifier: Option<&str>) -> Vec<u8> {
+    let mut result = Vec::with_capacity(256);
+    write_field(&mut result, "MESSAGE", &render_message(log));
+    write_field(&mut result, "PRIORITY", &severity(log.log_level()).to_string());
+    if let Some(identifier) = identifier {
+        write_field(&mut result, "SYSLOG_IDENTIFIER", identifier);
+    }
+
+    let mut written: HashSet<String> = ["MESSAGE", "PRIORITY", "SYSLOG_IDENTIFIER"]
+        .into_iter()
+        .map(ToOwned::to_owned)
+        .collect();
+    for dict in [log.template_params(), log.additional_data()] {
+        let mut fields: Vec<(&ImmutableString, &SLObject)> = dict.iter().collect();
+        fields.sort_by(|left, right| left.0.as_str().cmp(right.0.as_str()));
+        for (key, value) in fields {
+            let Some(name) = journal_field_name(key.as_str()) else { continue };
+            if written.contains(&name) {
+                continue;
+            }
+            write_field(&mut result, &name, &render_value(value));
+            written.insert(name);
+        }
+    }
+    result
+}
+
+fn write_field(output: &mut Vec<u8>, name: &str, value: &str) {
+    output.extend_from_slice(name.as_bytes());
+    if value.contains('\n') {
+        output.push(b'\n');
+        output.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        output.push(b'=');
+    }
+    output.extend_from_slice(value.as_bytes());
+    output.push(b'\n');
+}
+
+/// Handler that writes logs to systemd-journald with its native protocol,
+/// see [`journal_entry`]. Use [`JournaldHandlerBuilder`] to construct it.
+/// 
+/// Each log is sent as a single datagram, logs that cannot be sent,
+/// e.g. because they exceed maximal datagram size, are dropped and
+/// counted, see [`JournaldHandler::failed_logs`].
+pub struct JournaldHandler {
+    socket_path: PathBuf,
+    identifier: Option<String>,
+    socket: UnixDatagram,
+    failed_logs: AtomicU64,
+}
+
+impl JournaldHandler {
+    #[inline(always)]
+    pub fn socket_path(&self) -> &Path { &self.socket_path }
+
+    #[inline(always)]
+    pub fn identifier(&self) -> Option<&str> { self.identifier.as_deref() }
+
+    /// Returns number of logs dropped because they couldn't be sent.
+    #[inline(always)]
+    pub fn failed_logs(&self) -> u64 { self.failed_logs.load(Ordering::Relaxed) }
+}
+
+impl StructuralLogHandler for JournaldHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+
+        let entry = journal_entry(log, self.identifier.as_deref());
+        if self.socket.send_to(&entry, &self.socket_path).is_err() {
+            self.failed_logs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Builder of [`JournaldHandler`]. By default logs are sent to
+/// [`DEFAULT_JOURNALD_SOCKET`] with file name of the current executable
+/// as `SYSLOG_IDENTIFIER`.
+pub struct JournaldHandlerBuilder {
+    socket_path: PathBuf,
+    identifier: Option<String>,
+}
+
+impl Default for JournaldHandlerBuilder {
+    fn default() -> Self {
+        Self {
+            socket_path: PathBuf::from(DEFAULT_JOURNALD_SOCKET),
+            identifier: detect_app_name(),
+        }
+    }
+}
+
+impl JournaldHandlerBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn set_socket_path(&mut self, socket_path: impl Into<PathBuf>) {
+        self.socket_path = socket_path.into();
+    }
+
+    /// Sets `SYSLOG_IDENTIFIER` of logs, `None` skips the field.
+    pub fn set_identifier(&mut self, identifier: Option<&str>) {
+        self.identifier = identifier.map(ToOwned::to_owned);
+    }
+
+    /// Builds [`JournaldHandler`]. The socket is not required to exist
+    /// yet, since each log is sent separately.
+    /// 
+    /// # Errors
+    /// * [`io::Error`] if local socket cannot be created
+    pub fn build(self) -> Result<JournaldHandler, io::Error> {
+        Ok(JournaldHandler {
+            socket_path: self.socket_path,
+            identifier: self.identifier,
+            socket: UnixDatagram::unbound()?,
+            failed_logs: AtomicU64::new(0),
+        })
+    }
+}
@@ -0,0 +1,42 @@
+//! Implements structural logging handlers that pass logs to the system log:
+//! [`SyslogHandler`] and [`JournaldHandler`].
+//! 
+//! [`SyslogHandler`] formats logs as RFC 5424 messages, e.g.
+//! 
+//! ```text
+//! <14>1 2024-05-01T10:00:00.123Z host app 42 auth [params@32473 user="john"] User john logged in
+//! ```
+//! 
+//! where `auth` is the name of the logger and the structured data element
+//! holds template parameters. Messages are sent over a Unix datagram
+//! socket (e.g. `/dev/log`), UDP or TCP, see [`SyslogTransport`].
+//! 
+//! [`JournaldHandler`] writes logs to systemd-journald with its native
+//! protocol, with the rendered message, priority, template parameters and
+//! additional data as separate `KEY=value` fields.
+//! 
+//! Both handlers count logs they failed to send instead of panicking, since
+//! the system log being temporarily unavailable is an expected condition.
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unreadable_literal,
+    clippy::inline_always,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions,
+)]
+pub(crate) mod common;
+
+mod facility;
+mod rfc5424;
+mod transport;
+mod syslog_handler;
+#[cfg(unix)]
+mod journald;
+pub use facility::*;
+pub use rfc5424::*;
+pub use transport::*;
+pub use syslog_handler::*;
+#[cfg(unix)]
+pub use journald::*;
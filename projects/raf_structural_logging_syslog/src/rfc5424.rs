@@ -0,0 +1,168 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::render_message};
+
+use crate::{
+    common::{detect_app_name, detect_hostname, logger_name, render_value},
+    severity,
+    Facility};
+
+/// Default id of the structured data element holding template parameters.
+/// `32473` is the private enterprise number reserved for documentation.
+pub const DEFAULT_STRUCTURED_DATA_ID: &str = "params@32473";
+
+const NIL: &str = "-";
+const MAX_HOSTNAME_LEN: usize = 255;
+const MAX_APP_NAME_LEN: usize = 48;
+const MAX_PROCID_LEN: usize = 128;
+const MAX_MSGID_LEN: usize = 32;
+const MAX_SD_NAME_LEN: usize = 32;
+
+/// Formats logs as RFC 5424 messages:
+/// * `PRI` is made of [`Facility`] and [`severity`] of the log
+/// * `TIMESTAMP` is `LogDataHolder::created_at` in UTC, with milliseconds
+/// * `HOSTNAME` and `APP-NAME` are detected, unless set explicitly
+/// * `PROCID` is id of the current process
+/// * `MSGID` is name of the logger
+/// * structured data holds a single element with template parameters,
+///   sorted by name
+/// * `MSG` is the rendered message
+/// 
+/// Header fields are limited to printable ASCII, other characters are
+/// replaced with `_`, and too long values are truncated.
+#[derive(Clone, Debug)]
+pub struct SyslogFormatter {
+    facility: Facility,
+    hostname: String,
+    app_name: String,
+    process_id: String,
+    structured_data_id: String,
+}
+
+impl Default for SyslogFormatter {
+    fn default() -> Self {
+        Self {
+            facility: Facility::default(),
+            hostname: header_field(detect_hostname().as_deref().unwrap_or(NIL), MAX_HOSTNAME_LEN),
+            app_name: header_field(detect_app_name().as_deref().unwrap_or(NIL), MAX_APP_NAME_LEN),
+            process_id: header_field(&std::process::id().to_string(), MAX_PROCID_LEN),
+            structured_data_id: DEFAULT_STRUCTURED_DATA_ID.to_owned(),
+        }
+    }
+}
+
+impl SyslogFormatter {
+    pub fn new() -> Self { Self::default() }
+
+    #[inline(always)]
+    pub fn facility(&self) -> Facility { self.facility }
+
+    #[inline(always)]
+    pub fn hostname(&self) -> &str { &self.hostname }
+
+    #[inline(always)]
+    pub fn app_name(&self) -> &str { &self.app_name }
+
+    #[inline(always)]
+    pub fn structured_data_id(&self) -> &str { &self.structured_data_id }
+
+    pub fn set_facility(&mut self, facility: Facility) {
+        self.facility = facility;
+    }
+
+    pub fn set_hostname(&mut self, hostname: &str) {
+        self.hostname = header_field(hostname, MAX_HOSTNAME_LEN);
+    }
+
+    pub fn set_app_name(&mut self, app_name: &str) {
+        self.app_name = header_field(app_name, MAX_APP_NAME_LEN);
+    }
+
+    /// Sets id of the structured data element holding template parameters.
+    /// Ids other than those registered with IANA should have the
+    /// `name@<private enterprise number>` form.
+    pub fn set_structured_data_id(&mut self, structured_data_id: &str) {
+        self.structured_data_id = sd_name(structured_data_id);
+    }
+
+    /// Formats `log` as a single RFC 5424 message, without any framing.
+    pub fn format(&self, log: &LogDataHolder) -> String {
+        let priority = (self.facility as u8) * 8 + severity(log.log_level());
+        let timestamp = DateTime::<Utc>::from(log.created_at())
+            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        let msgid = header_field(logger_name(log).unwrap_or(NIL), MAX_MSGID_LEN);
+
+        let mut result = String::with_capacity(256);
+        let _ = write!(
+            result,
+            "<{priority}>1 {timestamp} {} {} {} {msgid} ",
+            self.hostname,
+            self.app_name,
+            self.process_id);
+        self.write_structured_data(log, &mut result);
+
+        let message = render_message(log);
+        if !message.is_empty() {
+            result.push(' ');
+            result.push_str(&message);
+        }
+        result
+    }
+
+    fn write_structured_data(&self, log: &LogDataHolder, output: &mut String) {
+        let mut params: Vec<(String, &SLObject)> = log.template_params()
+            .iter()
+            .map(|(key, value)| (sd_name(key.as_str()), value))
+            .filter(|(name, _)| name != NIL)
+            .collect();
+        if params.is_empty() {
+            output.push_str(NIL);
+            return;
+        }
+
+        params.sort_by(|left, right| left.0.cmp(&right.0));
+        output.push('[');
+        output.push_str(&self.structured_data_id);
+        for (name, value) in params {
+            output.push(' ');
+            output.push_str(&name);
+            output.push_str("=\"");
+            for chr in render_value(value).chars() {
+                if matches!(chr, '"' | '\\' | ']') {
+                    output.push('\\');
+                }
+                output.push(chr);
+            }
+            output.push('"');
+        }
+        output.push(']');
+    }
+}
+
+/// Returns `value` limited to printable ASCII and `max_len` characters,
+/// or nil value if empty.
+fn header_field(value: &str, max_len: usize) -> String {
+    let result: String = value
+        .chars()
+        .take(max_len)
+        .map(|chr| if chr.is_ascii_graphic() { chr } else { '_' })
+        .collect();
+    if result.is_empty() {
+        NIL.to_owned()
+    } else {
+        result
+    }
+}
+
+/// Same as [`header_field`] but additionally replaces characters not
+/// allowed in structured data names.
+fn sd_name(value: &str) -> String {
+    let result = header_field(value, MAX_SD_NAME_LEN);
+    if result == NIL {
+        return result;
+    }
+    result.replace(['=', ']', '"'], "_")
+}
@@ -0,0 +1,217 @@
+use std::{
+    io,
+    sync::{atomic::{AtomicU64, Ordering}, Mutex, MutexGuard},
+    time::{Duration, Instant}};
+
+use raf_structural_logging::{
+    models::LogDataHolder,
+    traits::StructuralLogHandler};
+
+use crate::{transport::Connection, Facility, SyslogFormatter, SyslogTransport};
+
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Exponential backoff between failed attempts to reconnect.
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    fn can_attempt(&self) -> bool {
+        match self.retry_at {
+            Some(retry_at) => Instant::now() >= retry_at,
+            None => true,
+        }
+    }
+
+    fn succeeded(&mut self) {
+        self.current = Duration::ZERO;
+        self.retry_at = None;
+    }
+
+    fn failed(&mut self) {
+        self.current = if self.current.is_zero() {
+            self.initial
+        } else {
+            (self.current * 2).min(self.max)
+        };
+        self.retry_at = Some(Instant::now() + self.current);
+    }
+}
+
+struct ConnectionState {
+    connection: Option<Connection>,
+    backoff: Backoff,
+}
+
+/// Handler that sends logs formatted by [`SyslogFormatter`] to syslog
+/// daemon through [`SyslogTransport`]. Use [`SyslogHandlerBuilder`]
+/// to construct it.
+/// 
+/// When sending fails, the handler reconnects and tries once more. If that
+/// fails too, the handler backs off exponentially and doesn't reconnect
+/// until the backoff expires, so that an unavailable daemon doesn't block
+/// the dispatching thread on every log. Logs that cannot be sent, including
+/// those handled while backing off, are dropped and counted, see
+/// [`SyslogHandler::failed_logs`].
+pub struct SyslogHandler {
+    transport: SyslogTransport,
+    formatter: SyslogFormatter,
+    state: Mutex<ConnectionState>,
+    failed_logs: AtomicU64,
+}
+
+impl SyslogHandler {
+    pub(crate) fn new(
+        transport: SyslogTransport,
+        formatter: SyslogFormatter,
+        initial_backoff: Duration,
+        max_backoff: Duration) -> Result<Self, io::Error>
+    {
+        let connection = transport.connect()?;
+        let backoff = Backoff {
+            initial: initial_backoff,
+            max: max_backoff.max(initial_backoff),
+            current: Duration::ZERO,
+            retry_at: None,
+        };
+        Ok(Self {
+            transport: transport,
+            formatter: formatter,
+            state: Mutex::new(ConnectionState {
+                connection: Some(connection),
+                backoff: backoff,
+            }),
+            failed_logs: AtomicU64::new(0),
+        })
+    }
+
+    #[inline(always)]
+    pub fn transport(&self) -> &SyslogTransport { &self.transport }
+
+    #[inline(always)]
+    pub fn formatter(&self) -> &SyslogFormatter { &self.formatter }
+
+    /// Returns number of logs dropped because they couldn't be sent.
+    #[inline(always)]
+    pub fn failed_logs(&self) -> u64 { self.failed_logs.load(Ordering::Relaxed) }
+
+    /// Returns current delay between attempts to reconnect, zero when
+    /// the last attempt succeeded.
+    pub fn backoff(&self) -> Duration { self.lock().backoff.current }
+
+    fn lock(&self) -> MutexGuard<'_, ConnectionState> {
+        self.state.lock().expect("SyslogHandler - lock() fail")
+    }
+
+    /// Sends `message` through the current connection and, if that fails,
+    /// through a new one. Connects at most once.
+    fn send(&self, state: &mut ConnectionState, message: &[u8]) -> bool {
+        if let Some(connection) = state.connection.as_mut() {
+            if connection.send(message).is_ok() {
+                return true;
+            }
+            // The daemon could have been restarted in the meantime, in
+            // which case the connection is stale.
+            state.connection = None;
+        }
+
+        let Ok(mut connection) = self.transport.connect() else { return false };
+        if connection.send(message).is_err() {
+            return false;
+        }
+        state.connection = Some(connection);
+        true
+    }
+}
+
+impl StructuralLogHandler for SyslogHandler {
+    fn handle(&self, log: &LogDataHolder) {
+        if log.is_empty() {
+            return;
+        }
+
+        let message = self.formatter.format(log);
+        let mut state = self.lock();
+        let state = &mut *state;
+        if !state.backoff.can_attempt() {
+            self.failed_logs.fetch_add(1, Ordering::Relaxed);
+        } else if self.send(state, message.as_bytes()) {
+            state.backoff.succeeded();
+        } else {
+            state.backoff.failed();
+            self.failed_logs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn flush(&self) {
+        let mut state = self.lock();
+        if let Some(connection) = state.connection.as_mut() {
+            if connection.flush().is_err() {
+                state.connection = None;
+            }
+        }
+    }
+}
+
+/// Builder of [`SyslogHandler`]. By default [`Facility::User`] is used,
+/// while host and application names are detected, see [`SyslogFormatter`].
+/// Backoff starts at [`DEFAULT_INITIAL_BACKOFF`] and doubles up to
+/// [`DEFAULT_MAX_BACKOFF`].
+pub struct SyslogHandlerBuilder {
+    transport: SyslogTransport,
+    formatter: SyslogFormatter,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl SyslogHandlerBuilder {
+    pub fn new(transport: SyslogTransport) -> Self {
+        Self {
+            transport: transport,
+            formatter: SyslogFormatter::new(),
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    pub fn set_facility(&mut self, facility: Facility) {
+        self.formatter.set_facility(facility);
+    }
+
+    pub fn set_hostname(&mut self, hostname: &str) {
+        self.formatter.set_hostname(hostname);
+    }
+
+    pub fn set_app_name(&mut self, app_name: &str) {
+        self.formatter.set_app_name(app_name);
+    }
+
+    /// See [`SyslogFormatter::set_structured_data_id`].
+    pub fn set_structured_data_id(&mut self, structured_data_id: &str) {
+        self.formatter.set_structured_data_id(structured_data_id);
+    }
+
+    /// Sets delay after the first failure, doubled after each next
+    /// failure up to `max_backoff`.
+    pub fn set_backoff(&mut self, initial_backoff: Duration, max_backoff: Duration) {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+    }
+
+    /// Builds [`SyslogHandler`] and connects it to syslog daemon.
+    /// 
+    /// # Errors
+    /// * [`io::Error`] if connection cannot be established
+    pub fn build(self) -> Result<SyslogHandler, io::Error> {
+        SyslogHandler::new(
+            self.transport,
+            self.formatter,
+            self.initial_backoff,
+            self.max_backoff)
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(unix)]
+use std::{os::unix::net::UnixDatagram, path::PathBuf};
+use std::{
+    io::{self, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket},
+    time::Duration};
+
+/// Path of the local syslog socket on most Linux systems.
+#[cfg(unix)]
+pub const DEFAULT_SYSLOG_SOCKET: &str = "/dev/log";
+
+const TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Describes how [`crate::SyslogHandler`] reaches syslog daemon.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum SyslogTransport {
+    /// Each message is sent as a single datagram to local socket,
+    /// e.g. [`DEFAULT_SYSLOG_SOCKET`].
+    #[cfg(unix)]
+    UnixDatagram(PathBuf),
+
+    /// Each message is sent as a single datagram, as in RFC 5426.
+    Udp(SocketAddr),
+
+    /// Messages are sent over a single connection with octet-counting
+    /// framing, as in RFC 6587, i.e. each message is preceded by its
+    /// length in bytes and a space.
+    Tcp(SocketAddr),
+}
+
+impl SyslogTransport {
+    pub(crate) fn connect(&self) -> Result<Connection, io::Error> {
+        match self {
+            #[cfg(unix)]
+            Self::UnixDatagram(path) => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                Ok(Connection::UnixDatagram(socket))
+            },
+            Self::Udp(address) => {
+                let local: SocketAddr = if address.is_ipv4() {
+                    (Ipv4Addr::UNSPECIFIED, 0).into()
+                } else {
+                    (Ipv6Addr::UNSPECIFIED, 0).into()
+                };
+                let socket = UdpSocket::bind(local)?;
+                socket.connect(address)?;
+                Ok(Connection::Udp(socket))
+            },
+            Self::Tcp(address) => {
+                let stream = TcpStream::connect_timeout(address, TCP_TIMEOUT)?;
+                stream.set_write_timeout(Some(TCP_TIMEOUT))?;
+                Ok(Connection::Tcp(stream))
+            },
+        }
+    }
+}
+
+pub(crate) enum Connection {
+    #[cfg(unix)]
+    UnixDatagram(UnixDatagram),
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+impl Connection {
+    pub(crate) fn send(&mut self, message: &[u8]) -> Result<(), io::Error> {
+        match self {
+            #[cfg(unix)]
+            Self::UnixDatagram(socket) => {
+                socket.send(message)?;
+            },
+            Self::Udp(socket) => {
+                socket.send(message)?;
+            },
+            Self::Tcp(stream) => {
+                let mut frame = Vec::with_capacity(message.len() + 8);
+                frame.extend_from_slice(message.len().to_string().as_bytes());
+                frame.push(b' ');
+                frame.extend_from_slice(message);
+                stream.write_all(&frame)?;
+            },
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<(), io::Error> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            _ => Ok(()),
+        }
+    }
+}
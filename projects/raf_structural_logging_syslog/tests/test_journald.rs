@@ -0,0 +1,85 @@
+#![cfg(unix)]
+use std::{
+    collections::HashMap,
+    os::unix::net::UnixDatagram,
+    sync::LazyLock,
+    time::Duration};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{LogLevel, StructuralLogHandler}};
+use raf_structural_logging_syslog::{journal_entry, journal_field_name, JournaldHandlerBuilder};
+use rstest::rstest;
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+fn imm(text: &str) -> ImmutableString { ImmutableString::new(text).unwrap() }
+
+fn build_log() -> LogDataHolder {
+    let mut params = HashMap::new();
+    params.insert(imm("user"), SLObject::from("john"));
+    params.insert(imm("_secret"), SLObject::from("x"));
+    params.insert(imm("message"), SLObject::from("shadowed"));
+    let mut log = LogDataHolder::new(
+        LogLevel::Warning,
+        TMPL_BUILDER.create_from_str("User {user} said {text}"),
+        params);
+    log.update_data(imm("text"), "hi\nthere");
+    log.update_data(imm("logger_name"), "auth");
+    log.update_data(imm("User"), "ignored, already written");
+    log
+}
+
+#[rstest]
+#[case("user", Some("USER"))]
+#[case("request-id", Some("REQUEST_ID"))]
+#[case("http.status2", Some("HTTP_STATUS2"))]
+#[case("_private", None)]
+#[case("2fa", None)]
+#[case("", None)]
+#[case(&"a".repeat(65), None)]
+fn test_field_name(#[case] key: &str, #[case] expected: Option<&str>) {
+    assert_eq!(journal_field_name(key).as_deref(), expected);
+}
+
+#[test]
+fn test_entry() {
+    let entry = journal_entry(&build_log(), Some("app"));
+    let multiline = "hi\nthere";
+    let mut expected = b"MESSAGE\n".to_vec();
+    let message = format!("User john said {multiline}");
+    expected.extend_from_slice(&(message.len() as u64).to_le_bytes());
+    expected.extend_from_slice(message.as_bytes());
+    expected.extend_from_slice(b"\nPRIORITY=4\nSYSLOG_IDENTIFIER=app\nUSER=john\nLOGGER_NAME=auth\nTEXT\n");
+    expected.extend_from_slice(&(multiline.len() as u64).to_le_bytes());
+    expected.extend_from_slice(multiline.as_bytes());
+    expected.push(b'\n');
+    assert_eq!(String::from_utf8_lossy(&entry), String::from_utf8_lossy(&expected));
+}
+
+#[test]
+fn test_handler() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.sock");
+
+    let mut builder = JournaldHandlerBuilder::new();
+    builder.set_socket_path(&path);
+    builder.set_identifier(None);
+    let handler = builder.build().unwrap();
+    assert_eq!(handler.identifier(), None);
+
+    let log = LogDataHolder::new(LogLevel::Info, TMPL_BUILDER.create_from_str("Ready"), HashMap::new());
+    handler.handle(&log);
+    assert_eq!(handler.failed_logs(), 1);
+
+    let server = UnixDatagram::bind(&path).unwrap();
+    server.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    handler.handle(&log);
+    let mut buffer = [0u8; 1024];
+    let len = server.recv(&mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"MESSAGE=Ready\nPRIORITY=6\n");
+    assert_eq!(handler.failed_logs(), 1);
+}
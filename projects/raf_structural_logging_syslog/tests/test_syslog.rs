@@ -0,0 +1,195 @@
+#![cfg(unix)]
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read},
+    net::{TcpListener, UdpSocket},
+    os::unix::net::UnixDatagram,
+    sync::{Arc, LazyLock},
+    thread,
+    time::{Duration, UNIX_EPOCH}};
+
+use raf_array::immutable_string::ImmutableString;
+use raf_structural_logging::{
+    core::{CoreLoggerFactoryBuilder, DispatchMode},
+    models::{LogDataHolder, SLObject},
+    template::TemplateBuilder,
+    traits::{
+        LogLevel,
+        StructuralLog,
+        StructuralLogHandler,
+        StructuralLogger,
+        StructuralLoggerFactory,
+        StructuralLoggerFactoryBuilder}};
+use raf_structural_logging_syslog::{
+    Facility,
+    SyslogFormatter,
+    SyslogHandler,
+    SyslogHandlerBuilder,
+    SyslogTransport};
+
+static TMPL_BUILDER: LazyLock<TemplateBuilder>
+    = LazyLock::new(TemplateBuilder::default);
+
+fn imm(text: &str) -> ImmutableString { ImmutableString::new(text).unwrap() }
+
+fn build_log(log_level: LogLevel, template: &str, params: &[(&str, SLObject)]) -> LogDataHolder {
+    let params: HashMap<ImmutableString, SLObject> = params
+        .iter()
+        .map(|(key, value)| (imm(key), value.clone()))
+        .collect();
+    LogDataHolder::new_with_created_at(
+        log_level,
+        TMPL_BUILDER.create_from_str(template),
+        params,
+        UNIX_EPOCH + Duration::from_millis(1714557600123))
+}
+
+fn formatter() -> SyslogFormatter {
+    let mut formatter = SyslogFormatter::new();
+    formatter.set_hostname("host");
+    formatter.set_app_name("my app");
+    formatter
+}
+
+fn build_handler(transport: SyslogTransport) -> SyslogHandler {
+    let mut builder = SyslogHandlerBuilder::new(transport);
+    builder.set_hostname("host");
+    builder.set_app_name("my app");
+    builder.build().unwrap()
+}
+
+struct TestLog(&'static str, i64);
+
+impl StructuralLog for TestLog {
+    fn log_data(&self) -> LogDataHolder {
+        build_log(LogLevel::Info, self.0, &[("idx", SLObject::from(self.1))])
+    }
+}
+
+#[test]
+fn test_format() {
+    let pid = std::process::id();
+    let mut formatter = formatter();
+    formatter.set_facility(Facility::Local0);
+
+    let mut log = build_log(LogLevel::Warning, "User {user} failed {count} times", &[
+        ("user", SLObject::from("jo\"h]n")),
+        ("count", SLObject::from(3i64)),
+        ("bad=key", SLObject::from(true)),
+    ]);
+    log.set_logger_name(imm("logger"), imm("auth"));
+    assert_eq!(
+        formatter.format(&log),
+        format!("<132>1 2024-05-01T10:00:00.123Z host my_app {pid} auth \
+            [params@32473 bad_key=\"true\" count=\"3\" user=\"jo\\\"h\\]n\"] User jo\"h]n failed 3 times"));
+
+    formatter.set_facility(Facility::Daemon);
+    formatter.set_structured_data_id("custom@1 x");
+    let log = build_log(LogLevel::Debug, "Started", &[]);
+    assert_eq!(formatter.format(&log), format!("<31>1 2024-05-01T10:00:00.123Z host my_app {pid} - - Started"));
+    assert_eq!(formatter.structured_data_id(), "custom@1_x");
+
+    let log = build_log(LogLevel::Error, "", &[("x", SLObject::from(1i64))]);
+    assert_eq!(formatter.format(&log), format!("<27>1 2024-05-01T10:00:00.123Z host my_app {pid} - [custom@1_x x=\"1\"]"));
+}
+
+#[test]
+fn test_udp() {
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    server.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let handler = build_handler(SyslogTransport::Udp(server.local_addr().unwrap()));
+
+    let log = build_log(LogLevel::Info, "Hello {name}", &[("name", SLObject::from("world"))]);
+    handler.handle(&log);
+
+    let mut buffer = [0u8; 1024];
+    let len = server.recv(&mut buffer).unwrap();
+    assert_eq!(std::str::from_utf8(&buffer[..len]).unwrap(), formatter().format(&log));
+    assert_eq!(handler.failed_logs(), 0);
+}
+
+#[test]
+fn test_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut messages = Vec::new();
+        for _ in 0..2 {
+            let mut length = Vec::new();
+            reader.read_until(b' ', &mut length).unwrap();
+            let length: usize = std::str::from_utf8(&length).unwrap().trim_end().parse().unwrap();
+            let mut message = vec![0u8; length];
+            reader.read_exact(&mut message).unwrap();
+            messages.push(String::from_utf8(message).unwrap());
+        }
+        messages
+    });
+
+    let handler = build_handler(SyslogTransport::Tcp(address));
+    let first = build_log(LogLevel::Info, "First", &[]);
+    let second = build_log(LogLevel::Error, "Second {value}", &[("value", SLObject::from("a b"))]);
+    handler.handle(&first);
+    handler.handle(&second);
+    handler.flush();
+
+    let messages = server.join().unwrap();
+    assert_eq!(messages, vec![formatter().format(&first), formatter().format(&second)]);
+}
+
+#[test]
+fn test_unix_datagram_through_logger() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.sock");
+    let server = UnixDatagram::bind(&path).unwrap();
+    server.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+
+    let handler = Arc::new(build_handler(SyslogTransport::UnixDatagram(path)));
+    let mut builder = CoreLoggerFactoryBuilder::default();
+    builder.set_dispatch_mode(DispatchMode::Synchronous);
+    builder.add_handler(handler.clone());
+    let factory = builder.build();
+    factory.create_from_str("db").log(TestLog("Query {idx}", 7));
+
+    let mut buffer = [0u8; 1024];
+    let len = server.recv(&mut buffer).unwrap();
+    let message = std::str::from_utf8(&buffer[..len]).unwrap();
+    assert!(message.starts_with("<14>1 2024-05-01T10:00:00.123Z host my_app "), "{message}");
+    assert!(message.ends_with(" db [params@32473 idx=\"7\"] Query 7"), "{message}");
+}
+
+#[test]
+fn test_unix_datagram_failures() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.sock");
+    assert!(SyslogHandlerBuilder::new(SyslogTransport::UnixDatagram(path.clone())).build().is_err());
+
+    let server = UnixDatagram::bind(&path).unwrap();
+    server.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut builder = SyslogHandlerBuilder::new(SyslogTransport::UnixDatagram(path.clone()));
+    builder.set_backoff(Duration::from_millis(200), Duration::from_secs(1));
+    let handler = builder.build().unwrap();
+    drop(server);
+    std::fs::remove_file(&path).unwrap();
+    handler.handle(&build_log(LogLevel::Info, "Lost", &[]));
+    assert_eq!(handler.failed_logs(), 1);
+    assert_eq!(handler.backoff(), Duration::from_millis(200));
+
+    // The daemon is back, but the handler still backs off.
+    let server = UnixDatagram::bind(&path).unwrap();
+    server.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+    handler.handle(&build_log(LogLevel::Info, "Skipped", &[]));
+    assert_eq!(handler.failed_logs(), 2);
+    let mut buffer = [0u8; 1024];
+    assert!(server.recv(&mut buffer).is_err());
+
+    // Once the backoff expires, the handler reconnects.
+    thread::sleep(Duration::from_millis(250));
+    server.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    handler.handle(&build_log(LogLevel::Info, "Found", &[]));
+    let len = server.recv(&mut buffer).unwrap();
+    assert!(std::str::from_utf8(&buffer[..len]).unwrap().ends_with(" - - Found"));
+    assert_eq!(handler.failed_logs(), 2);
+    assert_eq!(handler.backoff(), Duration::ZERO);
+}